@@ -1,20 +1,8 @@
 -- Basic schema
-
-CREATE TABLE IF NOT EXISTS iss_fetch_log (
-    id BIGSERIAL PRIMARY KEY,
-    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-    source_url TEXT NOT NULL,
-    payload JSONB NOT NULL
-);
-
-CREATE TABLE IF NOT EXISTS telemetry_legacy (
-    id BIGSERIAL PRIMARY KEY,
-    recorded_at TIMESTAMPTZ NOT NULL,
-    voltage NUMERIC(6,2) NOT NULL,
-    temp NUMERIC(6,2) NOT NULL,
-    source_file TEXT NOT NULL,
-    is_valid BOOLEAN NOT NULL
-);
+--
+-- Tables owned by rust_iss (iss_fetch_log, osdr_items, space_cache, telemetry_legacy,
+-- jwst_observations, astro_events) are created by its versioned migrations in
+-- services/rust-iss/migrations, applied at startup or with `rust_iss migrate`.
 
 CREATE TABLE IF NOT EXISTS cms_pages (
     id BIGSERIAL PRIMARY KEY,
@@ -45,25 +33,3 @@ INSERT INTO cms_blocks(slug, title, content, is_active)
 VALUES
 ('dashboard_experiment', 'Космические факты', '<div class="alert alert-info"><h5>Интересные факты о космосе</h5><ul><li>МКС движется со скоростью около 28 000 км/ч</li><li>JWST видит свет, излученный 13.5 млрд лет назад</li><li>Астрономические события можно наблюдать с Земли</li></ul><p><small>Обновлено: ' || NOW() || '</small></p></div>', TRUE)
 ON CONFLICT DO NOTHING;
-
--- OSDR
-CREATE TABLE IF NOT EXISTS osdr_items(
-    id BIGSERIAL PRIMARY KEY,
-    dataset_id TEXT,
-    title TEXT,
-    status TEXT,
-    updated_at TIMESTAMPTZ,
-    inserted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
-    raw JSONB NOT NULL
-);
-CREATE UNIQUE INDEX IF NOT EXISTS ux_osdr_dataset_id
- ON osdr_items(dataset_id) WHERE dataset_id IS NOT NULL;
-
--- универсальный кэш космоданных
-CREATE TABLE IF NOT EXISTS space_cache(
-    id BIGSERIAL PRIMARY KEY,
-    source TEXT NOT NULL,
-    fetched_at TIMESTAMPTZ NOT NULL DEFAULT now(),
-    payload JSONB NOT NULL
-);
-CREATE INDEX IF NOT EXISTS ix_space_cache_source ON space_cache(source,fetched_at DESC);
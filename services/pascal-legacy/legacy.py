@@ -4,20 +4,23 @@ import random
 import time
 import datetime
 import csv
-import psycopg2
 from openpyxl import Workbook
 from openpyxl.styles import NamedStyle
 
 def get_env(key, default):
     return os.getenv(key, default)
 
-def generate_and_insert():
-    # Output directory
+def publish(tmp_path, final_path):
+    # rust_iss ignores dot-files, so the rename makes the file visible atomically
+    os.replace(tmp_path, final_path)
+
+def generate():
+    # Output directory (drop directory scanned by rust_iss)
     out_dir = get_env('CSV_OUT_DIR', '/data/csv')
     os.makedirs(out_dir, exist_ok=True)
 
     # Generate filename
-    now = datetime.datetime.now()
+    now = datetime.datetime.now(datetime.timezone.utc)
     timestamp_str = now.strftime('%Y%m%d_%H%M%S')
     csv_filename = f'telemetry_{timestamp_str}.csv'
     xlsx_filename = f'telemetry_{timestamp_str}.xlsx'
@@ -25,68 +28,37 @@ def generate_and_insert():
     xlsx_path = os.path.join(out_dir, xlsx_filename)
 
     # Generate data
-    recorded_at_unix = int(time.time())
+    recorded_at_unix = int(now.timestamp())
     voltage = round(3.2 + random.random() * (12.6 - 3.2), 2)
     temp = round(-50 + random.random() * 130, 2)
     is_valid = random.choice([True, False])
-    source_file = csv_filename
 
     # Generate CSV
-    with open(csv_path, 'w', newline='') as csvfile:
+    tmp_csv = os.path.join(out_dir, f'.{csv_filename}.tmp')
+    with open(tmp_csv, 'w', newline='') as csvfile:
         writer = csv.writer(csvfile)
         writer.writerow(['recorded_at', 'voltage', 'temp', 'is_valid', 'source_file'])
-        writer.writerow([recorded_at_unix, voltage, temp, 'TRUE' if is_valid else 'FALSE', source_file])
+        writer.writerow([recorded_at_unix, voltage, temp, 'TRUE' if is_valid else 'FALSE', csv_filename])
+    publish(tmp_csv, csv_path)
 
-    # Generate XLSX with formulas
+    # Generate XLSX with literal values (formulas have no cached results to import)
     wb = Workbook()
     ws = wb.active
     ws.title = 'Telemetry'
 
-    # Headers
-    ws['A1'] = 'recorded_at'
-    ws['B1'] = 'voltage'
-    ws['C1'] = 'temp'
-    ws['D1'] = 'is_valid'
-    ws['E1'] = 'source_file'
-
-    # Data with formulas
-    ws['A2'] = '=NOW()'  # Value substitution for date/time
-    ws['B2'] = f'=3.2 + RAND() * {12.6 - 3.2}'  # Voltage with RAND
-    ws['C2'] = f'=-50 + RAND() * 130'  # Temp with RAND
-    ws['D2'] = '=TRUE' if is_valid else '=FALSE'  # Logical
-    ws['E2'] = f'="telemetry_" & TEXT(NOW(), "yyyymmdd_hhmmss") & ".xlsx"'  # Dynamic filename
+    ws.append(['recorded_at', 'voltage', 'temp', 'is_valid', 'source_file'])
+    ws.append([now.replace(tzinfo=None), voltage, temp, is_valid, xlsx_filename])
 
     # Style for datetime
     date_style = NamedStyle(name='datetime', number_format='YYYY-MM-DD HH:MM:SS')
     wb.add_named_style(date_style)
     ws['A2'].style = date_style
 
-    wb.save(xlsx_path)
-
-    # Database insertion
-    host = get_env('PGHOST', 'db')
-    port = get_env('PGPORT', '5432')
-    user = get_env('PGUSER', 'monouser')
-    password = get_env('PGPASSWORD', 'monopass')
-    dbname = get_env('PGDATABASE', 'monolith')
-
-    conn = psycopg2.connect(
-        host=host,
-        port=port,
-        user=user,
-        password=password,
-        dbname=dbname
-    )
-    cur = conn.cursor()
-    cur.execute(
-        "INSERT INTO telemetry_legacy (recorded_at, voltage, temp, source_file, is_valid) VALUES (%s, %s, %s, %s, %s)",
-        (now, voltage, temp, source_file, is_valid)
-    )
-    conn.commit()
-    cur.close()
-    conn.close()
+    tmp_xlsx = os.path.join(out_dir, f'.{xlsx_filename}.tmp')
+    wb.save(tmp_xlsx)
+    publish(tmp_xlsx, xlsx_path)
 
-    print(f'[python] Generated {csv_filename} and {xlsx_filename}, inserted into DB')
+    print(f'[python] Generated {csv_filename} and {xlsx_filename}')
 
 def main():
     random.seed()
@@ -95,7 +67,7 @@ def main():
     print(f'[python] Legacy generator started (period = {period_sec} sec)')
 
     while True:
-        generate_and_insert()
+        generate()
         print(f'[python] {datetime.datetime.now().strftime("%H:%M:%S")} — done')
         time.sleep(period_sec)
 
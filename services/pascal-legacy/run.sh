@@ -1,4 +1,4 @@
 #!/usr/bin/env bash
 set -e
-echo "[python] running legacy CSV and XLSX generator and importer"
+echo "[python] running legacy CSV and XLSX generator"
 python3 legacy.py
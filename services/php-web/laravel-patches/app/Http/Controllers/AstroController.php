@@ -23,12 +23,9 @@ class AstroController extends Controller
     {
         try {
             $validated = $r->validate([
-                'lat' => 'nullable|numeric',
-                'lon' => 'nullable|numeric',
-                'elevation' => 'nullable|numeric',
-                'from_date' => 'nullable|date',
-                'to_date' => 'nullable|date',
-                'time' => 'nullable|string',
+                'observer' => 'nullable|string|max:64',
+                'from_date' => 'nullable|date_format:Y-m-d',
+                'to_date' => 'nullable|date_format:Y-m-d',
             ]);
 
             $dto = $this->astroService->getEvents($validated);
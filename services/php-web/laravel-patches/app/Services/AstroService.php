@@ -2,50 +2,50 @@
 
 namespace App\Services;
 
-use App\Clients\AstroClient;
+use App\Clients\RustClient;
 use App\DTOs\AstroEventsDto;
 
 class AstroService
 {
-    private AstroClient $client;
+    private RustClient $client;
 
-    public function __construct(AstroClient $client)
+    public function __construct(RustClient $client)
     {
         $this->client = $client;
     }
 
     public function getEvents(array $params = []): AstroEventsDto
     {
-        $lat = (float)($params['lat'] ?? 65.9558);
-        $lon = (float)($params['lon'] ?? 37.6171);
-        $elev = (float)($params['elevation'] ?? 7);
-        $from = $params['from_date'] ?? '2025-11-29';
-        $to = $params['to_date'] ?? '2026-11-29';
-        $time = $params['time'] ?? '00:00:00';
-        $output = $params['output'] ?? 'rows';
-
-        $rows = $this->client->getEvents([
-            'lat' => $lat,
-            'lon' => $lon,
-            'elevation' => $elev,
-            'from_date' => $from,
-            'to_date' => $to,
-            'time' => $time,
-            'output' => $output,
-        ]);
-
-        $dates = [
-            'from' => $from . 'T' . $time . '.000+03:00',
-            'to' => $to . 'T' . $time . '.000+03:00'
-        ];
-        $observer = [
-            'location' => [
-                'longitude' => $lon,
-                'latitude' => $lat,
-                'elevation' => $elev
-            ]
-        ];
-
-        return new AstroEventsDto($dates, $observer, $rows);
+        $from = $params['from_date'] ?? date('Y-m-d');
+        $to = $params['to_date'] ?? date('Y-m-d', strtotime('+30 days'));
+
+        // События заранее собираются rust_iss по настроенным точкам наблюдения (ASTRO_OBSERVERS)
+        $query = ['from' => $from, 'to' => $to];
+        if (!empty($params['observer'])) {
+            $query['observer'] = $params['observer'];
+        }
+
+        $data = $this->client->getJson('astro/events', $query);
+        $events = $data['events'] ?? [];
+
+        // Группируем события по телам в формате строк AstronomyAPI, который ожидает фронтенд
+        $rows = [];
+        foreach ($events as $event) {
+            $body = $event['body'] ?? 'unknown';
+            if (!isset($rows[$body])) {
+                $rows[$body] = ['body' => ['id' => $body, 'name' => ucfirst($body)], 'events' => []];
+            }
+            $details = $event['details'] ?? [];
+            $rows[$body]['events'][] = [
+                'type' => $event['event_type'] ?? null,
+                'eventHighlights' => ['peak' => ['date' => $details['peak'] ?? $event['occurs_at'] ?? null]],
+                'extraInfo' => $details['extra'] ?? ($details['phase'] ?? null ? ['phase' => $details['phase']] : []),
+            ];
+        }
+
+        $dates = ['from' => $from, 'to' => $to];
+        $observer = ['name' => $query['observer'] ?? ($events[0]['observer'] ?? null)];
+
+        return new AstroEventsDto($dates, $observer, array_values($rows));
     }
 }
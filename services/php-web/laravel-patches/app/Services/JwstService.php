@@ -2,16 +2,16 @@
 
 namespace App\Services;
 
-use App\Clients\JwstClient;
+use App\Clients\RustClient;
 use App\DTOs\JwstFeedDto;
 
 class JwstService
 {
-    protected $jwstClient;
+    private RustClient $client;
 
-    public function __construct(JwstClient $jwstClient)
+    public function __construct(RustClient $client)
     {
-        $this->jwstClient = $jwstClient;
+        $this->client = $client;
     }
 
     public function getFeaturedObservation(): ?array
@@ -22,29 +22,23 @@ class JwstService
             return $selectedObs;
         }
 
-        $resp = $this->jwstClient->get('all/type/jpg', ['page' => 1, 'perPage' => 1]);
-        $list = $resp['body'] ?? ($resp['data'] ?? (is_array($resp) ? $resp : []));
-
-        if (!empty($list) && is_array($list)) {
-            $item = $list[0];
-            if (is_array($item)) {
-                $url = $this->pickImageUrl($item);
-                if ($url) {
-                    $instList = $this->extractInstruments($item);
-
-                    return [
-                        'url' => $url,
-                        'obs_id' => (string)($item['observation_id'] ?? $item['observationId'] ?? ''),
-                        'program' => (string)($item['program'] ?? ''),
-                        'suffix' => (string)($item['details']['suffix'] ?? $item['suffix'] ?? ''),
-                        'instruments' => $instList,
-                        'title' => trim($item['title'] ?? ''),
-                        'description' => trim($item['description'] ?? ''),
-                        'link' => $item['location'] ?? $url,
-                        'details' => $item['details'] ?? [],
-                    ];
-                }
-            }
+        $resp = $this->client->getJson('jwst/feed', ['page' => 1, 'perPage' => 1]);
+        $item = $resp['items'][0] ?? null;
+
+        if (is_array($item) && !empty($item['image_url'])) {
+            $raw = is_array($item['raw'] ?? null) ? $item['raw'] : [];
+
+            return [
+                'url' => $item['image_url'],
+                'obs_id' => (string)($item['observation_id'] ?? ''),
+                'program' => (string)($item['program'] ?? ''),
+                'suffix' => (string)($item['suffix'] ?? ''),
+                'instruments' => $item['instruments'] ?? [],
+                'title' => trim($raw['title'] ?? ''),
+                'description' => trim($raw['description'] ?? ($raw['details']['description'] ?? '')),
+                'link' => $raw['location'] ?? $item['image_url'],
+                'details' => $raw['details'] ?? [],
+            ];
         }
 
         return null;
@@ -84,71 +78,47 @@ class JwstService
         $src = $params['source'] ?? 'jpg';
         $sfx = trim($params['suffix'] ?? '');
         $prog = trim($params['program'] ?? '');
-        $instF = strtoupper(trim($params['instrument'] ?? ''));
+        $instF = trim($params['instrument'] ?? '');
         $page = max(1, (int)($params['page'] ?? 1));
         $per = max(1, min(60, (int)($params['perPage'] ?? 24)));
 
-        $path = 'all/type/jpg';
-        if ($src === 'suffix' && $sfx !== '') {
-            $path = 'all/suffix/' . ltrim($sfx, '/');
-        }
+        // Данные берутся из кэша rust_iss, а не напрямую из api.jwstapi.com
+        $query = ['page' => $page, 'perPage' => $per];
         if ($src === 'program' && $prog !== '') {
-            $path = 'program/id/' . rawurlencode($prog);
+            $query['program'] = $prog;
+        }
+        if ($instF !== '') {
+            $query['instrument'] = $instF;
+        }
+        if ($src === 'suffix' && $sfx !== '') {
+            $query['suffix'] = $sfx;
         }
 
-        $resp = $this->jwstClient->get($path, ['page' => $page, 'perPage' => $per]);
-        $list = $resp['body'] ?? ($resp['data'] ?? (is_array($resp) ? $resp : []));
+        $resp = $this->client->getJson('jwst/feed', $query);
 
         $items = [];
-        foreach ($list as $it) {
-            if (!is_array($it)) continue;
-
-            $url = $this->pickImageUrl($it);
-            if (!$url) continue;
+        foreach (($resp['items'] ?? []) as $it) {
+            if (!is_array($it) || empty($it['image_url'])) continue;
 
-            $instList = $this->extractInstruments($it);
-            if ($instF && $instList && !in_array($instF, $instList, true)) continue;
+            $suffix = (string)($it['suffix'] ?? '');
 
+            $instList = $it['instruments'] ?? [];
             $items[] = [
-                'url' => $url,
-                'obs' => (string)($it['observation_id'] ?? $it['observationId'] ?? ''),
+                'url' => $it['image_url'],
+                'obs' => (string)($it['observation_id'] ?? ''),
                 'program' => (string)($it['program'] ?? ''),
-                'suffix' => (string)($it['details']['suffix'] ?? $it['suffix'] ?? ''),
+                'suffix' => $suffix,
                 'inst' => $instList,
                 'caption' => trim(
-                    (($it['observation_id'] ?? '') ?: ($it['id'] ?? '')) .
+                    ($it['observation_id'] ?? '') .
                     ' · P' . ($it['program'] ?? '-') .
-                    (($it['details']['suffix'] ?? '') ? ' · ' . $it['details']['suffix'] : '') .
+                    ($suffix ? ' · ' . $suffix : '') .
                     ($instList ? ' · ' . implode('/', $instList) : '')
                 ),
-                'link' => $it['location'] ?? $url,
+                'link' => $it['raw']['location'] ?? $it['image_url'],
             ];
-            if (count($items) >= $per) break;
         }
 
-        return new JwstFeedDto($path, count($items), $items);
-    }
-
-    private function pickImageUrl(array $item): ?string
-    {
-        $loc = $item['location'] ?? $item['url'] ?? null;
-        $thumb = $item['thumbnail'] ?? null;
-        foreach ([$loc, $thumb] as $u) {
-            if (is_string($u) && preg_match('~\.(jpg|jpeg|png)(\?.*)?$~i', $u)) {
-                return $u;
-            }
-        }
-        return $this->jwstClient->pickImageUrl($item);
-    }
-
-    private function extractInstruments(array $item): array
-    {
-        $instList = [];
-        foreach (($item['details']['instruments'] ?? []) as $I) {
-            if (is_array($I) && !empty($I['instrument'])) {
-                $instList[] = strtoupper($I['instrument']);
-            }
-        }
-        return $instList;
+        return new JwstFeedDto('jwst/feed', count($items), $items);
     }
 }
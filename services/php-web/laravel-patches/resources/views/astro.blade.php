@@ -9,12 +9,9 @@
         <div class="d-flex justify-content-between align-items-center mb-2">
           <h5 class="card-title m-0 fade-in">Астрономические события (AstronomyAPI)</h5>
           <form id="astroForm" class="d-flex gap-2 align-items-center flex-nowrap">
-            <input type="number" step="0.0001" class="form-control form-control-sm" name="lat" value="65.9558" placeholder="lat" title="широта">
-            <input type="number" step="0.0001" class="form-control form-control-sm" name="lon" value="37.6171" placeholder="lon" title="долгота">
-            <input type="number" step="0.1" class="form-control form-control-sm" name="elevation" value="7" style="width:90px" title="высота (м)">
-            <input type="date" class="form-control form-control-sm" name="from_date" value="2025-11-29" placeholder="from_date" title="дата начала">
-            <input type="date" class="form-control form-control-sm" name="to_date" value="2026-11-29" placeholder="to_date" title="дата окончания">
-            <input type="time" class="form-control form-control-sm" name="time" value="00:00:00" placeholder="time" title="время">
+            <input type="text" class="form-control form-control-sm" name="observer" value="" placeholder="observer" title="точка наблюдения (ASTRO_OBSERVERS)">
+            <input type="date" class="form-control form-control-sm" name="from_date" value="{{ date('Y-m-d') }}" placeholder="from_date" title="дата начала">
+            <input type="date" class="form-control form-control-sm" name="to_date" value="{{ date('Y-m-d', strtotime('+30 days')) }}" placeholder="to_date" title="дата окончания">
             <button class="btn btn-sm btn-primary" type="submit">Показать</button>
           </form>
         </div>
@@ -125,7 +122,7 @@ document.addEventListener('DOMContentLoaded', () => {
   });
 
   // автозагрузка
-  load({lat: form.lat.value, lon: form.lon.value, elevation: form.elevation.value, from_date: form.from_date.value, to_date: form.to_date.value, time: form.time.value});
+  load({observer: form.observer.value, from_date: form.from_date.value, to_date: form.to_date.value});
 
   // Sorting and filtering functionality
   let sortDirection = {};
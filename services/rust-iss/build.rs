@@ -0,0 +1,4 @@
+// Re-embed migrations when files under migrations/ change
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS iss_fetch_log;
@@ -0,0 +1,7 @@
+-- ISS positions fetched from wheretheiss.at
+CREATE TABLE IF NOT EXISTS iss_fetch_log (
+    id BIGSERIAL PRIMARY KEY,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    source_url TEXT NOT NULL,
+    payload JSONB NOT NULL
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS osdr_items;
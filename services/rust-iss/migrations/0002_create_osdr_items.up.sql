@@ -0,0 +1,12 @@
+-- NASA OSDR datasets
+CREATE TABLE IF NOT EXISTS osdr_items (
+    id BIGSERIAL PRIMARY KEY,
+    dataset_id TEXT,
+    title TEXT,
+    status TEXT,
+    updated_at TIMESTAMPTZ,
+    inserted_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    raw JSONB NOT NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS ux_osdr_dataset_id
+    ON osdr_items(dataset_id) WHERE dataset_id IS NOT NULL;
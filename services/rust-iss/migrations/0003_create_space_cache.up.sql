@@ -0,0 +1,8 @@
+-- Latest payloads of the scheduled space sources (APOD, NeoWs, DONKI, SpaceX)
+CREATE TABLE IF NOT EXISTS space_cache (
+    id BIGSERIAL PRIMARY KEY,
+    source TEXT NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    payload JSONB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS ix_space_cache_source ON space_cache(source, fetched_at DESC);
@@ -0,0 +1,11 @@
+-- Legacy telemetry ingested from the CSV/XLSX drop directory
+CREATE TABLE IF NOT EXISTS telemetry_legacy (
+    id BIGSERIAL PRIMARY KEY,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    voltage NUMERIC(6,2) NOT NULL,
+    temp NUMERIC(6,2) NOT NULL,
+    source_file TEXT NOT NULL,
+    is_valid BOOLEAN NOT NULL
+);
+CREATE INDEX IF NOT EXISTS ix_telemetry_legacy_source_file ON telemetry_legacy(source_file);
+CREATE INDEX IF NOT EXISTS ix_telemetry_legacy_recorded_at ON telemetry_legacy(recorded_at);
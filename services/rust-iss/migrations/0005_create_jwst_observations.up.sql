@@ -0,0 +1,14 @@
+-- JWST observations with image URLs and program IDs
+CREATE TABLE IF NOT EXISTS jwst_observations (
+    id BIGSERIAL PRIMARY KEY,
+    observation_id TEXT NOT NULL,
+    program TEXT NOT NULL,
+    instruments TEXT[] NOT NULL DEFAULT '{}',
+    suffix TEXT,
+    image_url TEXT NOT NULL UNIQUE,
+    thumbnail_url TEXT,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    raw JSONB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS ix_jwst_observations_program ON jwst_observations(program, fetched_at DESC);
+CREATE INDEX IF NOT EXISTS ix_jwst_observations_instruments ON jwst_observations USING GIN(instruments);
@@ -0,0 +1,13 @@
+-- Astronomical events (eclipses, rise/set, moon phases) per observer location
+CREATE TABLE IF NOT EXISTS astro_events (
+    id BIGSERIAL PRIMARY KEY,
+    observer TEXT NOT NULL,
+    body TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    occurs_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (observer, body, event_type, occurs_at)
+);
+CREATE INDEX IF NOT EXISTS ix_astro_events_occurs_at ON astro_events(observer, occurs_at);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS ix_iss_fetch_log_fetched_at;
+DROP TABLE IF EXISTS compaction_runs;
+DROP TABLE IF EXISTS iss_position_hourly;
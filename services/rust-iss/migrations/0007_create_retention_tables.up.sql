@@ -0,0 +1,27 @@
+-- Hourly aggregates of ISS positions older than the raw retention window
+CREATE TABLE IF NOT EXISTS iss_position_hourly (
+    hour TIMESTAMPTZ PRIMARY KEY,
+    samples INTEGER NOT NULL,
+    first_at TIMESTAMPTZ NOT NULL,
+    last_at TIMESTAMPTZ NOT NULL,
+    -- Position of the first sample in the hour
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    latitude_min DOUBLE PRECISION,
+    latitude_max DOUBLE PRECISION,
+    altitude_avg DOUBLE PRECISION,
+    velocity_avg DOUBLE PRECISION
+);
+
+-- Outcome of every retention and compaction run
+CREATE TABLE IF NOT EXISTS compaction_runs (
+    id BIGSERIAL PRIMARY KEY,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL,
+    rows_removed BIGINT NOT NULL,
+    bytes_reclaimed BIGINT NOT NULL,
+    report JSONB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS ix_compaction_runs_started_at ON compaction_runs(started_at DESC);
+
+CREATE INDEX IF NOT EXISTS ix_iss_fetch_log_fetched_at ON iss_fetch_log(fetched_at);
@@ -0,0 +1,2 @@
+ALTER TABLE space_cache DROP COLUMN IF EXISTS last_seen_at;
+ALTER TABLE space_cache DROP COLUMN IF EXISTS payload_hash;
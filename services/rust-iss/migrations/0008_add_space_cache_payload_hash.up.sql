@@ -0,0 +1,11 @@
+-- Content hash of the payload; jsonb text output is canonical, so equal
+-- documents hash equally regardless of upstream key order or whitespace
+ALTER TABLE space_cache
+    ADD COLUMN IF NOT EXISTS payload_hash TEXT GENERATED ALWAYS AS (md5(payload::text)) STORED;
+
+-- fetched_at is when this payload first appeared, last_seen_at when it was last fetched
+ALTER TABLE space_cache ADD COLUMN IF NOT EXISTS last_seen_at TIMESTAMPTZ;
+UPDATE space_cache SET last_seen_at = fetched_at WHERE last_seen_at IS NULL;
+ALTER TABLE space_cache
+    ALTER COLUMN last_seen_at SET DEFAULT now(),
+    ALTER COLUMN last_seen_at SET NOT NULL;
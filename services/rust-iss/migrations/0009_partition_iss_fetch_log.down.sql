@@ -0,0 +1,18 @@
+CREATE TABLE iss_fetch_log_unpartitioned (
+    id BIGINT PRIMARY KEY DEFAULT nextval('iss_fetch_log_id_seq'),
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    source_url TEXT NOT NULL,
+    payload JSONB NOT NULL
+);
+ALTER SEQUENCE iss_fetch_log_id_seq OWNED BY iss_fetch_log_unpartitioned.id;
+
+INSERT INTO iss_fetch_log_unpartitioned (id, fetched_at, source_url, payload)
+SELECT id, fetched_at, source_url, payload FROM iss_fetch_log;
+
+-- Drops every partition along with the parent
+DROP TABLE iss_fetch_log;
+DROP FUNCTION IF EXISTS iss_fetch_log_ensure_partition(TIMESTAMPTZ);
+
+ALTER TABLE iss_fetch_log_unpartitioned RENAME TO iss_fetch_log;
+ALTER INDEX iss_fetch_log_unpartitioned_pkey RENAME TO iss_fetch_log_pkey;
+CREATE INDEX IF NOT EXISTS ix_iss_fetch_log_fetched_at ON iss_fetch_log(fetched_at);
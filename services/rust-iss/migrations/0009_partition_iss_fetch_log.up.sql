@@ -0,0 +1,50 @@
+-- Range-partition ISS history by calendar month (UTC) so time-bounded reads
+-- only touch recent partitions and retention can drop whole months
+DROP INDEX IF EXISTS ix_iss_fetch_log_fetched_at;
+ALTER TABLE iss_fetch_log RENAME TO iss_fetch_log_unpartitioned;
+ALTER INDEX iss_fetch_log_pkey RENAME TO iss_fetch_log_unpartitioned_pkey;
+
+-- The partition key has to be part of the primary key
+CREATE TABLE iss_fetch_log (
+    id BIGINT NOT NULL DEFAULT nextval('iss_fetch_log_id_seq'),
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    source_url TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    PRIMARY KEY (id, fetched_at)
+) PARTITION BY RANGE (fetched_at);
+ALTER SEQUENCE iss_fetch_log_id_seq OWNED BY iss_fetch_log.id;
+CREATE INDEX ix_iss_fetch_log_fetched_at ON iss_fetch_log (fetched_at, id);
+
+-- Create the iss_fetch_log_pYYYY_MM partition holding `ts` unless it exists.
+-- Returns the partition name when it was created, NULL otherwise.
+CREATE OR REPLACE FUNCTION iss_fetch_log_ensure_partition(ts TIMESTAMPTZ) RETURNS TEXT
+LANGUAGE plpgsql AS $$
+DECLARE
+    month_start TIMESTAMP := date_trunc('month', ts AT TIME ZONE 'UTC');
+    part TEXT := 'iss_fetch_log_p' || to_char(month_start, 'YYYY_MM');
+BEGIN
+    -- Replicas may race to create the same month
+    PERFORM pg_advisory_xact_lock(hashtext('iss_fetch_log:partitions'));
+    IF to_regclass(part) IS NOT NULL THEN
+        RETURN NULL;
+    END IF;
+    EXECUTE format(
+        'CREATE TABLE %I PARTITION OF iss_fetch_log FOR VALUES FROM (%L) TO (%L)',
+        part, month_start AT TIME ZONE 'UTC', (month_start + INTERVAL '1 month') AT TIME ZONE 'UTC'
+    );
+    RETURN part;
+END
+$$;
+
+-- Partitions for every month with data, through next month
+SELECT iss_fetch_log_ensure_partition(m AT TIME ZONE 'UTC')
+FROM generate_series(
+    date_trunc('month', COALESCE((SELECT min(fetched_at) FROM iss_fetch_log_unpartitioned), now()) AT TIME ZONE 'UTC'),
+    (now() AT TIME ZONE 'UTC') + INTERVAL '1 month',
+    INTERVAL '1 month'
+) AS m;
+
+INSERT INTO iss_fetch_log (id, fetched_at, source_url, payload)
+SELECT id, fetched_at, source_url, payload FROM iss_fetch_log_unpartitioned;
+
+DROP TABLE iss_fetch_log_unpartitioned;
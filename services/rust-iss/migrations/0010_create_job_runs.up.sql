@@ -0,0 +1,12 @@
+-- One row per scheduled background job run
+CREATE TABLE IF NOT EXISTS job_runs (
+    id BIGSERIAL PRIMARY KEY,
+    job TEXT NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    -- NULL while the run is in progress
+    finished_at TIMESTAMPTZ,
+    status TEXT NOT NULL,
+    error TEXT,
+    items BIGINT
+);
+CREATE INDEX IF NOT EXISTS ix_job_runs_job_started_at ON job_runs(job, started_at DESC);
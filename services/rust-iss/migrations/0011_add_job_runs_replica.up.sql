@@ -0,0 +1,2 @@
+-- Replica that ran the job, as set by SCHEDULER_REPLICA_ID
+ALTER TABLE job_runs ADD COLUMN IF NOT EXISTS replica TEXT;
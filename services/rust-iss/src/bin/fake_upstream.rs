@@ -0,0 +1,35 @@
+//! Local stand-in for the NASA, SpaceX and ISS APIs serving recorded fixtures.
+//!
+//! `FAKE_UPSTREAM_DIR` (default `fixtures`) holds files recorded with
+//! `HTTP_FIXTURES=record`; point the service's base URLs at
+//! `http://localhost:$FAKE_UPSTREAM_PORT` (default 8089) to run it offline.
+
+// Recording is HttpClient's half of the module
+#[allow(dead_code)]
+#[path = "../clients/fixtures.rs"]
+mod fixtures;
+
+use std::env;
+use tracing::info;
+use tracing_subscriber::{EnvFilter, FmtSubscriber};
+
+use fixtures::{fake_upstream, FixtureDir};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let subscriber = FmtSubscriber::builder()
+        .with_env_filter(EnvFilter::from_default_env())
+        .finish();
+    let _ = tracing::subscriber::set_global_default(subscriber);
+
+    let dir = FixtureDir::new(env::var("FAKE_UPSTREAM_DIR").unwrap_or_else(|_| "fixtures".to_string()));
+    let port: u16 = env::var("FAKE_UPSTREAM_PORT")
+        .unwrap_or_else(|_| "8089".to_string())
+        .parse()
+        .map_err(|_| anyhow::anyhow!("FAKE_UPSTREAM_PORT must be a valid u16"))?;
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    info!("Serving fixtures from {} on port {}", dir.path().display(), port);
+    axum::serve(listener, fake_upstream(dir)).await?;
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use super::{AstroClient, CircuitBreakers, HttpClient, Result as ClientResult};
+use crate::config::{HttpClientConfig, ObserverLocation};
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// AstronomyAPI Client implementation
+#[derive(Clone)]
+pub struct AstroClientImpl {
+    http_client: HttpClient,
+    base_url: String,
+}
+
+impl AstroClientImpl {
+    /// Create a new AstronomyAPI client authenticated with application ID and secret
+    pub fn new(config: HttpClientConfig, app_id: &str, app_secret: &str) -> Self {
+        Self {
+            http_client: HttpClient::new(config).with_basic_auth(app_id, app_secret),
+            base_url: "https://api.astronomyapi.com/api/v2".to_string(),
+        }
+    }
+
+    /// Create AstronomyAPI client with custom base URL (for testing)
+    pub fn with_base_url(config: HttpClientConfig, app_id: &str, app_secret: &str, base_url: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ..Self::new(config, app_id, app_secret)
+        }
+    }
+
+    /// Share per-host circuit breakers with the other upstream clients
+    pub fn with_circuit_breakers(mut self, breakers: CircuitBreakers) -> Self {
+        self.http_client = self.http_client.with_circuit_breakers(breakers);
+        self
+    }
+}
+
+/// Common observer query parameters
+fn observer_params(observer: &ObserverLocation) -> [(&'static str, String); 3] {
+    [
+        ("latitude", observer.latitude.to_string()),
+        ("longitude", observer.longitude.to_string()),
+        ("elevation", observer.elevation.to_string()),
+    ]
+}
+
+#[async_trait]
+impl AstroClient for AstroClientImpl {
+    async fn fetch_body_events(&self, body: &str, observer: &ObserverLocation, from_date: &str, to_date: &str) -> ClientResult<Value> {
+        let url = format!("{}/bodies/events/{}", self.base_url, body);
+        let location = observer_params(observer);
+        let mut params: Vec<(&str, &str)> = location.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        params.extend([
+            ("from_date", from_date),
+            ("to_date", to_date),
+            ("time", "00:00:00"),
+            ("output", "rows"),
+        ]);
+
+        self.http_client.get_with_retry(&url, &params).await
+    }
+
+    async fn fetch_body_positions(&self, observer: &ObserverLocation, from_date: &str, to_date: &str) -> ClientResult<Value> {
+        let url = format!("{}/bodies/positions", self.base_url);
+        let location = observer_params(observer);
+        let mut params: Vec<(&str, &str)> = location.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        params.extend([
+            ("from_date", from_date),
+            ("to_date", to_date),
+            ("time", "00:00:00"),
+        ]);
+
+        self.http_client.get_with_retry(&url, &params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_astro_client_creation() {
+        let config = HttpClientConfig::default();
+        let client = AstroClientImpl::new(config, "app", "secret");
+        assert_eq!(client.base_url, "https://api.astronomyapi.com/api/v2");
+    }
+
+    #[tokio::test]
+    async fn test_astro_client_with_custom_url() {
+        let config = HttpClientConfig::default();
+        let client = AstroClientImpl::with_base_url(config, "app", "secret", "https://test.astro.api/".to_string());
+        assert_eq!(client.base_url, "https://test.astro.api");
+    }
+
+    #[test]
+    fn test_observer_params() {
+        let observer = ObserverLocation {
+            name: "test".to_string(),
+            latitude: 55.75,
+            longitude: 37.62,
+            elevation: 150.0,
+        };
+        let params = observer_params(&observer);
+        assert_eq!(params[0], ("latitude", "55.75".to_string()));
+        assert_eq!(params[2], ("elevation", "150".to_string()));
+    }
+}
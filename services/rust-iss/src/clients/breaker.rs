@@ -0,0 +1,261 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::config::CircuitBreakerConfig;
+
+/// State of the circuit for one upstream host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests fail fast until the open period is over
+    Open,
+    /// A limited number of probes decide whether to close again
+    HalfOpen,
+}
+
+/// Point-in-time view of a host's circuit, for `/health/upstreams`
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitSnapshot {
+    pub host: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+    /// Calls refused without reaching the host
+    pub rejected: u64,
+    pub opened_at: Option<DateTime<Utc>>,
+    /// When an open circuit lets the next probe through
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    consecutive_failures: u32,
+    total_failures: u64,
+    rejected: u64,
+    opened: Option<(Instant, DateTime<Utc>)>,
+    probes_in_flight: u32,
+}
+
+impl Circuit {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            total_failures: 0,
+            rejected: 0,
+            opened: None,
+            probes_in_flight: 0,
+        }
+    }
+
+    fn open(&mut self) {
+        self.state = CircuitState::Open;
+        self.opened = Some((Instant::now(), Utc::now()));
+        self.probes_in_flight = 0;
+    }
+}
+
+/// Circuit breakers for every upstream host, shared by all clients
+#[derive(Clone)]
+pub struct CircuitBreakers {
+    config: CircuitBreakerConfig,
+    circuits: Arc<Mutex<HashMap<String, Circuit>>>,
+}
+
+/// Permission to call a host; report the outcome with `success` or `failure`.
+/// Dropping it unreported (e.g. a cancelled request) only frees the probe slot.
+pub struct CircuitPermit {
+    breakers: CircuitBreakers,
+    host: String,
+    probe: bool,
+    reported: bool,
+}
+
+impl CircuitPermit {
+    /// The host answered, even if with an error of its own
+    pub fn success(mut self) {
+        self.reported = true;
+        self.breakers.record(&self.host, self.probe, true);
+    }
+
+    /// The host is unreachable, timing out or failing with 5xx
+    pub fn failure(mut self) {
+        self.reported = true;
+        self.breakers.record(&self.host, self.probe, false);
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if !self.reported && self.probe {
+            let mut circuits = self.breakers.circuits.lock().unwrap();
+            if let Some(circuit) = circuits.get_mut(&self.host) {
+                circuit.probes_in_flight = circuit.probes_in_flight.saturating_sub(1);
+            }
+        }
+    }
+}
+
+impl CircuitBreakers {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self { config, circuits: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// Ask to call `host`; `None` while its circuit is open or all probes are taken
+    pub fn try_acquire(&self, host: &str) -> Option<CircuitPermit> {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(host.to_string()).or_insert_with(Circuit::new);
+
+        if circuit.state == CircuitState::Open {
+            let elapsed = circuit.opened.map(|(at, _)| at.elapsed()).unwrap_or_default();
+            if elapsed < self.config.open_duration {
+                circuit.rejected += 1;
+                return None;
+            }
+            info!("Circuit for {} is half-open, probing", host);
+            circuit.state = CircuitState::HalfOpen;
+        }
+
+        let probe = circuit.state == CircuitState::HalfOpen;
+        if probe {
+            if circuit.probes_in_flight >= self.config.half_open_probes {
+                circuit.rejected += 1;
+                return None;
+            }
+            circuit.probes_in_flight += 1;
+        }
+        Some(CircuitPermit { breakers: self.clone(), host: host.to_string(), probe, reported: false })
+    }
+
+    fn record(&self, host: &str, probe: bool, success: bool) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(host.to_string()).or_insert_with(Circuit::new);
+        if probe {
+            circuit.probes_in_flight = circuit.probes_in_flight.saturating_sub(1);
+        }
+
+        if success {
+            if circuit.state != CircuitState::Closed {
+                info!("Circuit for {} closed", host);
+            }
+            circuit.state = CircuitState::Closed;
+            circuit.consecutive_failures = 0;
+            circuit.opened = None;
+            return;
+        }
+
+        circuit.consecutive_failures += 1;
+        circuit.total_failures += 1;
+        match circuit.state {
+            CircuitState::HalfOpen => {
+                warn!("Probe to {} failed, circuit open again for {:?}", host, self.config.open_duration);
+                circuit.open();
+            }
+            CircuitState::Closed if circuit.consecutive_failures >= self.config.failure_threshold => {
+                warn!(
+                    "Circuit for {} opened after {} consecutive failures, failing fast for {:?}",
+                    host, circuit.consecutive_failures, self.config.open_duration
+                );
+                circuit.open();
+            }
+            _ => {}
+        }
+    }
+
+    /// Circuits of every host called so far, sorted by host
+    pub fn snapshot(&self) -> Vec<CircuitSnapshot> {
+        let circuits = self.circuits.lock().unwrap();
+        let open_for = chrono::Duration::from_std(self.config.open_duration).unwrap_or_default();
+        let mut hosts: Vec<CircuitSnapshot> = circuits.iter().map(|(host, c)| CircuitSnapshot {
+            host: host.clone(),
+            state: c.state,
+            consecutive_failures: c.consecutive_failures,
+            total_failures: c.total_failures,
+            rejected: c.rejected,
+            opened_at: c.opened.map(|(_, at)| at),
+            retry_at: c.opened.filter(|_| c.state == CircuitState::Open).map(|(_, at)| at + open_for),
+        }).collect();
+        hosts.sort_by(|a, b| a.host.cmp(&b.host));
+        hosts
+    }
+
+    /// Remaining open time of `host`, if its circuit is open
+    pub fn open_for(&self, host: &str) -> Option<Duration> {
+        let circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.get(host).filter(|c| c.state == CircuitState::Open)?;
+        let (at, _) = circuit.opened?;
+        Some(self.config.open_duration.saturating_sub(at.elapsed()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breakers(open_ms: u64) -> CircuitBreakers {
+        CircuitBreakers::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_millis(open_ms),
+            half_open_probes: 1,
+        })
+    }
+
+    fn state(b: &CircuitBreakers, host: &str) -> CircuitState {
+        b.snapshot().into_iter().find(|s| s.host == host).unwrap().state
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures_per_host() {
+        let b = breakers(60_000);
+        b.try_acquire("a").unwrap().failure();
+        b.try_acquire("a").unwrap().success();
+        b.try_acquire("a").unwrap().failure();
+        assert_eq!(state(&b, "a"), CircuitState::Closed);
+
+        b.try_acquire("a").unwrap().failure();
+        assert_eq!(state(&b, "a"), CircuitState::Open);
+        assert!(b.try_acquire("a").is_none());
+        assert!(b.open_for("a").is_some());
+        // Other hosts are unaffected
+        assert!(b.try_acquire("b").is_some());
+
+        let snapshot = b.snapshot();
+        assert_eq!(snapshot[0].rejected, 1);
+        assert_eq!(snapshot[0].total_failures, 3);
+        assert!(snapshot[0].retry_at.is_some());
+    }
+
+    #[test]
+    fn test_half_open_probe_closes_or_reopens() {
+        let b = breakers(0);
+        b.try_acquire("a").unwrap().failure();
+        b.try_acquire("a").unwrap().failure();
+        assert_eq!(state(&b, "a"), CircuitState::Open);
+
+        // The open period is over: one probe at a time
+        let probe = b.try_acquire("a").unwrap();
+        assert_eq!(state(&b, "a"), CircuitState::HalfOpen);
+        assert!(b.try_acquire("a").is_none());
+        probe.failure();
+        assert_eq!(state(&b, "a"), CircuitState::Open);
+
+        b.try_acquire("a").unwrap().success();
+        assert_eq!(state(&b, "a"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_dropped_probe_frees_its_slot() {
+        let b = breakers(0);
+        b.try_acquire("a").unwrap().failure();
+        b.try_acquire("a").unwrap().failure();
+        drop(b.try_acquire("a").unwrap());
+        assert!(b.try_acquire("a").is_some());
+    }
+}
@@ -0,0 +1,144 @@
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// URLs remembered per client; the least recently refreshed one is forgotten first
+const MAX_ENTRIES: usize = 64;
+
+/// Cache validators sent back by a server
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let get = |name| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        Self {
+            etag: get(reqwest::header::ETAG),
+            last_modified: get(reqwest::header::LAST_MODIFIED),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// `If-None-Match` and `If-Modified-Since` headers revalidating this response
+    pub fn request_headers(&self) -> Vec<(&'static str, &str)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match", etag.as_str()));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(("If-Modified-Since", last_modified.as_str()));
+        }
+        headers
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    validators: Validators,
+    /// Last full body, returned again when the server answers 304; `None`
+    /// for lists too large to keep, where a 304 means nothing to do
+    body: Option<Bytes>,
+    stored: Instant,
+}
+
+/// Validators, and last body where kept, of every URL fetched with a conditional GET
+#[derive(Clone, Default)]
+pub struct ConditionalCache {
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl ConditionalCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Key of a request: the URL and its query without `api_key`, so
+    /// rotating NASA keys still revalidates the same resource
+    pub fn key(url: &str, query_params: &[(&str, &str)]) -> String {
+        let mut params: Vec<_> = query_params.iter().filter(|(k, _)| *k != "api_key").collect();
+        params.sort();
+        let query: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!("{}?{}", url, query.join("&"))
+    }
+
+    pub fn get(&self, key: &str) -> Option<(Validators, Option<Bytes>)> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(key).map(|e| (e.validators.clone(), e.body.clone()))
+    }
+
+    /// Remember a full response; responses without validators are forgotten
+    pub fn store(&self, key: String, validators: Validators, body: Option<Bytes>) {
+        let mut entries = self.entries.lock().unwrap();
+        if validators.is_empty() {
+            entries.remove(&key);
+            return;
+        }
+        if !entries.contains_key(&key) && entries.len() >= MAX_ENTRIES {
+            let oldest = entries.iter().min_by_key(|(_, e)| e.stored).map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, Entry { validators, body, stored: Instant::now() });
+    }
+
+    /// A 304 confirmed the stored response is current
+    pub fn refresh(&self, key: &str) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.stored = Instant::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn etag(tag: &str) -> Validators {
+        Validators { etag: Some(tag.to_string()), last_modified: None }
+    }
+
+    #[test]
+    fn test_key_ignores_api_key_and_param_order() {
+        let a = ConditionalCache::key("https://api.nasa.gov/DONKI/FLR", &[("startDate", "1"), ("endDate", "2"), ("api_key", "A")]);
+        let b = ConditionalCache::key("https://api.nasa.gov/DONKI/FLR", &[("api_key", "B"), ("endDate", "2"), ("startDate", "1")]);
+        assert_eq!(a, b);
+        assert_ne!(a, ConditionalCache::key("https://api.nasa.gov/DONKI/FLR", &[("startDate", "2"), ("endDate", "2")]));
+    }
+
+    #[test]
+    fn test_stores_only_responses_with_validators() {
+        let cache = ConditionalCache::new();
+        cache.store("a".to_string(), etag("\"v1\""), Some(Bytes::from_static(b"[1]")));
+        let (validators, body) = cache.get("a").unwrap();
+        assert_eq!(validators.request_headers(), vec![("If-None-Match", "\"v1\"")]);
+        assert_eq!(body, Some(Bytes::from_static(b"[1]")));
+
+        cache.store("a".to_string(), etag("\"v2\""), None);
+        assert_eq!(cache.get("a"), Some((etag("\"v2\""), None)));
+
+        cache.store("a".to_string(), Validators::default(), Some(Bytes::from_static(b"[2]")));
+        assert!(cache.get("a").is_none());
+    }
+
+    #[test]
+    fn test_evicts_the_least_recently_refreshed_url() {
+        let cache = ConditionalCache::new();
+        for i in 0..MAX_ENTRIES {
+            cache.store(i.to_string(), etag("x"), None);
+        }
+        cache.refresh("0");
+        cache.store("new".to_string(), etag("x"), None);
+        assert!(cache.get("0").is_some());
+        assert!(cache.get("new").is_some());
+        let kept = (0..MAX_ENTRIES).filter(|i| cache.get(&i.to_string()).is_some()).count();
+        assert_eq!(kept, MAX_ENTRIES - 1);
+    }
+}
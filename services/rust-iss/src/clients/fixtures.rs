@@ -0,0 +1,225 @@
+//! Recorded upstream responses, replayed by `HttpClient` and served by the
+//! `fake_upstream` binary. Kept free of crate imports so the binary can
+//! include it as a module.
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Response headers worth keeping; anything else varies between runs
+pub const RECORDED_HEADERS: &[&str] = &[
+    "content-type",
+    "etag",
+    "last-modified",
+    "retry-after",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+];
+
+/// One recorded response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: Value,
+    /// `body` holds the raw text of a response that was not JSON
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub raw: bool,
+}
+
+impl Fixture {
+    pub fn new(status: u16, body: &[u8]) -> Self {
+        match serde_json::from_slice(body) {
+            Ok(body) => Self { status, headers: BTreeMap::new(), body, raw: false },
+            Err(_) => Self {
+                status,
+                headers: BTreeMap::new(),
+                body: Value::String(String::from_utf8_lossy(body).into_owned()),
+                raw: true,
+            },
+        }
+    }
+
+    /// Keep the `RECORDED_HEADERS` among `headers`
+    pub fn with_headers<'a>(mut self, headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        for (name, value) in headers {
+            let name = name.to_ascii_lowercase();
+            if RECORDED_HEADERS.contains(&name.as_str()) {
+                self.headers.insert(name, value.to_string());
+            }
+        }
+        self
+    }
+
+    pub fn body_bytes(&self) -> Vec<u8> {
+        match (&self.body, self.raw) {
+            (Value::String(text), true) => text.clone().into_bytes(),
+            (body, _) => serde_json::to_vec(body).unwrap_or_default(),
+        }
+    }
+}
+
+/// File name of the fixture for a request: its path and query, without the
+/// host so that any mirror replays the same files, and without `api_key`
+pub fn fixture_name(path: &str, query: &[(String, String)]) -> String {
+    let mut params: Vec<_> = query.iter().filter(|(k, _)| k != "api_key").collect();
+    params.sort();
+    let mut name = match path.trim_matches('/') {
+        "" => "index".to_string(),
+        path => path.to_string(),
+    };
+    if !params.is_empty() {
+        let query: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        name = format!("{}__{}", name, query.join("&"));
+    }
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '=') { c } else { '_' })
+        .take(200)
+        .collect();
+    format!("{}.json", name)
+}
+
+/// Directory of fixture files
+#[derive(Debug, Clone)]
+pub struct FixtureDir {
+    dir: PathBuf,
+}
+
+impl FixtureDir {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The fixture recorded for exactly this query, else the one for the
+    /// bare path, which stands in for every date range of a feed
+    pub fn load(&self, path: &str, query: &[(String, String)]) -> io::Result<Option<Fixture>> {
+        for name in [fixture_name(path, query), fixture_name(path, &[])] {
+            match std::fs::read(self.dir.join(&name)) {
+                Ok(bytes) => {
+                    let fixture = serde_json::from_slice(&bytes)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", name, e)))?;
+                    return Ok(Some(fixture));
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn save(&self, path: &str, query: &[(String, String)], fixture: &Fixture) -> io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        let file = self.dir.join(fixture_name(path, query));
+        let json = serde_json::to_vec_pretty(fixture)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&file, json)?;
+        Ok(file)
+    }
+}
+
+/// Stand-in for the upstream APIs serving the fixtures of `dir` on every path.
+/// `If-None-Match` matching a fixture's `etag` is answered with 304.
+// Only the fake_upstream binary and client tests serve fixtures
+#[allow(dead_code)]
+pub fn fake_upstream(dir: FixtureDir) -> Router {
+    Router::new().fallback(serve_fixture).with_state(Arc::new(dir))
+}
+
+async fn serve_fixture(
+    State(dir): State<Arc<FixtureDir>>,
+    uri: Uri,
+    Query(query): Query<Vec<(String, String)>>,
+    request_headers: HeaderMap,
+) -> Response {
+    let fixture = match dir.load(uri.path(), &query) {
+        Ok(Some(fixture)) => fixture,
+        Ok(None) => {
+            let name = fixture_name(uri.path(), &query);
+            tracing::warn!("No fixture for {} (expected {})", uri, name);
+            return (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": format!("no fixture {}", name) })))
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Unreadable fixture for {}: {}", uri, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({ "error": e.to_string() })))
+                .into_response();
+        }
+    };
+
+    let etag = fixture.headers.get("etag");
+    let if_none_match = request_headers.get("if-none-match").and_then(|v| v.to_str().ok());
+    let status = match (etag, if_none_match) {
+        (Some(etag), Some(tag)) if etag == tag => StatusCode::NOT_MODIFIED,
+        _ => StatusCode::from_u16(fixture.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in &fixture.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name.as_str()), HeaderValue::from_str(value)) {
+            headers.insert(name, value);
+        }
+    }
+    if status == StatusCode::NOT_MODIFIED {
+        return (status, headers).into_response();
+    }
+    (status, headers, fixture.body_bytes()).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn temp_dir(name: &str) -> FixtureDir {
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+        FixtureDir::new(std::env::temp_dir().join(format!("rust_iss_{}_{}_{}", name, std::process::id(), nanos)))
+    }
+
+    #[test]
+    fn test_fixture_name_ignores_host_and_api_key() {
+        assert_eq!(fixture_name("/planetary/apod", &query(&[("thumbs", "true"), ("api_key", "SECRET")])), "planetary_apod__thumbs=true.json");
+        assert_eq!(
+            fixture_name("/DONKI/FLR", &query(&[("startDate", "2026-10-13"), ("endDate", "2026-10-18")])),
+            "DONKI_FLR__endDate=2026-10-18_startDate=2026-10-13.json"
+        );
+        assert_eq!(fixture_name("/", &[]), "index.json");
+    }
+
+    #[test]
+    fn test_round_trip_and_path_fallback() {
+        let dir = temp_dir("fixtures");
+        let fixture = Fixture::new(200, br#"[{"flrID": "x"}]"#)
+            .with_headers([("ETag", "\"1\""), ("Date", "today")]);
+        dir.save("/DONKI/FLR", &[], &fixture).unwrap();
+
+        // Any date range falls back to the bare path
+        let loaded = dir.load("/DONKI/FLR", &query(&[("startDate", "2020-01-01")])).unwrap().unwrap();
+        assert_eq!(loaded.body[0]["flrID"], "x");
+        assert_eq!(loaded.headers.keys().collect::<Vec<_>>(), vec!["etag"]);
+        assert!(dir.load("/DONKI/CME", &[]).unwrap().is_none());
+
+        let raw = Fixture::new(502, b"<html>Bad Gateway</html>");
+        assert!(raw.raw);
+        assert_eq!(raw.body_bytes(), b"<html>Bad Gateway</html>");
+        std::fs::remove_dir_all(dir.path()).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use super::{HttpClient, SpaceXClient, Result as ClientResult};
+use super::{CircuitBreakers, HttpClient, SpaceXClient, Result as ClientResult};
 use crate::config::HttpClientConfig;
 use async_trait::async_trait;
 use serde_json::Value;
@@ -8,32 +8,47 @@ use crate::IssClient;
 #[derive(Clone)]
 pub struct IssClientImpl {
     http_client: HttpClient,
-    base_url: String,
+    position_url: String,
 }
 
+/// Position endpoint of the ISS under a wheretheiss.at compatible base URL
+const POSITION_PATH: &str = "/v1/satellites/25544";
+
 impl IssClientImpl {
     /// Create a new ISS client
     pub fn new(config: HttpClientConfig) -> Self {
         Self {
             http_client: HttpClient::new(config),
-            base_url: "https://api.wheretheiss.at".to_string(),
+            position_url: format!("https://api.wheretheiss.at{}", POSITION_PATH),
         }
     }
 
     /// Create ISS client with custom base URL (for testing)
     pub fn with_base_url(config: HttpClientConfig, base_url: String) -> Self {
-        Self {
-            http_client: HttpClient::new(config),
-            base_url,
-        }
+        Self::new(config).with_position_url(format!("{}{}", base_url.trim_end_matches('/'), POSITION_PATH))
+    }
+
+    /// Poll the full position URL, as `WHERE_ISS_URL` holds it
+    pub fn with_position_url(mut self, position_url: String) -> Self {
+        self.position_url = position_url;
+        self
+    }
+
+    /// Share per-host circuit breakers with the other upstream clients
+    pub fn with_circuit_breakers(mut self, breakers: CircuitBreakers) -> Self {
+        self.http_client = self.http_client.with_circuit_breakers(breakers);
+        self
     }
 }
 
 #[async_trait]
 impl IssClient for IssClientImpl {
+    fn position_url(&self) -> &str {
+        &self.position_url
+    }
+
     async fn fetch_iss_position(&self) -> ClientResult<Value> {
-        let url = format!("{}/v1/satellites/25544", self.base_url);
-        self.http_client.get_with_retry(&url, &[]).await
+        self.http_client.get_with_retry(&self.position_url, &[]).await
     }
 
     async fn fetch_iss_position_by_url(&self, url: &str) -> ClientResult<Value> {
@@ -44,19 +59,35 @@ impl IssClient for IssClientImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
+    use crate::clients::{serve_fixtures, test_config};
+    use crate::config::FixtureMode;
 
     #[tokio::test]
     async fn test_iss_client_creation() {
         let config = HttpClientConfig::default();
         let client = IssClientImpl::new(config);
-        assert_eq!(client.base_url, "https://api.wheretheiss.at");
+        assert_eq!(client.position_url(), "https://api.wheretheiss.at/v1/satellites/25544");
     }
 
     #[tokio::test]
     async fn test_iss_client_with_custom_url() {
         let config = HttpClientConfig::default();
-        let client = IssClientImpl::with_base_url(config, "https://test.iss.api".to_string());
-        assert_eq!(client.base_url, "https://test.iss.api");
+        let client = IssClientImpl::with_base_url(config.clone(), "https://test.iss.api/".to_string());
+        assert_eq!(client.position_url(), "https://test.iss.api/v1/satellites/25544");
+
+        let client = IssClientImpl::new(config).with_position_url("https://mirror.test/iss/now".to_string());
+        assert_eq!(client.position_url(), "https://mirror.test/iss/now");
+    }
+
+    #[tokio::test]
+    async fn test_parses_recorded_position() {
+        let url = serve_fixtures("").await;
+        let client = IssClientImpl::with_base_url(test_config(FixtureMode::Off, ""), url.clone());
+        let position = client.fetch_iss_position().await.unwrap();
+        assert_eq!(position["id"], 25544);
+        assert!(position["velocity"].as_f64().unwrap() > 27000.0);
+
+        let missing = client.fetch_iss_position_by_url(&format!("{}/v1/satellites/1", url)).await.unwrap_err();
+        assert!(missing.to_string().contains("404"));
     }
 }
@@ -0,0 +1,338 @@
+//! Incremental deserialization of JSON list responses.
+//!
+//! Body chunks are fed as they arrive to serde on a blocking thread, and every
+//! item is handed over a bounded channel as soon as it is parsed, so a consumer
+//! writing items to the database never holds the whole body or list in memory.
+
+use bytes::{Buf, Bytes};
+use futures_util::StreamExt;
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::io::{self, Read};
+use tokio::sync::mpsc;
+
+use super::{ClientError, Result};
+
+/// Items parsed ahead of the consumer
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Body chunks received ahead of the parser
+const CHUNK_CAPACITY: usize = 8;
+
+/// Keys of object responses wrapping the item list
+const WRAPPER_KEYS: &[&str] = &["items", "results"];
+
+/// Items of a JSON list response, in document order.
+///
+/// Accepted shapes are a top-level array, an object wrapping the array in
+/// `items` or `results`, an object keyed by item id whose values are the
+/// items (the id is added as `id` when the item has none), and finally any
+/// other object, which is a single item.
+///
+/// A body that is cut short, too large or not valid JSON ends the stream
+/// with an `Err` item after the items parsed before the fault.
+pub struct ItemStream {
+    rx: mpsc::Receiver<Result<Value>>,
+    /// Run once the last item has been handed out, unless an error was before it
+    on_complete: Option<Box<dyn FnOnce() + Send>>,
+    failed: bool,
+}
+
+impl ItemStream {
+    /// Deserialize the items of a raw JSON body
+    pub fn from_bytes(body: Bytes) -> Self {
+        Self::spawn(move |emit| {
+            let mut deserializer = serde_json::Deserializer::from_slice(&body);
+            ItemsSeed { emit }.deserialize(&mut deserializer).and_then(|_| deserializer.end()).map_err(parse_error)
+        })
+    }
+
+    /// Deserialize the items of a response body while it downloads, failing
+    /// as soon as more than `limit` bytes have arrived
+    pub fn from_response(response: reqwest::Response, limit: u64) -> Self {
+        let (chunk_tx, chunk_rx) = mpsc::channel(CHUNK_CAPACITY);
+        tokio::spawn(async move {
+            let mut body = response.bytes_stream();
+            let mut received = 0u64;
+            while let Some(chunk) = body.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => {
+                        received += chunk.len() as u64;
+                        if received > limit {
+                            Err(ClientError::ResponseTooLarge(format!("body exceeds the {} byte limit", limit)))
+                        } else {
+                            Ok(chunk)
+                        }
+                    }
+                    Err(e) => Err(ClientError::HttpError(format!("Failed to read response body: {}", e))),
+                };
+                // Stops after a failure or once the parser has gone away
+                let failed = chunk.is_err();
+                if chunk_tx.send(chunk).await.is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        Self::spawn(move |emit| {
+            let mut reader = ChunkReader { rx: chunk_rx, chunk: Bytes::new(), failure: None };
+            let mut deserializer = serde_json::Deserializer::from_reader(&mut reader);
+            let parsed = ItemsSeed { emit }.deserialize(&mut deserializer).and_then(|_| deserializer.end());
+            // A transport or size failure explains the parse error it caused
+            parsed.map_err(|e| reader.failure.take().unwrap_or_else(|| parse_error(e)))
+        })
+    }
+
+    /// Split an already parsed body into its items
+    pub fn from_value(value: Value) -> Self {
+        Self::spawn(move |emit| ItemsSeed { emit }.deserialize(value).map_err(parse_error))
+    }
+
+    /// A list without items
+    pub fn empty() -> Self {
+        let (_, rx) = mpsc::channel(1);
+        Self { rx, on_complete: None, failed: false }
+    }
+
+    /// Call `f` once every item has been handed out without error
+    pub fn on_complete(mut self, f: impl FnOnce() + Send + 'static) -> Self {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    fn spawn<F>(parse: F) -> Self
+    where
+        F: FnOnce(&mut dyn FnMut(Value) -> bool) -> Result<()> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        tokio::task::spawn_blocking(move || {
+            // A dropped receiver stops the parse at the next item
+            let mut emit = |item| tx.blocking_send(Ok(item)).is_ok();
+            if let Err(e) = parse(&mut emit) {
+                if !tx.is_closed() {
+                    let _ = tx.blocking_send(Err(e));
+                }
+            }
+        });
+        Self { rx, on_complete: None, failed: false }
+    }
+
+    /// The next item, `None` once the list is exhausted
+    pub async fn next(&mut self) -> Option<Result<Value>> {
+        let item = self.rx.recv().await;
+        match item {
+            Some(Err(_)) => self.failed = true,
+            None if !self.failed => {
+                if let Some(on_complete) = self.on_complete.take() {
+                    on_complete();
+                }
+            }
+            _ => {}
+        }
+        item
+    }
+}
+
+fn parse_error(e: serde_json::Error) -> ClientError {
+    ClientError::ParseError(format!("Failed to parse JSON item list: {}", e))
+}
+
+/// Blocking reader over the body chunks sent by the download task
+struct ChunkReader {
+    rx: mpsc::Receiver<Result<Bytes>>,
+    chunk: Bytes,
+    /// Why the body ended early, if it did
+    failure: Option<ClientError>,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.rx.blocking_recv() {
+                Some(Ok(chunk)) => self.chunk = chunk,
+                Some(Err(e)) => {
+                    let message = e.to_string();
+                    self.failure = Some(e);
+                    return Err(io::Error::other(message));
+                }
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk[..n]);
+        self.chunk.advance(n);
+        Ok(n)
+    }
+}
+
+/// Deserializes a whole response, emitting its items
+struct ItemsSeed<'a> {
+    emit: &'a mut dyn FnMut(Value) -> bool,
+}
+
+fn stopped<E: de::Error>() -> E {
+    E::custom("item consumer went away")
+}
+
+impl<'de> DeserializeSeed<'de> for ItemsSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ItemsSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON array or object of items")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        while let Some(item) = seq.next_element::<Value>()? {
+            if !(self.emit)(item) {
+                return Err(stopped());
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        // Fields of a single-item response, kept until the document ends
+        let mut single = Map::new();
+        let mut streamed = false;
+        while let Some(key) = map.next_key::<String>()? {
+            // Fields seen before a wrapper array are its metadata
+            if !streamed && WRAPPER_KEYS.contains(&key.as_str()) {
+                match map.next_value_seed(WrapperSeed { emit: &mut *self.emit })? {
+                    None => streamed = true,
+                    Some(other) => {
+                        single.insert(key, other);
+                    }
+                }
+                continue;
+            }
+            if !single.is_empty() {
+                single.insert(key, map.next_value()?);
+                continue;
+            }
+            match map.next_value::<Value>()? {
+                Value::Object(mut item) => {
+                    if !item.contains_key("id") {
+                        item.insert("id".to_string(), Value::String(key));
+                    }
+                    if !(self.emit)(Value::Object(item)) {
+                        return Err(stopped());
+                    }
+                    streamed = true;
+                }
+                // Stray metadata next to keyed items
+                _ if streamed => {}
+                other => {
+                    single.insert(key, other);
+                }
+            }
+        }
+        if !streamed && !(self.emit)(Value::Object(single)) {
+            return Err(stopped());
+        }
+        Ok(())
+    }
+}
+
+/// Value of a wrapper key: its elements are emitted when it is an array,
+/// anything else is handed back whole
+struct WrapperSeed<'a> {
+    emit: &'a mut dyn FnMut(Value) -> bool,
+}
+
+impl<'de> DeserializeSeed<'de> for WrapperSeed<'_> {
+    type Value = Option<Value>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<Option<Value>, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for WrapperSeed<'_> {
+    type Value = Option<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> std::result::Result<Option<Value>, A::Error> {
+        ItemsSeed { emit: self.emit }.visit_seq(seq).map(|_| None)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> std::result::Result<Option<Value>, A::Error> {
+        Value::deserialize(de::value::MapAccessDeserializer::new(map)).map(Some)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> std::result::Result<Option<Value>, E> {
+        Ok(Some(Value::Bool(v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Option<Value>, E> {
+        Ok(Some(Value::from(v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Option<Value>, E> {
+        Ok(Some(Value::from(v)))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Option<Value>, E> {
+        Ok(Some(Value::from(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Option<Value>, E> {
+        Ok(Some(Value::String(v.to_string())))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Option<Value>, E> {
+        Ok(Some(Value::Null))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect(mut items: ItemStream) -> Vec<Value> {
+        let mut all = Vec::new();
+        while let Some(item) = items.next().await {
+            all.push(item.unwrap());
+        }
+        all
+    }
+
+    #[tokio::test]
+    async fn test_streams_every_list_shape() {
+        let array = collect(ItemStream::from_bytes(Bytes::from_static(br#"[{"id": 1}, {"id": 2}]"#))).await;
+        assert_eq!(array.len(), 2);
+
+        let wrapped = collect(ItemStream::from_bytes(Bytes::from_static(br#"{"count": 2, "results": [1, 2]}"#))).await;
+        assert_eq!(wrapped, vec![Value::from(1), Value::from(2)]);
+
+        let keyed = collect(ItemStream::from_value(serde_json::json!({
+            "OSD-1": {"REST_URL": "a"},
+            "OSD-2": {"id": "own", "REST_URL": "b"}
+        })))
+        .await;
+        assert_eq!(keyed[0]["id"], "OSD-1");
+        assert_eq!(keyed[1]["id"], "own");
+
+        let single = collect(ItemStream::from_bytes(Bytes::from_static(br#"{"dataset_id": "1", "meta": {"a": 1}}"#))).await;
+        assert_eq!(single, vec![serde_json::json!({"dataset_id": "1", "meta": {"a": 1}})]);
+    }
+
+    #[tokio::test]
+    async fn test_reports_malformed_body_after_parsed_items() {
+        let mut items = ItemStream::from_bytes(Bytes::from_static(br#"[{"id": 1}, {"id": "#));
+        assert_eq!(items.next().await.unwrap().unwrap()["id"], 1);
+        assert!(matches!(items.next().await, Some(Err(ClientError::ParseError(_)))));
+        assert!(items.next().await.is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use super::{CircuitBreakers, HttpClient, JwstClient, Result as ClientResult};
+use crate::config::HttpClientConfig;
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// JWST API Client implementation
+#[derive(Clone)]
+pub struct JwstClientImpl {
+    http_client: HttpClient,
+    base_url: String,
+    api_key: Option<String>,
+    email: Option<String>,
+}
+
+impl JwstClientImpl {
+    /// Create a new JWST client
+    pub fn new(config: HttpClientConfig) -> Self {
+        Self {
+            http_client: HttpClient::new(config),
+            base_url: "https://api.jwstapi.com".to_string(),
+            api_key: None,
+            email: None,
+        }
+    }
+
+    /// Create JWST client with custom base URL (for testing)
+    pub fn with_base_url(config: HttpClientConfig, base_url: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ..Self::new(config)
+        }
+    }
+
+    /// Share per-host circuit breakers with the other upstream clients
+    pub fn with_circuit_breakers(mut self, breakers: CircuitBreakers) -> Self {
+        self.http_client = self.http_client.with_circuit_breakers(breakers);
+        self
+    }
+
+    /// Set the API key and optional contact email sent with every request
+    pub fn with_credentials(mut self, api_key: Option<String>, email: Option<String>) -> Self {
+        self.api_key = api_key;
+        self.email = email;
+        self
+    }
+
+    async fn get(&self, path: &str, page: u32, per_page: u32) -> ClientResult<Value> {
+        let url = format!("{}/{}", self.base_url, path);
+        let page = page.to_string();
+        let per_page = per_page.to_string();
+        let params = [("page", page.as_str()), ("perPage", per_page.as_str())];
+
+        let mut headers = Vec::new();
+        if let Some(ref key) = self.api_key {
+            headers.push(("x-api-key", key.as_str()));
+        }
+        if let Some(ref email) = self.email {
+            headers.push(("email", email.as_str()));
+        }
+
+        self.http_client.get_with_headers(&url, &params, &headers).await
+    }
+}
+
+#[async_trait]
+impl JwstClient for JwstClientImpl {
+    async fn fetch_images_by_type(&self, file_type: &str, page: u32, per_page: u32) -> ClientResult<Value> {
+        self.get(&format!("all/type/{}", file_type), page, per_page).await
+    }
+
+    async fn fetch_program_images(&self, program_id: &str, page: u32, per_page: u32) -> ClientResult<Value> {
+        self.get(&format!("program/id/{}", program_id), page, per_page).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_jwst_client_creation() {
+        let config = HttpClientConfig::default();
+        let client = JwstClientImpl::new(config);
+        assert_eq!(client.base_url, "https://api.jwstapi.com");
+        assert!(client.api_key.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_jwst_client_with_custom_url() {
+        let config = HttpClientConfig::default();
+        let client = JwstClientImpl::with_base_url(config, "https://test.jwst.api/".to_string())
+            .with_credentials(Some("key".to_string()), None);
+        assert_eq!(client.base_url, "https://test.jwst.api");
+        assert_eq!(client.api_key.as_deref(), Some("key"));
+    }
+}
@@ -1,8 +1,13 @@
 use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde_json::Value;
-use std::time::Duration;
-use crate::config::HttpClientConfig;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+use crate::config::{FixtureMode, HttpClientConfig, ObserverLocation};
 
 /// Common client error type
 #[derive(Debug, Clone)]
@@ -11,6 +16,10 @@ pub enum ClientError {
     TimeoutError(String),
     ParseError(String),
     RateLimitError(String),
+    /// Refused without a request because the host's circuit is open
+    CircuitOpen(String),
+    /// The body is larger than `HTTP_MAX_RESPONSE_BYTES`
+    ResponseTooLarge(String),
 }
 
 impl std::fmt::Display for ClientError {
@@ -20,6 +29,8 @@ impl std::fmt::Display for ClientError {
             ClientError::TimeoutError(msg) => write!(f, "Timeout error: {}", msg),
             ClientError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             ClientError::RateLimitError(msg) => write!(f, "Rate limit error: {}", msg),
+            ClientError::CircuitOpen(msg) => write!(f, "Circuit open: {}", msg),
+            ClientError::ResponseTooLarge(msg) => write!(f, "Response too large: {}", msg),
         }
     }
 }
@@ -28,82 +39,562 @@ impl std::error::Error for ClientError {}
 
 pub type Result<T> = std::result::Result<T, ClientError>;
 
+/// Body of a conditional GET
+#[derive(Debug, Clone)]
+pub struct Fetched {
+    pub value: Value,
+    /// False when the server answered 304 and `value` is the body it sent last time
+    pub changed: bool,
+}
+
+impl Fetched {
+    pub fn fresh(value: Value) -> Self {
+        Self { value, changed: true }
+    }
+
+    pub fn unchanged(value: Value) -> Self {
+        Self { value, changed: false }
+    }
+}
+
+/// Items of a conditional GET of a JSON list
+pub struct FetchedItems {
+    /// Items as the body downloads; empty when the server answered 304
+    pub items: ItemStream,
+    /// False when the server answered 304 and the list is unchanged
+    pub changed: bool,
+}
+
 /// Base HTTP client with common functionality
 #[derive(Clone)]
 pub struct HttpClient {
     client: std::sync::Arc<Client>,
     config: HttpClientConfig,
+    basic_auth: Option<(String, String)>,
+    breakers: CircuitBreakers,
+    quota: Option<NasaQuota>,
+    conditional: ConditionalCache,
+    fixtures: FixtureDir,
 }
 
 impl HttpClient {
     /// Create a new HTTP client with configuration
     pub fn new(config: HttpClientConfig) -> Self {
-        let client = Client::builder()
+        let mut builder = Client::builder()
             .timeout(config.timeout)
-            .user_agent(&config.user_agent)
-            .build()
-            .expect("Failed to build HTTP client");
+            .user_agent(&config.user_agent);
+        // AppConfig::validate has already rejected unusable proxy and TLS settings
+        if let Some(proxy) = config.reqwest_proxy().expect("Invalid HTTP proxy settings") {
+            builder = builder.proxy(proxy);
+        }
+        for certificate in config.tls.root_certificates().expect("Invalid extra root certificates") {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(identity) = config.tls.identity().expect("Invalid client certificate") {
+            // PEM identities are only understood by the rustls backend
+            builder = builder.use_rustls_tls().identity(identity);
+        }
+        let client = builder.build().expect("Failed to build HTTP client");
 
-        Self { client: std::sync::Arc::new(client), config }
+        let breakers = CircuitBreakers::new(config.circuit_breaker.clone());
+        let fixtures = FixtureDir::new(config.fixture_dir.clone());
+        Self {
+            client: std::sync::Arc::new(client),
+            config,
+            basic_auth: None,
+            breakers,
+            quota: None,
+            conditional: ConditionalCache::new(),
+            fixtures,
+        }
+    }
+
+    /// Report the rate-limit headers of requests carrying an `api_key` to `quota`
+    pub fn with_quota(mut self, quota: NasaQuota) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
+    /// Track upstream health in a registry shared with other clients
+    pub fn with_circuit_breakers(mut self, breakers: CircuitBreakers) -> Self {
+        self.breakers = breakers;
+        self
+    }
+
+    /// Send HTTP basic authentication credentials with every request
+    pub fn with_basic_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.basic_auth = Some((username.into(), password.into()));
+        self
     }
 
     /// Make a GET request with retry logic
     pub async fn get_with_retry(&self, url: &str, query_params: &[(&str, &str)]) -> Result<Value> {
-        let mut attempts = 0;
-        let mut last_error = None;
+        self.get_with_headers(url, query_params, &[]).await
+    }
 
-        while attempts < self.config.max_retries {
-            match self.make_request(url, query_params).await {
-                Ok(response) => return Ok(response),
-                Err(e) => {
-                    last_error = Some(e);
-                    attempts += 1;
-                    if attempts < self.config.max_retries {
-                        tokio::time::sleep(self.config.retry_delay).await;
+    /// Make a GET request with extra headers and retry logic.
+    ///
+    /// Only transient failures are retried, with exponential backoff and jitter
+    /// or for as long as the server asks through `Retry-After`.
+    pub async fn get_with_headers(&self, url: &str, query_params: &[(&str, &str)], headers: &[(&str, &str)]) -> Result<Value> {
+        let body = self.send_with_retry(url, query_params, headers, false).await?
+            .body
+            .ok_or_else(|| ClientError::HttpError("HTTP 304 Not Modified to an unconditional request".to_string()))?;
+        parse_json(&body.bytes(self.config.max_response_bytes).await?)
+    }
+
+    /// Make a GET request revalidating the previous response for the same URL.
+    ///
+    /// The `ETag` and `Last-Modified` of the last full response are sent back as
+    /// `If-None-Match` and `If-Modified-Since`; a 304 returns the remembered body
+    /// marked as unchanged.
+    pub async fn get_conditional(&self, url: &str, query_params: &[(&str, &str)]) -> Result<Fetched> {
+        let key = ConditionalCache::key(url, query_params);
+        // Validators remembered without a body (item lists) cannot answer a 304
+        let known = self.conditional.get(&key).and_then(|(validators, body)| Some((validators, body?)));
+        let headers = known.as_ref().map(|(validators, _)| validators.request_headers()).unwrap_or_default();
+
+        let reply = self.send_with_retry(url, query_params, &headers, false).await?;
+        match (reply.body, known) {
+            (Some(body), _) => {
+                let body = body.bytes(self.config.max_response_bytes).await?;
+                let value = parse_json(&body)?;
+                self.conditional.store(key, reply.validators, Some(body));
+                Ok(Fetched::fresh(value))
+            }
+            (None, Some((_, body))) => {
+                debug!("GET {} not modified", url);
+                self.conditional.refresh(&key);
+                Ok(Fetched::unchanged(parse_json(&body)?))
+            }
+            (None, None) => Err(ClientError::HttpError("HTTP 304 Not Modified to an unconditional request".to_string())),
+        }
+    }
+
+    /// Conditional GET of a JSON list whose items are deserialized while the
+    /// body downloads, rather than into a single `Value`.
+    ///
+    /// Only the validators are remembered, and only once every item has been
+    /// read, so a 304 yields no items and a list that failed halfway is
+    /// fetched in full next time.
+    pub async fn get_conditional_items(&self, url: &str, query_params: &[(&str, &str)]) -> Result<FetchedItems> {
+        let key = ConditionalCache::key(url, query_params);
+        let known = self.conditional.get(&key).map(|(validators, _)| validators);
+        let headers = known.as_ref().map(Validators::request_headers).unwrap_or_default();
+
+        let reply = self.send_with_retry(url, query_params, &headers, true).await?;
+        let Some(body) = reply.body else {
+            if known.is_none() {
+                return Err(ClientError::HttpError("HTTP 304 Not Modified to an unconditional request".to_string()));
+            }
+            debug!("GET {} not modified", url);
+            self.conditional.refresh(&key);
+            return Ok(FetchedItems { items: ItemStream::empty(), changed: false });
+        };
+
+        let items = match body {
+            Body::Full(body) => ItemStream::from_bytes(body),
+            Body::Streaming(response) => ItemStream::from_response(response, self.config.max_response_bytes),
+        };
+        let (conditional, validators) = (self.conditional.clone(), reply.validators);
+        let items = items.on_complete(move || conditional.store(key, validators, None));
+        Ok(FetchedItems { items, changed: true })
+    }
+
+    /// GET with retries; `stream` leaves a successful body unread for the caller
+    async fn send_with_retry(&self, url: &str, query_params: &[(&str, &str)], headers: &[(&str, &str)], stream: bool) -> Result<Reply> {
+        let max_attempts = self.config.max_retries.max(1);
+        let host = circuit_host(url);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let permit = match host.as_deref() {
+                Some(host) => match self.breakers.try_acquire(host) {
+                    Some(permit) => Some(permit),
+                    None => {
+                        let wait = self.breakers.open_for(host).unwrap_or_default();
+                        debug!("GET {} refused, circuit for {} is open for another {:?}", url, host, wait);
+                        return Err(ClientError::CircuitOpen(format!("{} is unavailable, retry in {}s", host, wait.as_secs())));
+                    }
+                },
+                None => None,
+            };
+            let started = Instant::now();
+            let failure = match self.make_request(url, query_params, headers, stream).await {
+                Ok(response) => {
+                    if let Some(permit) = permit {
+                        permit.success();
                     }
+                    debug!("GET {} succeeded on attempt {}/{} in {:?}", url, attempt, max_attempts, started.elapsed());
+                    return Ok(response);
+                }
+                Err(failure) => failure,
+            };
+            // Only an unreachable or failing host counts against its circuit
+            if let Some(permit) = permit {
+                if failure.retryable && !matches!(failure.error, ClientError::RateLimitError(_)) {
+                    permit.failure();
+                } else {
+                    permit.success();
+                }
+            }
+
+            if !failure.retryable {
+                warn!("GET {} failed permanently on attempt {}/{}: {}", url, attempt, max_attempts, failure.error);
+                return Err(failure.error);
+            }
+            if attempt >= max_attempts {
+                warn!("GET {} failed on attempt {}/{}, giving up: {}", url, attempt, max_attempts, failure.error);
+                return Err(failure.error);
+            }
+            let delay = match failure.retry_after {
+                Some(wait) if wait > self.config.retry_max_delay => {
+                    warn!("GET {} asked to retry after {:?}, more than the {:?} limit: {}", url, wait, self.config.retry_max_delay, failure.error);
+                    return Err(failure.error);
+                }
+                Some(wait) => wait,
+                None => backoff_delay(self.config.retry_delay, self.config.retry_max_delay, attempt, jitter()),
+            };
+            warn!("GET {} failed on attempt {}/{} after {:?}: {}; retrying in {:?}", url, attempt, max_attempts, started.elapsed(), failure.error, delay);
+            tokio::time::sleep(delay).await;
+
+            // Every attempt spends NASA quota, so each retry reserves its own call
+            if let Some((quota, key)) = self.quota_key(query_params) {
+                if !quota.reserve_retry(key) {
+                    warn!("GET {} not retried, NASA quota exhausted: {}", url, failure.error);
+                    return Err(failure.error);
                 }
             }
         }
+    }
 
-        Err(last_error.unwrap_or_else(|| ClientError::HttpError("Max retries exceeded".to_string())))
+    /// NASA quota and the key a request draws on, when it carries an `api_key`
+    fn quota_key<'a>(&'a self, query_params: &[(&str, &'a str)]) -> Option<(&'a NasaQuota, &'a str)> {
+        let quota = self.quota.as_ref()?;
+        query_params.iter().find(|(k, _)| *k == "api_key").map(|(_, key)| (quota, *key))
     }
 
-    /// Make a single HTTP request
-    async fn make_request(&self, url: &str, query_params: &[(&str, &str)]) -> Result<Value> {
+    /// Make a single HTTP request, or answer it from a fixture when replaying
+    async fn make_request(&self, url: &str, query_params: &[(&str, &str)], headers: &[(&str, &str)], stream: bool) -> std::result::Result<Reply, AttemptError> {
+        let quota_key = self.quota_key(query_params);
+        let release_quota = || {
+            if let Some((quota, key)) = quota_key {
+                quota.release(key);
+            }
+        };
+
+        if self.config.fixture_mode == FixtureMode::Replay {
+            let (path, query) = fixture_request(url, query_params);
+            let fixture = match self.fixtures.load(&path, &query) {
+                Ok(Some(fixture)) => fixture,
+                Ok(None) => {
+                    release_quota();
+                    return Err(AttemptError::permanent(ClientError::HttpError(format!(
+                        "No fixture {} in {} for {}", fixtures::fixture_name(&path, &query), self.fixtures.path().display(), url
+                    ))));
+                }
+                Err(e) => {
+                    release_quota();
+                    return Err(AttemptError::permanent(ClientError::HttpError(format!("Unreadable fixture for {}: {}", url, e))));
+                }
+            };
+            let status = reqwest::StatusCode::from_u16(fixture.status)
+                .unwrap_or(reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+            let mut response_headers = reqwest::header::HeaderMap::new();
+            for (name, value) in &fixture.headers {
+                if let (Ok(name), Ok(value)) = (
+                    reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+                    reqwest::header::HeaderValue::from_str(value),
+                ) {
+                    response_headers.insert(name, value);
+                }
+            }
+            let body = fixture.body_bytes();
+            if body.len() as u64 > self.config.max_response_bytes {
+                release_quota();
+                return Err(AttemptError::permanent(too_large(body.len() as u64, self.config.max_response_bytes)));
+            }
+            return self.handle_response(url, quota_key, status, &response_headers, Body::Full(Bytes::from(body)));
+        }
+
         let mut request = self.client.get(url);
 
         for (key, value) in query_params {
             request = request.query(&[(key, value)]);
         }
 
-        let response = request.send().await
-            .map_err(|e| ClientError::HttpError(format!("Request failed: {}", e)))?;
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        if let Some((ref username, ref password)) = self.basic_auth {
+            request = request.basic_auth(username, Some(password));
+        }
+
+        let response = request.send().await.map_err(|e| {
+            release_quota();
+            if e.is_timeout() {
+                AttemptError::transient(ClientError::TimeoutError(format!("Request timed out: {}", e)))
+            } else if e.is_builder() {
+                AttemptError::permanent(ClientError::HttpError(format!("Invalid request: {}", e)))
+            } else {
+                AttemptError::transient(ClientError::HttpError(format!("Request failed: {}", e)))
+            }
+        })?;
+
+        let status = response.status();
+        let response_headers = response.headers().clone();
+        let limit = self.config.max_response_bytes;
 
-        if !response.status().is_success() {
-            if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                return Err(ClientError::RateLimitError("Rate limit exceeded".to_string()));
+        // Recording needs the whole body for its fixture
+        if stream && status.is_success() && self.config.fixture_mode != FixtureMode::Record {
+            if let Some(length) = response.content_length().filter(|length| *length > limit) {
+                release_quota();
+                let e = too_large(length, limit);
+                warn!("GET {} refused: {}", url, e);
+                return Err(AttemptError::permanent(e));
+            }
+            return self.handle_response(url, quota_key, status, &response_headers, Body::Streaming(response));
+        }
+
+        let body = match read_body(response, limit).await {
+            Ok(body) => body,
+            // The status alone decides a failed response
+            Err(_) if !status.is_success() => Bytes::new(),
+            Err(e @ ClientError::ResponseTooLarge(_)) => {
+                release_quota();
+                warn!("GET {} refused: {}", url, e);
+                return Err(AttemptError::permanent(e));
+            }
+            Err(e) => {
+                release_quota();
+                return Err(AttemptError::transient(e));
+            }
+        };
+
+        // A 304 only makes sense next to the response it revalidated
+        if self.config.fixture_mode == FixtureMode::Record && status != reqwest::StatusCode::NOT_MODIFIED {
+            let (path, query) = fixture_request(url, query_params);
+            let fixture = Fixture::new(status.as_u16(), &body).with_headers(
+                response_headers.iter().filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+            );
+            match self.fixtures.save(&path, &query, &fixture) {
+                Ok(file) => debug!("Recorded GET {} -> {} to {}", url, status, file.display()),
+                Err(e) => warn!("Failed to record fixture for {}: {}", url, e),
+            }
+        }
+
+        self.handle_response(url, quota_key, status, &response_headers, Body::Full(body))
+    }
+
+    /// Classify a response, reporting its rate-limit headers to the NASA quota
+    fn handle_response(
+        &self,
+        url: &str,
+        quota_key: Option<(&NasaQuota, &str)>,
+        status: reqwest::StatusCode,
+        headers: &reqwest::header::HeaderMap,
+        body: Body,
+    ) -> std::result::Result<Reply, AttemptError> {
+        let retry_after = headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| parse_retry_after(v, Utc::now()));
+        let remaining = headers
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<i64>().ok());
+        if let Some(remaining) = remaining {
+            debug!("GET {} -> {}, rate limit remaining: {}", url, status, remaining);
+        }
+        if let Some((quota, key)) = quota_key {
+            let limit = headers
+                .get("x-ratelimit-limit")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<i64>().ok());
+            match remaining {
+                _ if status == reqwest::StatusCode::TOO_MANY_REQUESTS => quota.exhausted(key),
+                Some(remaining) => quota.observe(key, limit, remaining),
+                None => quota.release(key),
             }
-            return Err(ClientError::HttpError(format!("HTTP {}: {}", response.status(), response.status().canonical_reason().unwrap_or("Unknown"))));
         }
 
-        response.json().await
-            .map_err(|e| ClientError::ParseError(format!("Failed to parse JSON response: {}", e)))
+        let validators = Validators::from_headers(headers);
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Reply { body: None, validators });
+        }
+
+        if !status.is_success() {
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                // NASA keys have an hourly quota: with nothing left and no
+                // Retry-After, retrying within seconds cannot succeed
+                let exhausted = remaining == Some(0) && retry_after.is_none();
+                let message = match retry_after {
+                    Some(wait) => format!("Rate limit exceeded, retry after {}s", wait.as_secs()),
+                    None if exhausted => "Rate limit exceeded, quota exhausted".to_string(),
+                    None => "Rate limit exceeded".to_string(),
+                };
+                return Err(AttemptError {
+                    error: ClientError::RateLimitError(message),
+                    retryable: !exhausted,
+                    retry_after,
+                });
+            }
+            return Err(AttemptError {
+                error: ClientError::HttpError(format!("HTTP {}: {}", status, status.canonical_reason().unwrap_or("Unknown"))),
+                retryable: is_retryable_status(status),
+                retry_after,
+            });
+        }
+
+        Ok(Reply { body: Some(body), validators })
+    }
+}
+
+fn parse_json(body: &[u8]) -> Result<Value> {
+    serde_json::from_slice(body).map_err(|e| ClientError::ParseError(format!("Failed to parse JSON response: {}", e)))
+}
+
+fn too_large(size: u64, limit: u64) -> ClientError {
+    ClientError::ResponseTooLarge(format!("body of {} bytes exceeds the {} byte limit", size, limit))
+}
+
+/// Read a response body, giving up as soon as it grows past `limit` bytes
+async fn read_body(mut response: reqwest::Response, limit: u64) -> Result<Bytes> {
+    if let Some(length) = response.content_length().filter(|length| *length > limit) {
+        return Err(too_large(length, limit));
+    }
+    let mut body = Vec::with_capacity(response.content_length().unwrap_or(0) as usize);
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| ClientError::HttpError(format!("Failed to read response body: {}", e)))?
+    {
+        if (body.len() + chunk.len()) as u64 > limit {
+            return Err(ClientError::ResponseTooLarge(format!("body exceeds the {} byte limit", limit)));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(body))
+}
+
+/// Path and full query of a request, which name its fixture
+fn fixture_request(url: &str, query_params: &[(&str, &str)]) -> (String, Vec<(String, String)>) {
+    let params = query_params.iter().map(|(k, v)| (k.to_string(), v.to_string()));
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => (
+            parsed.path().to_string(),
+            parsed.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).chain(params).collect(),
+        ),
+        Err(_) => (url.to_string(), params.collect()),
+    }
+}
+
+/// Response of a successful attempt
+struct Reply {
+    /// Raw JSON, `None` for 304 Not Modified
+    body: Option<Body>,
+    validators: Validators,
+}
+
+/// Body of a successful response
+enum Body {
+    Full(Bytes),
+    /// Still on the connection, for the caller to stream
+    Streaming(reqwest::Response),
+}
+
+impl Body {
+    /// The whole body, read up to `limit` bytes if it is still streaming
+    async fn bytes(self, limit: u64) -> Result<Bytes> {
+        match self {
+            Body::Full(body) => Ok(body),
+            Body::Streaming(response) => read_body(response, limit).await,
+        }
+    }
+}
+
+/// `host[:port]` of a URL, the key of its circuit breaker
+fn circuit_host(url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+/// A failed attempt and whether repeating it may succeed
+#[derive(Debug)]
+struct AttemptError {
+    error: ClientError,
+    retryable: bool,
+    /// Wait requested by the server through `Retry-After`
+    retry_after: Option<Duration>,
+}
+
+impl AttemptError {
+    fn transient(error: ClientError) -> Self {
+        Self { error, retryable: true, retry_after: None }
+    }
+
+    fn permanent(error: ClientError) -> Self {
+        Self { error, retryable: false, retry_after: None }
     }
 }
 
+/// Server errors and request timeouts may go away; other 4xx will not
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    (status.is_server_error() && status != reqwest::StatusCode::NOT_IMPLEMENTED)
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+}
+
+/// `Retry-After` as delta-seconds or an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Exponential backoff capped at `max`, with the upper half jittered by
+/// `jitter` in `[0, 1)` so that replicas do not retry in lockstep
+fn backoff_delay(base: Duration, max: Duration, attempt: u32, jitter: f64) -> Duration {
+    let exp = base.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1))).min(max);
+    exp / 2 + (exp / 2).mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+/// Uniform value in `[0, 1)` from the randomly keyed std hasher
+pub(crate) fn jitter() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
 /// NASA API Client trait
 #[async_trait]
 pub trait NasaClient {
-    async fn fetch_osdr_datasets(&self) -> Result<Value>;
-    async fn fetch_apod(&self, api_key: Option<&str>) -> Result<Value>;
-    async fn fetch_neo_feed(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> Result<Value>;
-    async fn fetch_donki_flr(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> Result<Value>;
-    async fn fetch_donki_cme(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> Result<Value>;
+    async fn fetch_osdr_datasets(&self) -> Result<Fetched>;
+    /// The OSDR dataset list item by item; clients without a streaming
+    /// transport split the whole list
+    async fn stream_osdr_datasets(&self) -> Result<FetchedItems> {
+        let fetched = self.fetch_osdr_datasets().await?;
+        let items = if fetched.changed { ItemStream::from_value(fetched.value) } else { ItemStream::empty() };
+        Ok(FetchedItems { items, changed: fetched.changed })
+    }
+    async fn fetch_apod(&self, api_key: Option<&str>) -> Result<Fetched>;
+    async fn fetch_neo_feed(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> Result<Fetched>;
+    async fn fetch_donki_flr(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> Result<Fetched>;
+    async fn fetch_donki_cme(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> Result<Fetched>;
 }
 
 /// ISS Position API Client trait
 #[async_trait]
 pub trait IssClient {
+    /// URL polled by `fetch_iss_position`, stored with each position
+    fn position_url(&self) -> &str;
     async fn fetch_iss_position(&self) -> Result<Value>;
     async fn fetch_iss_position_by_url(&self, url: &str) -> Result<Value>;
 }
@@ -111,16 +602,426 @@ pub trait IssClient {
 /// SpaceX API Client trait
 #[async_trait]
 pub trait SpaceXClient {
-    async fn fetch_next_launch(&self) -> Result<Value>;
+    async fn fetch_next_launch(&self) -> Result<Fetched>;
     async fn fetch_latest_launch(&self) -> Result<Value>;
     async fn fetch_upcoming_launches(&self) -> Result<Value>;
 }
 
+/// JWST API Client trait
+#[async_trait]
+pub trait JwstClient {
+    async fn fetch_images_by_type(&self, file_type: &str, page: u32, per_page: u32) -> Result<Value>;
+    async fn fetch_program_images(&self, program_id: &str, page: u32, per_page: u32) -> Result<Value>;
+}
+
+/// AstronomyAPI Client trait
+#[async_trait]
+pub trait AstroClient {
+    async fn fetch_body_events(&self, body: &str, observer: &ObserverLocation, from_date: &str, to_date: &str) -> Result<Value>;
+    async fn fetch_body_positions(&self, observer: &ObserverLocation, from_date: &str, to_date: &str) -> Result<Value>;
+}
+
 // Re-export client implementations
+pub mod breaker;
+pub mod conditional;
+pub mod fixtures;
+pub mod items;
+pub mod quota;
 pub mod nasa;
 pub mod iss;
 pub mod spacex;
+pub mod jwst;
+pub mod astro;
 
 pub use nasa::NasaClientImpl;
 pub use iss::IssClientImpl;
 pub use spacex::SpaceXClientImpl;
+pub use jwst::JwstClientImpl;
+pub use astro::AstroClientImpl;
+pub use breaker::{CircuitBreakers, CircuitState};
+pub use quota::{NasaQuota, QuotaPriority};
+pub use items::ItemStream;
+use conditional::{ConditionalCache, Validators};
+use fixtures::{Fixture, FixtureDir};
+
+/// Config for client tests: quick retries, replaying `fixtures/<dir>` in replay mode
+#[cfg(test)]
+pub(crate) fn test_config(fixture_mode: FixtureMode, dir: &str) -> HttpClientConfig {
+    HttpClientConfig {
+        max_retries: 3,
+        retry_delay: Duration::from_millis(1),
+        retry_max_delay: Duration::from_millis(50),
+        fixture_mode,
+        fixture_dir: std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(dir),
+        ..HttpClientConfig::default()
+    }
+}
+
+/// Fake upstream serving `fixtures/<dir>` on a local port, returning its base URL
+#[cfg(test)]
+pub(crate) async fn serve_fixtures(dir: &str) -> String {
+    let dir = FixtureDir::new(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(dir));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, fixtures::fake_upstream(dir)).await.unwrap() });
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CircuitBreakerConfig;
+    use axum::{extract::State, http::{HeaderMap, StatusCode}, response::IntoResponse, routing::get, Json, Router};
+    use std::sync::{atomic::{AtomicU32, Ordering}, Arc};
+
+    /// Forward proxy stand-in echoing the absolute URI and credentials it was sent
+    async fn serve_proxy() -> String {
+        async fn echo(uri: axum::http::Uri, headers: HeaderMap) -> Json<Value> {
+            let auth = headers.get("proxy-authorization").and_then(|v| v.to_str().ok()).map(str::to_string);
+            Json(serde_json::json!({ "uri": uri.to_string(), "proxy_authorization": auth }))
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, Router::new().fallback(echo)).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_configured_proxy() {
+        let proxy_url = serve_proxy().await;
+        let config = HttpClientConfig {
+            max_retries: 1,
+            proxy: Some(crate::config::ProxyConfig {
+                url: proxy_url,
+                username: Some("svc".to_string()),
+                password: Some("secret".to_string()),
+                no_proxy: Some("direct.invalid".to_string()),
+            }),
+            ..test_config(crate::config::FixtureMode::Off, "")
+        };
+        let client = HttpClient::new(config);
+
+        let echoed = client.get_with_retry("http://upstream.invalid/v1/satellites/25544", &[]).await.unwrap();
+        assert_eq!(echoed["uri"], "http://upstream.invalid/v1/satellites/25544");
+        // base64("svc:secret")
+        assert_eq!(echoed["proxy_authorization"], "Basic c3ZjOnNlY3JldA==");
+
+        // Hosts on the no-proxy list are resolved directly, and .invalid never resolves
+        assert!(client.get_with_retry("http://direct.invalid/", &[]).await.is_err());
+    }
+
+    #[test]
+    fn test_builds_with_extra_roots_and_client_certificate() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("tls");
+        let config = HttpClientConfig {
+            tls: crate::config::TlsConfig {
+                extra_root_certs: vec![dir.join("client.pem")],
+                client_cert: Some(dir.join("client.pem")),
+                client_key: Some(dir.join("client.key")),
+            },
+            ..HttpClientConfig::default()
+        };
+        // Panics if reqwest rejects the identity for the selected TLS backend
+        HttpClient::new(config);
+    }
+
+    #[test]
+    fn test_retry_classification() {
+        assert!(is_retryable_status(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(reqwest::StatusCode::REQUEST_TIMEOUT));
+        assert!(!is_retryable_status(reqwest::StatusCode::NOT_IMPLEMENTED));
+        assert!(!is_retryable_status(reqwest::StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(reqwest::StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:27:30Z").unwrap().with_timezone(&Utc);
+        assert_eq!(parse_retry_after(" 120 ", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now), Some(Duration::from_secs(30)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_and_is_capped() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_millis(1000);
+        assert_eq!(backoff_delay(base, max, 1, 0.0), Duration::from_millis(50));
+        assert_eq!(backoff_delay(base, max, 1, 1.0), Duration::from_millis(100));
+        assert_eq!(backoff_delay(base, max, 3, 0.5), Duration::from_millis(300));
+        assert_eq!(backoff_delay(base, max, 10, 1.0), max);
+        assert_eq!(backoff_delay(base, max, u32::MAX, 1.0), max);
+        for _ in 0..100 {
+            assert!((0.0..1.0).contains(&jitter()));
+        }
+    }
+
+    type Script = Vec<(StatusCode, Vec<(&'static str, &'static str)>)>;
+
+    /// Serve `responses` in order (the last one repeats), counting requests
+    async fn upstream(responses: Script) -> (String, Arc<AtomicU32>) {
+        let hits = Arc::new(AtomicU32::new(0));
+        let state = (Arc::new(responses), hits.clone());
+        let app = Router::new()
+            .route("/", get(|State((responses, hits)): State<(Arc<Script>, Arc<AtomicU32>)>| async move {
+                let n = hits.fetch_add(1, Ordering::SeqCst) as usize;
+                let (status, headers) = responses[n.min(responses.len() - 1)].clone();
+                let mut map = HeaderMap::new();
+                for (name, value) in headers {
+                    map.insert(name, value.parse().unwrap());
+                }
+                (status, map, Json(serde_json::json!({ "ok": true })))
+            }))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, hits)
+    }
+
+    fn client() -> HttpClient {
+        HttpClient::new(test_config(FixtureMode::Off, ""))
+    }
+
+    #[tokio::test]
+    async fn test_refuses_oversized_responses_without_retrying() {
+        let limited = |config: HttpClientConfig| HttpClient::new(HttpClientConfig { max_response_bytes: 8, ..config });
+
+        // Declared by Content-Length
+        let (url, hits) = upstream(vec![(StatusCode::OK, vec![])]).await;
+        let err = limited(test_config(FixtureMode::Off, "")).get_with_retry(&url, &[]).await.unwrap_err();
+        assert!(matches!(err, ClientError::ResponseTooLarge(_)));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Counted while a chunked body streams in
+        let chunks = || async {
+            let chunks: Vec<std::result::Result<&str, std::io::Error>> = vec![Ok("[1, 2, "), Ok("3, 4]")];
+            axum::body::Body::from_stream(futures_util::stream::iter(chunks))
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, Router::new().route("/", get(chunks))).await.unwrap() });
+        let err = limited(test_config(FixtureMode::Off, "")).get_with_retry(&url, &[]).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds the 8 byte limit"));
+        assert_eq!(client().get_with_retry(&url, &[]).await.unwrap(), serde_json::json!([1, 2, 3, 4]));
+
+        // And for replayed fixtures
+        let replay = limited(test_config(FixtureMode::Replay, ""));
+        let err = replay.get_with_retry("https://api.wheretheiss.at/v1/satellites/25544", &[]).await.unwrap_err();
+        assert!(matches!(err, ClientError::ResponseTooLarge(_)));
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_fails_fast() {
+        let (url, hits) = upstream(vec![(StatusCode::BAD_GATEWAY, vec![])]).await;
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_secs(60),
+            half_open_probes: 1,
+        });
+        let http = client().with_circuit_breakers(breakers.clone());
+
+        // The second failed attempt opens the circuit and stops the retries
+        assert!(matches!(http.get_with_retry(&url, &[]).await, Err(ClientError::CircuitOpen(_))));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        assert!(matches!(http.get_with_retry(&url, &[]).await, Err(ClientError::CircuitOpen(_))));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let host = circuit_host(&url).unwrap();
+        let snapshot = breakers.snapshot();
+        assert_eq!(snapshot[0].host, host);
+        assert_eq!(snapshot[0].state, CircuitState::Open);
+        assert_eq!(snapshot[0].rejected, 2);
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_retried() {
+        let (url, hits) = upstream(vec![
+            (StatusCode::SERVICE_UNAVAILABLE, vec![]),
+            (StatusCode::TOO_MANY_REQUESTS, vec![("retry-after", "0")]),
+            (StatusCode::OK, vec![]),
+        ]).await;
+        assert_eq!(client().get_with_retry(&url, &[]).await.unwrap()["ok"], true);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_reserve_nasa_quota_per_attempt() {
+        let limit = |remaining: &'static str| vec![("x-ratelimit-limit", "100"), ("x-ratelimit-remaining", remaining)];
+        let (url, hits) = upstream(vec![
+            (StatusCode::SERVICE_UNAVAILABLE, limit("40")),
+            (StatusCode::SERVICE_UNAVAILABLE, limit("39")),
+            (StatusCode::OK, limit("38")),
+        ]).await;
+        let quota = NasaQuota::new(vec!["KEY".to_string()]);
+        let http = client().with_quota(quota.clone());
+        let key = quota.acquire(QuotaPriority::Normal).unwrap();
+        http.get_with_retry(&url, &[("api_key", &key)]).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        assert_eq!(quota.snapshot()[0].calls, 3);
+        assert_eq!(quota.snapshot()[0].remaining, Some(38));
+
+        // A key with nothing left is not retried
+        let (url, hits) = upstream(vec![(StatusCode::SERVICE_UNAVAILABLE, limit("0"))]).await;
+        let key = quota.acquire(QuotaPriority::High).unwrap();
+        assert!(http.get_with_retry(&url, &[("api_key", &key)]).await.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(quota.snapshot()[0].calls, 4);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_are_not_retried() {
+        let (url, hits) = upstream(vec![(StatusCode::NOT_FOUND, vec![])]).await;
+        assert!(matches!(client().get_with_retry(&url, &[]).await, Err(ClientError::HttpError(_))));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_get_revalidates_with_etag() {
+        // Answers 304 to the current ETag, counting full responses
+        let full = Arc::new(AtomicU32::new(0));
+        let app = Router::new()
+            .route("/", get(|State(full): State<Arc<AtomicU32>>, headers: HeaderMap| async move {
+                if headers.get("if-none-match").is_some_and(|v| v == "\"v1\"") {
+                    return StatusCode::NOT_MODIFIED.into_response();
+                }
+                full.fetch_add(1, Ordering::SeqCst);
+                ([("etag", "\"v1\"")], Json(serde_json::json!({ "version": 1 }))).into_response()
+            }))
+            .with_state(full.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let http = client();
+        let first = http.get_conditional(&url, &[("api_key", "A")]).await.unwrap();
+        assert!(first.changed);
+        // Another key revalidates the same resource
+        let second = http.get_conditional(&url, &[("api_key", "B")]).await.unwrap();
+        assert!(!second.changed);
+        assert_eq!(second.value["version"], 1);
+        assert_eq!(full.load(Ordering::SeqCst), 1);
+
+        // Plain GETs neither send nor expect validators
+        assert_eq!(http.get_with_retry(&url, &[]).await.unwrap()["version"], 1);
+        assert_eq!(full.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unexpected_not_modified_is_an_error() {
+        let (url, _) = upstream(vec![(StatusCode::NOT_MODIFIED, vec![])]).await;
+        assert!(matches!(client().get_conditional(&url, &[]).await, Err(ClientError::HttpError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_items_stream_before_the_body_finishes() {
+        // Holds the end of the list back until released, answering 304 to its ETag
+        let release = Arc::new(tokio::sync::Notify::new());
+        let full = Arc::new(AtomicU32::new(0));
+        let app = Router::new()
+            .route("/", get(|State((release, full)): State<(Arc<tokio::sync::Notify>, Arc<AtomicU32>)>, headers: HeaderMap| async move {
+                if headers.get("if-none-match").is_some_and(|v| v == "\"v1\"") {
+                    return StatusCode::NOT_MODIFIED.into_response();
+                }
+                full.fetch_add(1, Ordering::SeqCst);
+                let head = futures_util::stream::iter([Ok::<_, std::io::Error>(r#"[{"id": 1}, "#)]);
+                let tail = futures_util::stream::once(async move {
+                    release.notified().await;
+                    Ok(r#"{"id": 2}]"#)
+                });
+                ([("etag", "\"v1\"")], axum::body::Body::from_stream(futures_util::StreamExt::chain(head, tail))).into_response()
+            }))
+            .with_state((release.clone(), full.clone()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let http = client();
+        let mut fetched = http.get_conditional_items(&url, &[]).await.unwrap();
+        assert!(fetched.changed);
+        let first = tokio::time::timeout(Duration::from_secs(5), fetched.items.next()).await.unwrap();
+        assert_eq!(first.unwrap().unwrap()["id"], 1);
+
+        // Validators are only kept once the whole list has been read
+        assert!(http.get_conditional_items(&url, &[]).await.unwrap().changed);
+        assert_eq!(full.load(Ordering::SeqCst), 2);
+
+        release.notify_one();
+        assert_eq!(fetched.items.next().await.unwrap().unwrap()["id"], 2);
+        assert!(fetched.items.next().await.is_none());
+
+        let mut unchanged = http.get_conditional_items(&url, &[]).await.unwrap();
+        assert!(!unchanged.changed);
+        assert!(unchanged.items.next().await.is_none());
+        assert_eq!(full.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_items_stop_at_the_size_limit() {
+        let chunks = || async {
+            let chunks: Vec<std::result::Result<&str, std::io::Error>> = vec![Ok("[1, "), Ok("2, 3, 4, 5]")];
+            axum::body::Body::from_stream(futures_util::stream::iter(chunks))
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, Router::new().route("/", get(chunks))).await.unwrap() });
+
+        let http = HttpClient::new(HttpClientConfig { max_response_bytes: 8, ..test_config(FixtureMode::Off, "") });
+        let mut items = http.get_conditional_items(&url, &[]).await.unwrap().items;
+        let mut parsed = Vec::new();
+        let err = loop {
+            match items.next().await.unwrap() {
+                Ok(item) => parsed.push(item),
+                Err(e) => break e,
+            }
+        };
+        assert!(matches!(err, ClientError::ResponseTooLarge(_)));
+        assert!(parsed.len() <= 1);
+        assert!(items.next().await.is_none());
+
+        // A declared length over the limit fails before any item
+        let (url, _) = upstream(vec![(StatusCode::OK, vec![])]).await;
+        assert!(matches!(http.get_conditional_items(&url, &[]).await, Err(ClientError::ResponseTooLarge(_))));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_responses_replay_offline() {
+        let (url, hits) = upstream(vec![
+            (StatusCode::OK, vec![("etag", "\"1\""), ("date", "Sun, 18 Oct 2026 06:00:00 GMT")]),
+        ]).await;
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+        let dir = std::env::temp_dir().join(format!("rust_iss_record_{}_{}", std::process::id(), nanos));
+        let config = |fixture_mode| HttpClientConfig { fixture_mode, fixture_dir: dir.clone(), ..test_config(fixture_mode, "") };
+
+        let recorder = HttpClient::new(config(FixtureMode::Record));
+        let live = recorder.get_with_retry(&url, &[("day", "1"), ("api_key", "SECRET")]).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        let recorded = std::fs::read_to_string(dir.join("index__day=1.json")).unwrap();
+        assert!(!recorded.contains("SECRET"));
+        assert!(!recorded.contains("date"));
+
+        // Replay needs neither the server nor the same host
+        let replayer = HttpClient::new(config(FixtureMode::Replay));
+        let replayed = replayer.get_with_retry("https://upstream.invalid/", &[("day", "1")]).await.unwrap();
+        assert_eq!(replayed, live);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        let missing = replayer.get_with_retry("https://upstream.invalid/other", &[]).await.unwrap_err();
+        assert!(missing.to_string().contains("No fixture other.json"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limits_beyond_the_backoff_cap_fail_fast() {
+        let (url, hits) = upstream(vec![(StatusCode::TOO_MANY_REQUESTS, vec![("retry-after", "3600")])]).await;
+        assert!(matches!(client().get_with_retry(&url, &[]).await, Err(ClientError::RateLimitError(_))));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // An exhausted NASA quota is not retried either
+        let (url, hits) = upstream(vec![(StatusCode::TOO_MANY_REQUESTS, vec![("x-ratelimit-remaining", "0")])]).await;
+        let err = client().get_with_retry(&url, &[]).await.unwrap_err();
+        assert!(err.to_string().contains("quota exhausted"));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,7 +1,6 @@
-use super::{HttpClient, SpaceXClient, Result as ClientResult};
+use super::{CircuitBreakers, Fetched, FetchedItems, HttpClient, NasaQuota, SpaceXClient, Result as ClientResult};
 use crate::config::HttpClientConfig;
 use async_trait::async_trait;
-use serde_json::Value;
 use crate::NasaClient;
 
 /// NASA API Client implementation
@@ -9,14 +8,19 @@ use crate::NasaClient;
 pub struct NasaClientImpl {
     http_client: HttpClient,
     base_url: String,
+    osdr_url: String,
 }
 
+/// OSDR dataset list, served from its own host rather than api.nasa.gov
+const DEFAULT_OSDR_URL: &str = "https://visualization.osdr.nasa.gov/biodata/api/v2/datasets/?format=json";
+
 impl NasaClientImpl {
     /// Create a new NASA client
     pub fn new(config: HttpClientConfig) -> Self {
         Self {
             http_client: HttpClient::new(config),
             base_url: "https://api.nasa.gov".to_string(),
+            osdr_url: DEFAULT_OSDR_URL.to_string(),
         }
     }
 
@@ -25,18 +29,40 @@ impl NasaClientImpl {
         Self {
             http_client: HttpClient::new(config),
             base_url,
+            osdr_url: DEFAULT_OSDR_URL.to_string(),
         }
     }
+
+    /// Fetch the OSDR dataset list from `url` instead of the public OSDR API
+    pub fn with_osdr_url(mut self, url: String) -> Self {
+        self.osdr_url = url;
+        self
+    }
+
+    /// Share per-host circuit breakers with the other upstream clients
+    pub fn with_circuit_breakers(mut self, breakers: CircuitBreakers) -> Self {
+        self.http_client = self.http_client.with_circuit_breakers(breakers);
+        self
+    }
+
+    /// Track the hourly quota of the API keys sent with NASA requests
+    pub fn with_quota(mut self, quota: NasaQuota) -> Self {
+        self.http_client = self.http_client.with_quota(quota);
+        self
+    }
 }
 
 #[async_trait]
 impl NasaClient for NasaClientImpl {
-    async fn fetch_osdr_datasets(&self) -> ClientResult<Value> {
-        let url = "https://visualization.osdr.nasa.gov/biodata/api/v2/datasets/?format=json";
-        self.http_client.get_with_retry(url, &[]).await
+    async fn fetch_osdr_datasets(&self) -> ClientResult<Fetched> {
+        self.http_client.get_conditional(&self.osdr_url, &[]).await
+    }
+
+    async fn stream_osdr_datasets(&self) -> ClientResult<FetchedItems> {
+        self.http_client.get_conditional_items(&self.osdr_url, &[]).await
     }
 
-    async fn fetch_apod(&self, api_key: Option<&str>) -> ClientResult<Value> {
+    async fn fetch_apod(&self, api_key: Option<&str>) -> ClientResult<Fetched> {
         let url = format!("{}/planetary/apod", self.base_url);
         let mut params = vec![("thumbs", "true")];
 
@@ -44,10 +70,10 @@ impl NasaClient for NasaClientImpl {
             params.push(("api_key", key));
         }
 
-        self.http_client.get_with_retry(&url, &params).await
+        self.http_client.get_conditional(&url, &params).await
     }
 
-    async fn fetch_neo_feed(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Value> {
+    async fn fetch_neo_feed(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Fetched> {
         let url = format!("{}/neo/rest/v1/feed", self.base_url);
         let mut params = vec![
             ("start_date", start_date),
@@ -58,10 +84,10 @@ impl NasaClient for NasaClientImpl {
             params.push(("api_key", key));
         }
 
-        self.http_client.get_with_retry(&url, &params).await
+        self.http_client.get_conditional(&url, &params).await
     }
 
-    async fn fetch_donki_flr(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Value> {
+    async fn fetch_donki_flr(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Fetched> {
         let url = format!("{}/DONKI/FLR", self.base_url);
         let mut params = vec![
             ("startDate", start_date),
@@ -72,10 +98,10 @@ impl NasaClient for NasaClientImpl {
             params.push(("api_key", key));
         }
 
-        self.http_client.get_with_retry(&url, &params).await
+        self.http_client.get_conditional(&url, &params).await
     }
 
-    async fn fetch_donki_cme(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Value> {
+    async fn fetch_donki_cme(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Fetched> {
         let url = format!("{}/DONKI/CME", self.base_url);
         let mut params = vec![
             ("startDate", start_date),
@@ -86,14 +112,15 @@ impl NasaClient for NasaClientImpl {
             params.push(("api_key", key));
         }
 
-        self.http_client.get_with_retry(&url, &params).await
+        self.http_client.get_conditional(&url, &params).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
+    use crate::clients::{serve_fixtures, test_config, ClientError};
+    use crate::config::FixtureMode;
 
     #[tokio::test]
     async fn test_nasa_client_creation() {
@@ -108,4 +135,65 @@ mod tests {
         let client = NasaClientImpl::with_base_url(config, "https://test.nasa.gov".to_string());
         assert_eq!(client.base_url, "https://test.nasa.gov");
     }
+
+    #[tokio::test]
+    async fn test_parses_recorded_feeds() {
+        let url = serve_fixtures("").await;
+        let client = NasaClientImpl::with_base_url(test_config(FixtureMode::Off, ""), url.clone());
+
+        let apod = client.fetch_apod(Some("KEY")).await.unwrap();
+        assert_eq!(apod.value["title"], "Andromeda Rising");
+        let neo = client.fetch_neo_feed("2026-10-16", "2026-10-18", Some("KEY")).await.unwrap();
+        assert_eq!(neo.value["element_count"], 2);
+        let flr = client.fetch_donki_flr("2026-10-13", "2026-10-18", None).await.unwrap();
+        assert_eq!(flr.value[0]["classType"], "M1.4");
+        let cme = client.fetch_donki_cme("2026-10-13", "2026-10-18", None).await.unwrap();
+        assert_eq!(cme.value, serde_json::json!([]));
+
+        let osdr = client
+            .clone()
+            .with_osdr_url(format!("{}/biodata/api/v2/datasets/?format=json", url))
+            .fetch_osdr_datasets()
+            .await
+            .unwrap();
+        assert!(osdr.value.get("OSD-1").is_some());
+
+        // The fixture's ETag is revalidated on the next fetch
+        let again = client.fetch_apod(Some("KEY")).await.unwrap();
+        assert!(!again.changed);
+        assert_eq!(again.value, apod.value);
+    }
+
+    #[tokio::test]
+    async fn test_recorded_error_responses() {
+        let url = serve_fixtures("errors").await;
+        let quota = NasaQuota::new(vec!["KEY".to_string()]);
+        let client = NasaClientImpl::with_base_url(test_config(FixtureMode::Off, ""), url).with_quota(quota.clone());
+
+        let err = client.fetch_apod(Some("KEY")).await.unwrap_err();
+        assert!(matches!(err, ClientError::RateLimitError(_)));
+        assert!(err.to_string().contains("quota exhausted"));
+        assert_eq!(quota.snapshot()[0].remaining, Some(0));
+
+        let err = client.fetch_donki_flr("2026-10-13", "2026-10-18", None).await.unwrap_err();
+        assert!(err.to_string().contains("503"));
+        let err = client.fetch_neo_feed("2026-10-16", "2026-10-18", None).await.unwrap_err();
+        assert!(matches!(err, ClientError::ParseError(_)));
+        // Feeds without a fixture are a plain 404
+        assert!(client.fetch_donki_cme("2026-10-13", "2026-10-18", None).await.unwrap_err().to_string().contains("404"));
+    }
+
+    #[tokio::test]
+    async fn test_replays_fixtures_without_network() {
+        let client = NasaClientImpl::new(test_config(FixtureMode::Replay, ""));
+        let osdr = client.fetch_osdr_datasets().await.unwrap();
+        assert!(osdr.value.get("OSD-1").is_some());
+
+        let mut streamed = client.stream_osdr_datasets().await.unwrap();
+        assert!(streamed.changed);
+        assert_eq!(streamed.items.next().await.unwrap().unwrap()["id"], "OSD-1");
+        assert_eq!(streamed.items.next().await.unwrap().unwrap()["id"], "OSD-2");
+        assert!(streamed.items.next().await.is_none());
+        assert_eq!(client.fetch_apod(None).await.unwrap().value["media_type"], "image");
+    }
 }
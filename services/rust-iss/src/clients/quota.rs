@@ -0,0 +1,293 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// NASA counts requests per key over a rolling hour
+const QUOTA_WINDOW: Duration = Duration::from_secs(3600);
+
+/// How much of the remaining hourly quota a call may draw on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaPriority {
+    /// Backfills that can wait for the next window
+    Low,
+    Normal,
+    /// May use the quota down to the last request
+    High,
+}
+
+/// A call held back because no key has enough quota left for its priority
+#[derive(Debug, Clone)]
+pub struct QuotaDeferred {
+    pub priority: QuotaPriority,
+    /// Best remaining count across keys
+    pub remaining: i64,
+    /// When the oldest observation leaves the window
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+impl std::fmt::Display for QuotaDeferred {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NASA quota low ({} requests left) for {:?} priority calls", self.remaining, self.priority)?;
+        if let Some(at) = self.retry_at {
+            write!(f, ", retry after {}", at.to_rfc3339())?;
+        }
+        Ok(())
+    }
+}
+
+/// Quota of one key as last reported by the API
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyQuota {
+    /// First characters of the key only
+    pub key: String,
+    pub limit: Option<i64>,
+    /// Last reported remaining count minus calls made since
+    pub remaining: Option<i64>,
+    pub observed_at: Option<DateTime<Utc>>,
+    pub calls: u64,
+}
+
+#[derive(Debug)]
+struct KeyState {
+    key: String,
+    limit: Option<i64>,
+    remaining: Option<i64>,
+    observed: Option<(Instant, DateTime<Utc>)>,
+    /// Calls started since the last observation
+    reserved: i64,
+    calls: u64,
+}
+
+impl KeyState {
+    /// Forget observations that have left the rolling window
+    fn expire(&mut self) {
+        if self.observed.is_some_and(|(at, _)| at.elapsed() >= QUOTA_WINDOW) {
+            self.remaining = None;
+            self.observed = None;
+            self.reserved = 0;
+        }
+    }
+
+    /// Requests left, `None` when unknown
+    fn estimate(&self) -> Option<i64> {
+        self.remaining.map(|r| r - self.reserved)
+    }
+}
+
+/// Hourly quota of the configured NASA API keys, shared by every NASA call.
+///
+/// Keys are rotated towards the one with the most requests left, and lower
+/// priorities keep a reserve of the quota untouched for higher ones.
+#[derive(Clone)]
+pub struct NasaQuota {
+    keys: Arc<Mutex<Vec<KeyState>>>,
+    normal_reserve: f64,
+    low_reserve: f64,
+}
+
+impl NasaQuota {
+    pub fn new(keys: Vec<String>) -> Self {
+        let keys = keys.into_iter().map(|key| KeyState {
+            key,
+            limit: None,
+            remaining: None,
+            observed: None,
+            reserved: 0,
+            calls: 0,
+        }).collect();
+        Self { keys: Arc::new(Mutex::new(keys)), normal_reserve: 0.05, low_reserve: 0.25 }
+    }
+
+    /// Share of a key's hourly limit kept back from normal and low priority calls
+    pub fn with_reserves(mut self, normal: f64, low: f64) -> Self {
+        self.normal_reserve = normal;
+        self.low_reserve = low;
+        self
+    }
+
+    fn reserve(&self, priority: QuotaPriority, limit: Option<i64>) -> i64 {
+        let share = match priority {
+            QuotaPriority::High => 0.0,
+            QuotaPriority::Normal => self.normal_reserve,
+            QuotaPriority::Low => self.low_reserve,
+        };
+        limit.map(|l| (l as f64 * share).ceil() as i64).unwrap_or(0)
+    }
+
+    /// Pick a key for a call of `priority`, counting the call against it
+    pub fn acquire(&self, priority: QuotaPriority) -> Result<String, QuotaDeferred> {
+        let mut keys = self.keys.lock().unwrap();
+        keys.iter_mut().for_each(KeyState::expire);
+
+        // Unknown quota counts as full; ties go to the least used key
+        let best = keys.iter_mut()
+            .filter(|k| k.estimate().is_none_or(|left| left > self.reserve(priority, k.limit)))
+            .min_by(|a, b| {
+                let left = |k: &KeyState| k.estimate().unwrap_or(i64::MAX);
+                left(b).cmp(&left(a)).then(a.calls.cmp(&b.calls))
+            });
+
+        if let Some(key) = best {
+            key.reserved += 1;
+            key.calls += 1;
+            return Ok(key.key.clone());
+        }
+
+        let deferred = QuotaDeferred {
+            priority,
+            remaining: keys.iter().filter_map(KeyState::estimate).max().unwrap_or(0),
+            retry_at: keys.iter()
+                .filter_map(|k| k.observed.map(|(_, at)| at))
+                .min()
+                .and_then(|at| chrono::Duration::from_std(QUOTA_WINDOW).ok().map(|w| at + w)),
+        };
+        debug!("Deferring NASA call: {}", deferred);
+        Err(deferred)
+    }
+
+    /// Count a retry of a call already made with `key`.
+    ///
+    /// The call's priority was weighed when it first drew the key; a retry
+    /// only needs the key to have a request left. Returns false when it has not.
+    pub fn reserve_retry(&self, key: &str) -> bool {
+        let mut keys = self.keys.lock().unwrap();
+        let Some(k) = keys.iter_mut().find(|k| k.key == key) else {
+            return false;
+        };
+        k.expire();
+        if k.estimate().is_some_and(|left| left <= 0) {
+            debug!("Not retrying NASA call, key {} has no quota left", mask(key));
+            return false;
+        }
+        k.reserved += 1;
+        k.calls += 1;
+        true
+    }
+
+    /// Record the `X-RateLimit-*` headers of a response made with `key`
+    pub fn observe(&self, key: &str, limit: Option<i64>, remaining: i64) {
+        self.update(key, |k| {
+            k.limit = limit.or(k.limit);
+            k.remaining = Some(remaining);
+        });
+    }
+
+    /// The API refused `key` with 429
+    pub fn exhausted(&self, key: &str) {
+        warn!("NASA API key {} exhausted its hourly quota", mask(key));
+        self.update(key, |k| k.remaining = Some(0));
+    }
+
+    /// A call with `key` ended without quota headers
+    pub fn release(&self, key: &str) {
+        let mut keys = self.keys.lock().unwrap();
+        if let Some(k) = keys.iter_mut().find(|k| k.key == key) {
+            k.reserved = (k.reserved - 1).max(0);
+        }
+    }
+
+    fn update(&self, key: &str, apply: impl FnOnce(&mut KeyState)) {
+        let mut keys = self.keys.lock().unwrap();
+        let Some(k) = keys.iter_mut().find(|k| k.key == key) else {
+            return;
+        };
+        apply(k);
+        k.observed = Some((Instant::now(), Utc::now()));
+        k.reserved = (k.reserved - 1).max(0);
+    }
+
+    /// Current quota of every key, with the keys masked
+    pub fn snapshot(&self) -> Vec<KeyQuota> {
+        let mut keys = self.keys.lock().unwrap();
+        keys.iter_mut().for_each(KeyState::expire);
+        keys.iter().map(|k| KeyQuota {
+            key: mask(&k.key),
+            limit: k.limit,
+            remaining: k.estimate(),
+            observed_at: k.observed.map(|(_, at)| at),
+            calls: k.calls,
+        }).collect()
+    }
+}
+
+fn mask(key: &str) -> String {
+    format!("{}…", key.chars().take(4).collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota() -> NasaQuota {
+        NasaQuota::new(vec!["KEY_A".to_string(), "KEY_B".to_string()]).with_reserves(0.1, 0.5)
+    }
+
+    #[test]
+    fn test_rotates_to_the_key_with_most_quota_left() {
+        let q = quota();
+        // Unknown quotas alternate between keys
+        assert_eq!(q.acquire(QuotaPriority::Normal).unwrap(), "KEY_A");
+        assert_eq!(q.acquire(QuotaPriority::Normal).unwrap(), "KEY_B");
+
+        q.observe("KEY_A", Some(100), 80);
+        q.observe("KEY_B", Some(100), 30);
+        assert_eq!(q.acquire(QuotaPriority::Normal).unwrap(), "KEY_A");
+
+        q.exhausted("KEY_A");
+        assert_eq!(q.acquire(QuotaPriority::Normal).unwrap(), "KEY_B");
+        let snapshot = q.snapshot();
+        assert_eq!(snapshot[0].key, "KEY_…");
+        assert_eq!(snapshot[0].remaining, Some(0));
+        assert_eq!(snapshot[1].remaining, Some(29));
+    }
+
+    #[test]
+    fn test_low_priority_calls_are_deferred_first() {
+        let q = NasaQuota::new(vec!["DEMO_KEY".to_string()]).with_reserves(0.1, 0.5);
+        q.acquire(QuotaPriority::High).unwrap();
+        q.observe("DEMO_KEY", Some(30), 12);
+
+        // 15 of 30 are reserved for normal and high priority calls
+        let deferred = q.acquire(QuotaPriority::Low).unwrap_err();
+        assert_eq!(deferred.remaining, 12);
+        assert!(deferred.retry_at.is_some());
+        assert!(deferred.to_string().contains("12 requests left"));
+
+        // Normal calls keep 3 back for high priority ones
+        for _ in 0..9 {
+            q.acquire(QuotaPriority::Normal).unwrap();
+        }
+        assert!(q.acquire(QuotaPriority::Normal).is_err());
+        for _ in 0..3 {
+            q.acquire(QuotaPriority::High).unwrap();
+        }
+        assert!(q.acquire(QuotaPriority::High).is_err());
+    }
+
+    #[test]
+    fn test_retries_are_counted_against_their_key() {
+        let q = NasaQuota::new(vec!["KEY_A".to_string()]);
+        q.acquire(QuotaPriority::Normal).unwrap();
+        q.observe("KEY_A", Some(10), 2);
+        assert!(q.reserve_retry("KEY_A"));
+        assert_eq!(q.snapshot()[0].remaining, Some(1));
+        assert_eq!(q.snapshot()[0].calls, 2);
+        q.observe("KEY_A", Some(10), 0);
+        assert!(!q.reserve_retry("KEY_A"));
+        assert!(!q.reserve_retry("UNKNOWN"));
+    }
+
+    #[test]
+    fn test_released_calls_return_their_reservation() {
+        let q = NasaQuota::new(vec!["KEY_A".to_string()]);
+        q.acquire(QuotaPriority::High).unwrap();
+        q.observe("KEY_A", Some(10), 1);
+        q.acquire(QuotaPriority::High).unwrap();
+        assert!(q.acquire(QuotaPriority::High).is_err());
+        q.release("KEY_A");
+        assert!(q.acquire(QuotaPriority::High).is_ok());
+    }
+}
@@ -1,4 +1,4 @@
-use super::{HttpClient, SpaceXClient, Result as ClientResult};
+use super::{CircuitBreakers, Fetched, HttpClient, SpaceXClient, Result as ClientResult};
 use crate::config::HttpClientConfig;
 use async_trait::async_trait;
 use serde_json::Value;
@@ -26,13 +26,19 @@ impl SpaceXClientImpl {
             base_url,
         }
     }
+
+    /// Share per-host circuit breakers with the other upstream clients
+    pub fn with_circuit_breakers(mut self, breakers: CircuitBreakers) -> Self {
+        self.http_client = self.http_client.with_circuit_breakers(breakers);
+        self
+    }
 }
 
 #[async_trait]
 impl SpaceXClient for SpaceXClientImpl {
-    async fn fetch_next_launch(&self) -> ClientResult<Value> {
+    async fn fetch_next_launch(&self) -> ClientResult<Fetched> {
         let url = format!("{}/v4/launches/next", self.base_url);
-        self.http_client.get_with_retry(&url, &[]).await
+        self.http_client.get_conditional(&url, &[]).await
     }
 
     async fn fetch_latest_launch(&self) -> ClientResult<Value> {
@@ -49,7 +55,8 @@ impl SpaceXClient for SpaceXClientImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
+    use crate::clients::{serve_fixtures, test_config};
+    use crate::config::FixtureMode;
 
     #[tokio::test]
     async fn test_spacex_client_creation() {
@@ -64,4 +71,15 @@ mod tests {
         let client = SpaceXClientImpl::with_base_url(config, "https://test.spacex.api".to_string());
         assert_eq!(client.base_url, "https://test.spacex.api");
     }
+
+    #[tokio::test]
+    async fn test_parses_recorded_launches() {
+        let url = serve_fixtures("").await;
+        let client = SpaceXClientImpl::with_base_url(test_config(FixtureMode::Off, ""), url);
+        let next = client.fetch_next_launch().await.unwrap();
+        assert_eq!(next.value["name"], "Crew-13");
+        assert!(!client.fetch_next_launch().await.unwrap().changed);
+        // No upcoming launches fixture was recorded
+        assert!(client.fetch_upcoming_launches().await.is_err());
+    }
 }
@@ -142,18 +142,26 @@ impl OsdrItem {
 pub struct SpaceCache {
     pub id: Option<Id>,
     pub source: String,
+    /// When this payload was first fetched, i.e. when the source last changed
     pub fetched_at: Timestamp,
+    /// When this payload was most recently fetched
+    pub last_seen_at: Timestamp,
     pub payload: Value,
+    /// Content hash computed by Postgres; `None` until stored
+    pub payload_hash: Option<String>,
 }
 
 impl SpaceCache {
     /// Create a new SpaceCache instance
     pub fn new(source: String, payload: Value) -> Self {
+        let now = Utc::now();
         Self {
             id: None,
             source,
-            fetched_at: Utc::now(),
+            fetched_at: now,
+            last_seen_at: now,
             payload,
+            payload_hash: None,
         }
     }
 
@@ -163,25 +171,399 @@ impl SpaceCache {
             return Err(DomainError::ValidationError("source cannot be empty".to_string()));
         }
 
-        // Source should be one of the known space data sources
-        let valid_sources = ["apod", "neo", "flr", "cme", "spacex"];
-        if !valid_sources.contains(&self.source.to_lowercase().as_str()) {
+        // Payload must be valid JSON
+        if self.payload.is_null() {
+            return Err(DomainError::ValidationError("payload cannot be null".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Result of storing a fetched space_cache payload
+#[derive(Debug, Clone)]
+pub struct CacheWrite {
+    /// The stored row: a new one, or the latest one with `last_seen_at` bumped
+    pub entry: SpaceCache,
+    /// Whether the payload differed from the latest stored one
+    pub changed: bool,
+}
+
+/// JWST observation image domain model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JwstObservation {
+    pub id: Option<Id>,
+    pub observation_id: String,
+    pub program: String,
+    pub instruments: Vec<String>,
+    pub suffix: Option<String>,
+    pub image_url: String,
+    pub thumbnail_url: Option<String>,
+    pub fetched_at: Timestamp,
+    pub raw: Value,
+}
+
+impl JwstObservation {
+    /// Validate the JwstObservation instance
+    pub fn validate(&self) -> Result<(), DomainError> {
+        validators::validate_non_empty_string(&self.observation_id, "observation_id")?;
+        validators::validate_non_empty_string(&self.program, "program")?;
+        validators::validate_url(&self.image_url, "image_url")?;
+
+        if !is_image_url(&self.image_url) {
             return Err(DomainError::ValidationError(format!(
-                "source '{}' is not a valid source. Valid sources: {}",
-                self.source,
-                valid_sources.join(", ")
+                "image_url '{}' must point to a JPEG or PNG image",
+                self.image_url
             )));
         }
 
-        // Payload must be valid JSON
-        if self.payload.is_null() {
-            return Err(DomainError::ValidationError("payload cannot be null".to_string()));
+        if self.instruments.iter().any(|i| i.trim().is_empty()) {
+            return Err(DomainError::ValidationError("instruments cannot contain empty names".to_string()));
+        }
+
+        validators::validate_json_object(&self.raw, "raw")?;
+
+        Ok(())
+    }
+}
+
+/// Filter for JWST feed queries
+#[derive(Debug, Clone, Default)]
+pub struct JwstFilter {
+    pub program: Option<String>,
+    pub instrument: Option<String>,
+    /// Exact product suffix such as `_i2d`
+    pub suffix: Option<String>,
+}
+
+/// Astronomical event domain model (eclipse, rise/set or moon phase)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AstroEvent {
+    pub id: Option<Id>,
+    pub observer: String,
+    pub body: String,
+    pub event_type: String,
+    pub occurs_at: Timestamp,
+    pub ends_at: Option<Timestamp>,
+    pub details: Value,
+    pub fetched_at: Timestamp,
+}
+
+impl AstroEvent {
+    /// Create a new AstroEvent instance
+    pub fn new(observer: String, body: String, event_type: String, occurs_at: Timestamp, details: Value) -> Self {
+        Self {
+            id: None,
+            observer,
+            body,
+            event_type,
+            occurs_at,
+            ends_at: None,
+            details,
+            fetched_at: Utc::now(),
+        }
+    }
+
+    /// Validate the AstroEvent instance
+    pub fn validate(&self) -> Result<(), DomainError> {
+        validators::validate_non_empty_string(&self.observer, "observer")?;
+        validators::validate_non_empty_string(&self.body, "body")?;
+        validators::validate_non_empty_string(&self.event_type, "event_type")?;
+
+        if let Some(ends_at) = self.ends_at {
+            if ends_at < self.occurs_at {
+                return Err(DomainError::ValidationError("ends_at cannot be before occurs_at".to_string()));
+            }
         }
 
+        validators::validate_json_object(&self.details, "details")?;
+
         Ok(())
     }
 }
 
+/// Filter for astronomical event queries
+#[derive(Debug, Clone, Default)]
+pub struct AstroEventFilter {
+    pub observer: Option<String>,
+    pub body: Option<String>,
+    pub event_type: Option<String>,
+    pub from: Option<Timestamp>,
+    pub to: Option<Timestamp>,
+}
+
+/// State of a schema migration relative to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    Failed,
+    /// Applied migration whose SQL has since been edited
+    ChecksumMismatch,
+    /// Applied in the database but not embedded in this build
+    Missing,
+}
+
+/// Schema migration with its applied state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+    pub reversible: bool,
+    pub checksum: String,
+    pub installed_on: Option<DateTime<Utc>>,
+    pub execution_ms: Option<i64>,
+}
+
+/// Redis cache key as listed by the admin API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheKeyInfo {
+    pub key: String,
+    pub ttl_seconds: Option<i64>,
+    pub size_bytes: Option<i64>,
+}
+
+/// Kind of work a compaction step performed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionAction {
+    /// Raw rows folded into hourly aggregates
+    Downsample,
+    /// Rows repeating the previous payload of the same source
+    Dedupe,
+    /// Rows older than the retention window
+    Expire,
+}
+
+/// Rows removed from one table (and source) by a compaction step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionStep {
+    pub table: String,
+    pub source: Option<String>,
+    pub action: CompactionAction,
+    pub rows_removed: i64,
+    /// Aggregate rows inserted or updated
+    pub rows_written: i64,
+    /// Size of the removed rows as stored by Postgres
+    pub bytes_freed: i64,
+    /// Whole partitions dropped instead of deleting their rows
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partitions_dropped: Vec<String>,
+}
+
+impl CompactionStep {
+    pub fn new(table: &str, source: Option<&str>, action: CompactionAction) -> Self {
+        Self {
+            table: table.to_string(),
+            source: source.map(str::to_string),
+            action,
+            rows_removed: 0,
+            rows_written: 0,
+            bytes_freed: 0,
+            partitions_dropped: Vec::new(),
+        }
+    }
+}
+
+/// On-disk size of a table including indexes and TOAST, around a compaction run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSize {
+    pub table: String,
+    pub bytes_before: i64,
+    pub bytes_after: i64,
+}
+
+/// Outcome of a retention and compaction run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactionReport {
+    pub id: Option<Id>,
+    pub started_at: Timestamp,
+    pub finished_at: Timestamp,
+    pub rows_removed: i64,
+    pub bytes_reclaimed: i64,
+    pub steps: Vec<CompactionStep>,
+    pub tables: Vec<TableSize>,
+    /// iss_fetch_log partitions created ahead of time
+    #[serde(default)]
+    pub partitions_created: Vec<String>,
+    /// Steps that failed; the others still ran
+    pub errors: Vec<String>,
+}
+
+/// Outcome of a background job run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for JobStatus {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(JobStatus::Running),
+            "succeeded" => Ok(JobStatus::Succeeded),
+            "failed" => Ok(JobStatus::Failed),
+            other => Err(DomainError::ValidationError(format!("unknown job status '{}'", other))),
+        }
+    }
+}
+
+/// One run of a scheduled background job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRun {
+    pub id: Option<Id>,
+    pub job: String,
+    pub started_at: Timestamp,
+    pub finished_at: Option<Timestamp>,
+    pub status: JobStatus,
+    pub error: Option<String>,
+    /// Items the job fetched or wrote, as reported by the job
+    pub items: Option<i64>,
+    /// Replica that ran the job
+    pub replica: Option<String>,
+}
+
+impl JobRun {
+    pub fn started(job: &str, replica: &str, started_at: Timestamp) -> Self {
+        Self {
+            id: None,
+            job: job.to_string(),
+            started_at,
+            finished_at: None,
+            status: JobStatus::Running,
+            error: None,
+            items: None,
+            replica: Some(replica.to_string()),
+        }
+    }
+}
+
+/// Legacy telemetry reading domain model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryReading {
+    pub id: Option<Id>,
+    pub recorded_at: Timestamp,
+    pub voltage: f64,
+    pub temp: f64,
+    pub source_file: String,
+    pub is_valid: bool,
+}
+
+impl TelemetryReading {
+    /// Validate the TelemetryReading instance
+    pub fn validate(&self) -> Result<(), DomainError> {
+        validators::validate_non_empty_string(&self.source_file, "source_file")?;
+
+        // Columns are NUMERIC(6,2) in the database
+        for (name, value) in [("voltage", self.voltage), ("temp", self.temp)] {
+            if !value.is_finite() || value.abs() >= 10_000.0 {
+                return Err(DomainError::ValidationError(format!("{} must be a finite number below 10000", name)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Acceptable operating range for legacy telemetry readings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryEnvelope {
+    pub voltage_min: f64,
+    pub voltage_max: f64,
+    pub temp_min: f64,
+    pub temp_max: f64,
+}
+
+impl Default for TelemetryEnvelope {
+    fn default() -> Self {
+        Self {
+            voltage_min: 3.2,
+            voltage_max: 12.6,
+            temp_min: -40.0,
+            temp_max: 60.0,
+        }
+    }
+}
+
+impl TelemetryEnvelope {
+    /// Validate the TelemetryEnvelope instance
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.voltage_min >= self.voltage_max {
+            return Err(DomainError::ValidationError("voltage_min must be less than voltage_max".to_string()));
+        }
+        if self.temp_min >= self.temp_max {
+            return Err(DomainError::ValidationError("temp_min must be less than temp_max".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Names of the limits a reading violates (empty when inside the envelope)
+    pub fn violations(&self, reading: &TelemetryReading) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        if reading.voltage < self.voltage_min {
+            flags.push("voltage_low");
+        }
+        if reading.voltage > self.voltage_max {
+            flags.push("voltage_high");
+        }
+        if reading.temp < self.temp_min {
+            flags.push("temp_low");
+        }
+        if reading.temp > self.temp_max {
+            flags.push("temp_high");
+        }
+        flags
+    }
+}
+
+/// Aggregated telemetry over one time bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryBucket {
+    pub bucket_start: Timestamp,
+    pub readings: i64,
+    pub voltage_min: f64,
+    pub voltage_max: f64,
+    pub voltage_avg: f64,
+    pub temp_min: f64,
+    pub temp_max: f64,
+    pub temp_avg: f64,
+    pub valid_readings: i64,
+    pub out_of_range: i64,
+}
+
+impl TelemetryBucket {
+    /// Share of readings in the bucket marked valid by the source system
+    pub fn validity_rate(&self) -> f64 {
+        if self.readings == 0 {
+            0.0
+        } else {
+            self.valid_readings as f64 / self.readings as f64
+        }
+    }
+}
+
+/// Check whether a URL points to a JPEG or PNG image (query string allowed)
+pub fn is_image_url(url: &str) -> bool {
+    let path = url.split('?').next().unwrap_or(url).to_lowercase();
+    path.ends_with(".jpg") || path.ends_with(".jpeg") || path.ends_with(".png")
+}
+
 /// Domain validation error
 #[derive(Debug, Clone)]
 pub enum DomainError {
@@ -274,14 +656,102 @@ mod tests {
         assert!(invalid_osdr.validate().is_err());
     }
 
+    #[test]
+    fn test_jwst_observation_validation() {
+        let observation = JwstObservation {
+            id: None,
+            observation_id: "jw02734002001_02103_00001_nrcalong".to_string(),
+            program: "2734".to_string(),
+            instruments: vec!["NIRCAM".to_string()],
+            suffix: Some("_i2d".to_string()),
+            image_url: "https://stsci-opo.org/image.jpg".to_string(),
+            thumbnail_url: None,
+            fetched_at: Utc::now(),
+            raw: serde_json::json!({"id": "x"}),
+        };
+        assert!(observation.validate().is_ok());
+
+        let fits = JwstObservation { image_url: "https://mast.stsci.edu/file.fits".to_string(), ..observation.clone() };
+        assert!(fits.validate().is_err());
+
+        let no_program = JwstObservation { program: " ".to_string(), ..observation };
+        assert!(no_program.validate().is_err());
+    }
+
+    #[test]
+    fn test_astro_event_validation() {
+        let now = Utc::now();
+        let event = AstroEvent::new(
+            "default".to_string(),
+            "moon".to_string(),
+            "moon_phase".to_string(),
+            now,
+            serde_json::json!({"phase": "Full Moon"}),
+        );
+        assert!(event.validate().is_ok());
+
+        let inverted = AstroEvent { ends_at: Some(now - chrono::Duration::hours(1)), ..event.clone() };
+        assert!(inverted.validate().is_err());
+
+        let no_type = AstroEvent { event_type: "".to_string(), ..event };
+        assert!(no_type.validate().is_err());
+    }
+
+    #[test]
+    fn test_telemetry_reading_validation() {
+        let reading = TelemetryReading {
+            id: None,
+            recorded_at: Utc::now(),
+            voltage: 12.1,
+            temp: -20.5,
+            source_file: "telemetry_20260101_000000.csv".to_string(),
+            is_valid: true,
+        };
+        assert!(reading.validate().is_ok());
+
+        let nan = TelemetryReading { voltage: f64::NAN, ..reading.clone() };
+        assert!(nan.validate().is_err());
+
+        let overflow = TelemetryReading { temp: 12345.0, ..reading.clone() };
+        assert!(overflow.validate().is_err());
+
+        let no_file = TelemetryReading { source_file: "".to_string(), ..reading };
+        assert!(no_file.validate().is_err());
+    }
+
+    #[test]
+    fn test_telemetry_envelope_violations() {
+        let envelope = TelemetryEnvelope::default();
+        assert!(envelope.validate().is_ok());
+
+        let reading = TelemetryReading {
+            id: None,
+            recorded_at: Utc::now(),
+            voltage: 2.0,
+            temp: 75.0,
+            source_file: "telemetry.csv".to_string(),
+            is_valid: true,
+        };
+        assert_eq!(envelope.violations(&reading), vec!["voltage_low", "temp_high"]);
+
+        let inside = TelemetryReading { voltage: 5.0, temp: 20.0, ..reading };
+        assert!(envelope.violations(&inside).is_empty());
+
+        let inverted = TelemetryEnvelope { voltage_min: 10.0, voltage_max: 5.0, ..envelope };
+        assert!(inverted.validate().is_err());
+    }
+
     #[test]
     fn test_space_cache_validation() {
         let payload = serde_json::json!({"data": "test"});
         let space_cache = SpaceCache::new("apod".to_string(), payload);
         assert!(space_cache.validate().is_ok());
 
-        // Test invalid source
-        let invalid_cache = SpaceCache::new("invalid_source".to_string(), serde_json::json!({"test": "data"}));
-        assert!(invalid_cache.validate().is_err());
+        // Known sources are checked by the service's source registry
+        let empty_source = SpaceCache::new(" ".to_string(), serde_json::json!({"test": "data"}));
+        assert!(empty_source.validate().is_err());
+
+        let null_payload = SpaceCache::new("apod".to_string(), serde_json::Value::Null);
+        assert!(null_payload.validate().is_err());
     }
 }
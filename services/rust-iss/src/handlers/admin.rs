@@ -0,0 +1,223 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{error, info, instrument, warn};
+
+use crate::{AppState, handlers::ApiError, services::{source_pattern, DERIVED_SOURCES}};
+
+const DEFAULT_KEY_LIMIT: usize = 200;
+const MAX_KEY_LIMIT: usize = 5000;
+const DEFAULT_RUN_LIMIT: i64 = 20;
+const MAX_RUN_LIMIT: i64 = 500;
+
+/// Check the `Authorization: Bearer` header against `ADMIN_TOKEN`.
+///
+/// Admin endpoints are disabled entirely when no token is configured.
+fn require_admin(st: &AppState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let Some(expected) = st.config.server.admin_token.as_deref() else {
+        return Err(ApiError::forbidden("Admin API is disabled (ADMIN_TOKEN not set)"));
+    };
+    let provided = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        Some(_) => Err(ApiError::forbidden("Invalid admin token")),
+        None => Err(ApiError::unauthorized("Missing bearer token")),
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Every source with cache entries: registered space sources plus ISS and OSDR
+fn cache_sources(st: &AppState) -> Vec<&'static str> {
+    let mut sources = st.cache_service.sources().names();
+    sources.extend(DERIVED_SOURCES);
+    sources
+}
+
+fn require_redis(st: &AppState) -> Result<(), ApiError> {
+    if st.redis_repo.is_none() {
+        return Err(ApiError::service_unavailable("Redis is not configured"));
+    }
+    Ok(())
+}
+
+/// Resolve `?source=` or `?pattern=` into a Redis key pattern
+fn purge_pattern(q: &HashMap<String, String>, known: &[&str]) -> Result<String, ApiError> {
+    match (q.get("source"), q.get("pattern")) {
+        (Some(_), Some(_)) => Err(ApiError::bad_request("Use either source or pattern, not both")),
+        (Some(source), None) => {
+            let source = source.trim().to_lowercase();
+            if !known.contains(&source.as_str()) {
+                return Err(ApiError::bad_request(format!("Unknown source: {}", source)));
+            }
+            Ok(source_pattern(&source))
+        }
+        (None, Some(pattern)) if !pattern.trim().is_empty() => Ok(pattern.trim().to_string()),
+        _ => Err(ApiError::bad_request("source or pattern is required")),
+    }
+}
+
+#[instrument(skip(st, headers))]
+pub async fn admin_cache_list(
+    Query(q): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    require_admin(&st, &headers)?;
+    require_redis(&st)?;
+    let pattern = q.get("pattern").map(|p| p.trim()).filter(|p| !p.is_empty()).unwrap_or("*");
+    let limit = match q.get("limit") {
+        Some(raw) => raw.parse::<usize>()
+            .ok()
+            .filter(|l| (1..=MAX_KEY_LIMIT).contains(l))
+            .ok_or_else(|| ApiError::bad_request(format!("limit must be between 1 and {}", MAX_KEY_LIMIT)))?,
+        None => DEFAULT_KEY_LIMIT,
+    };
+
+    let keys = st.cache_service.list_cache_keys(pattern, limit).await
+        .map_err(|e| {
+            error!("Failed to list cache keys for {}: {:?}", pattern, e);
+            ApiError::internal_error("Failed to list cache keys")
+        })?;
+    Ok(Json(serde_json::json!({ "pattern": pattern, "count": keys.len(), "keys": keys })))
+}
+
+#[instrument(skip(st, headers))]
+pub async fn admin_cache_purge(
+    Query(q): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    require_admin(&st, &headers)?;
+    require_redis(&st)?;
+    let pattern = purge_pattern(&q, &cache_sources(&st))?;
+
+    let deleted = st.cache_service.purge_cache(&pattern).await
+        .map_err(|e| {
+            error!("Failed to purge cache keys for {}: {:?}", pattern, e);
+            ApiError::internal_error("Failed to purge cache")
+        })?;
+    Ok(Json(serde_json::json!({ "pattern": pattern, "deleted": deleted.len(), "keys": deleted })))
+}
+
+/// Purge the given sources and reload them from Postgres into Redis
+#[instrument(skip(st, headers))]
+pub async fn admin_cache_warm(
+    Query(q): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    require_admin(&st, &headers)?;
+    require_redis(&st)?;
+    let known = cache_sources(&st);
+    let list = q.get("src").cloned().unwrap_or_else(|| known.join(","));
+
+    let mut warmed = Vec::new();
+    let mut failed = serde_json::Map::new();
+    for source in list.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+        if !known.contains(&source.as_str()) {
+            warn!("Unknown source: {}", source);
+            failed.insert(source, Value::String("unknown source".to_string()));
+            continue;
+        }
+        match warm_source(&st, &source).await {
+            Ok(()) => warmed.push(source),
+            Err(e) => {
+                warn!("Failed to warm {}: {}", source, e);
+                failed.insert(source, Value::String(e));
+            }
+        }
+    }
+    info!("Warmed {} cache sources, {} failed", warmed.len(), failed.len());
+    Ok(Json(serde_json::json!({ "warmed": warmed, "failed": failed })))
+}
+
+async fn warm_source(st: &AppState, source: &str) -> Result<(), String> {
+    st.cache_service.purge_cache(&source_pattern(source)).await.map_err(|e| e.to_string())?;
+    match source {
+        "iss" => {
+            st.iss_service.get_latest_iss_data().await.map_err(|e| e.to_string())?;
+            st.iss_service.get_iss_trend_points(240).await.map_err(|e| e.to_string())?;
+        }
+        "osdr" => {
+            st.osdr_service.get_osdr_page(1, 20).await.map_err(|e| e.to_string())?;
+        }
+        _ => {
+            st.cache_service.get_cached_source(source).await.map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Recent compaction runs, newest first
+#[instrument(skip(st, headers))]
+pub async fn admin_compaction_runs(
+    Query(q): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    require_admin(&st, &headers)?;
+    let limit = match q.get("limit") {
+        Some(raw) => raw.parse::<i64>()
+            .ok()
+            .filter(|l| (1..=MAX_RUN_LIMIT).contains(l))
+            .ok_or_else(|| ApiError::bad_request(format!("limit must be between 1 and {}", MAX_RUN_LIMIT)))?,
+        None => DEFAULT_RUN_LIMIT,
+    };
+
+    let runs = st.retention_service.get_compaction_runs(limit).await
+        .map_err(|e| {
+            error!("Failed to read compaction runs: {:?}", e);
+            ApiError::internal_error("Failed to read compaction runs")
+        })?;
+    Ok(Json(serde_json::json!({ "count": runs.len(), "runs": runs })))
+}
+
+/// Run retention and compaction now instead of waiting for the schedule
+#[instrument(skip(st, headers))]
+pub async fn admin_compaction_run(
+    headers: HeaderMap,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    require_admin(&st, &headers)?;
+    let report = st.retention_service.run_compaction().await
+        .map_err(|e| {
+            error!("Compaction failed: {:?}", e);
+            ApiError::internal_error("Compaction failed")
+        })?;
+    Ok(Json(serde_json::to_value(report).map_err(|e| ApiError::internal_error(e.to_string()))?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purge_pattern_resolution() {
+        let known = ["apod", "osdr"];
+        let q = |pairs: &[(&str, &str)]| pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>();
+        assert_eq!(purge_pattern(&q(&[("source", "APOD")]), &known).unwrap(), "space:apod");
+        assert_eq!(purge_pattern(&q(&[("source", "osdr")]), &known).unwrap(), "osdr:*");
+        assert_eq!(purge_pattern(&q(&[("pattern", "iss:trend:*")]), &known).unwrap(), "iss:trend:*");
+        assert!(purge_pattern(&q(&[("source", "bogus")]), &known).is_err());
+        assert!(purge_pattern(&q(&[("source", "apod"), ("pattern", "*")]), &known).is_err());
+        assert!(purge_pattern(&q(&[]), &known).is_err());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
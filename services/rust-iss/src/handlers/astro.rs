@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{error, info, instrument};
+
+use crate::{AppState, domain::AstroEventFilter, handlers::{ApiError, parse_day}};
+
+const DEFAULT_EVENTS_LIMIT: i64 = 500;
+
+#[instrument(skip(st))]
+pub async fn astro_sync(State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
+    info!("Starting astronomical events synchronization");
+    let written = st.astro_service.sync_astro_events().await
+        .map_err(|e| {
+            error!("Failed to sync astronomical events: {:?}", e);
+            ApiError::internal_error("Failed to sync astronomical events")
+        })?;
+    info!("Astronomical events sync completed with {} events", written);
+    Ok(Json(serde_json::json!({ "message": "Astro sync completed", "written": written })))
+}
+
+/// Astronomical events served from Postgres.
+///
+/// Query parameters: `observer`, `body`, `type`, `from`, `to` (`YYYY-MM-DD`), `limit`
+#[instrument(skip(st))]
+pub async fn astro_events(
+    Query(q): Query<HashMap<String, String>>,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    let text = |name: &str| q.get(name).map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty());
+
+    let filter = AstroEventFilter {
+        observer: q.get("observer").map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+        body: text("body"),
+        event_type: text("type"),
+        from: q.get("from").map(|s| parse_day(s, "from", false)).transpose()?,
+        to: q.get("to").map(|s| parse_day(s, "to", true)).transpose()?,
+    };
+    let limit = match q.get("limit") {
+        None => DEFAULT_EVENTS_LIMIT,
+        Some(s) => s.trim().parse::<i64>().ok().filter(|v| (1..=DEFAULT_EVENTS_LIMIT).contains(v))
+            .ok_or_else(|| ApiError::bad_request(format!("limit must be between 1 and {}", DEFAULT_EVENTS_LIMIT)))?,
+    };
+
+    info!("Retrieving astronomical events with {:?}", filter);
+    let events = st.astro_service.get_astro_events(&filter, limit).await
+        .map_err(|e| {
+            error!("Failed to retrieve astronomical events: {:?}", e);
+            ApiError::internal_error("Failed to retrieve astronomical events")
+        })?;
+
+    info!("Retrieved {} astronomical events", events.len());
+    Ok(Json(serde_json::json!({ "events": events })))
+}
@@ -2,82 +2,124 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use chrono::DateTime;
 use serde_json::Value;
-use sqlx::Row;
 use std::collections::HashMap;
-use tracing::{info, instrument, warn};
+use tracing::{error, info, instrument, warn};
 
-use crate::{AppState, handlers::ApiError};
+use crate::{AppState, handlers::ApiError, services::CachedSource};
+
+const DEFAULT_HISTORY_LIMIT: i64 = 20;
+const MAX_HISTORY_LIMIT: i64 = 200;
 
 #[instrument(skip(st))]
 pub async fn space_latest(Path(src): Path<String>, State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
     info!("Retrieving latest data for source: {}", src);
-    let row = sqlx::query(
-        "SELECT fetched_at, payload FROM space_cache
-         WHERE source = $1 ORDER BY id DESC LIMIT 1"
-    ).bind(&src).fetch_optional(&st.pool).await?;
+    let cached = st.cache_service.get_cached_source(&src).await
+        .map_err(|e| {
+            error!("Failed to load cached data for {}: {:?}", src, e);
+            ApiError::internal_error("Failed to retrieve cached data")
+        })?;
 
-    if let Some(r) = row {
-        let fetched_at: DateTime<chrono::Utc> = r.get("fetched_at");
-        let payload: Value = r.get("payload");
-        info!("Found cached data for source: {} from {}", src, fetched_at);
-        return Ok(Json(serde_json::json!({ "source": src, "fetched_at": fetched_at, "payload": payload })));
+    if let Some(cached) = cached {
+        info!("Found cached data for source: {} from {} (stale: {})", src, cached.entry.fetched_at, cached.stale);
+        return Ok(Json(serde_json::json!({
+            "source": src, "fetched_at": cached.entry.fetched_at, "last_seen_at": cached.entry.last_seen_at,
+            "payload": cached.entry.payload, "stale": cached.stale, "age_seconds": cached.age_seconds
+        })));
     }
     info!("No cached data found for source: {}", src);
     Ok(Json(serde_json::json!({ "source": src, "message":"no data" })))
 }
 
+/// Payloads of a source as they changed, newest first; repeated fetches of
+/// the same payload are folded into its `last_seen_at`
+#[instrument(skip(st))]
+pub async fn space_history(
+    Path(src): Path<String>,
+    Query(q): Query<HashMap<String, String>>,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    if st.cache_service.sources().get(&src).is_none() {
+        return Err(ApiError::not_found(format!("Unknown source: {}", src)));
+    }
+    let limit = match q.get("limit") {
+        Some(raw) => raw.parse::<i64>()
+            .ok()
+            .filter(|l| (1..=MAX_HISTORY_LIMIT).contains(l))
+            .ok_or_else(|| ApiError::bad_request(format!("limit must be between 1 and {}", MAX_HISTORY_LIMIT)))?,
+        None => DEFAULT_HISTORY_LIMIT,
+    };
+
+    let entries = st.cache_service.get_source_history(&src, limit).await
+        .map_err(|e| {
+            error!("Failed to load history for {}: {:?}", src, e);
+            ApiError::internal_error("Failed to retrieve history")
+        })?;
+    let items: Vec<Value> = entries.into_iter().map(|e| serde_json::json!({
+        "changed_at": e.fetched_at, "last_seen_at": e.last_seen_at, "payload_hash": e.payload_hash, "payload": e.payload
+    })).collect();
+    Ok(Json(serde_json::json!({ "source": src, "count": items.len(), "items": items })))
+}
+
 #[instrument(skip(st))]
 pub async fn space_refresh(Query(q): Query<HashMap<String,String>>, State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
-    let list = q.get("src").cloned().unwrap_or_else(|| "apod,neo,flr,cme,spacex".to_string());
+    let list = q.get("src").cloned().unwrap_or_else(|| st.cache_service.sources().names().join(","));
     info!("Refreshing space data for sources: {}", list);
     let mut done = Vec::new();
+    let mut failed = serde_json::Map::new();
     for s in list.split(',').map(|x| x.trim().to_lowercase()) {
-        match s.as_str() {
-            "apod"   => { let _ = super::fetch_apod(&st).await;       done.push("apod"); }
-            "neo"    => { let _ = super::fetch_neo_feed(&st).await;   done.push("neo"); }
-            "flr"    => { let _ = super::fetch_donki_flr(&st).await;  done.push("flr"); }
-            "cme"    => { let _ = super::fetch_donki_cme(&st).await;  done.push("cme"); }
-            "spacex" => { let _ = super::fetch_spacex_next(&st).await; done.push("spacex"); }
-            _ => {
-                warn!("Unknown source: {}", s);
+        if st.cache_service.sources().get(&s).is_none() {
+            warn!("Unknown source: {}", s);
+            continue;
+        }
+        match super::fetch_space_source(&st, &s).await {
+            Ok(_) => done.push(s),
+            Err(e) => {
+                warn!("Failed to refresh {}: {}", s, e);
+                failed.insert(s, Value::String(e.to_string()));
             }
         }
     }
-    info!("Refreshed {} sources", done.len());
-    Ok(Json(serde_json::json!({ "refreshed": done })))
+    info!("Refreshed {} sources, {} failed", done.len(), failed.len());
+    Ok(Json(serde_json::json!({ "refreshed": done, "failed": failed })))
 }
 
-async fn latest_from_cache(pool: &sqlx::PgPool, src: &str) -> Value {
-    sqlx::query("SELECT fetched_at, payload FROM space_cache WHERE source=$1 ORDER BY id DESC LIMIT 1")
-        .bind(src)
-        .fetch_optional(pool).await.ok().flatten()
-        .map(|r| serde_json::json!({"at": r.get::<DateTime<chrono::Utc>,_>("fetched_at"), "payload": r.get::<Value,_>("payload")}))
-        .unwrap_or(serde_json::json!({}))
+/// `{at, last_seen_at, payload, stale, age_seconds}` view of a cached entry, or `{}` when absent
+fn summary_entry(cached: Option<CachedSource>) -> Value {
+    match cached {
+        Some(c) => serde_json::json!({
+            "at": c.entry.fetched_at, "last_seen_at": c.entry.last_seen_at, "payload": c.entry.payload,
+            "stale": c.stale, "age_seconds": c.age_seconds
+        }),
+        None => serde_json::json!({}),
+    }
 }
 
 #[instrument(skip(st))]
 pub async fn space_summary(State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
     info!("Generating space data summary");
-    let apod   = latest_from_cache(&st.pool, "apod").await;
-    let neo    = latest_from_cache(&st.pool, "neo").await;
-    let flr    = latest_from_cache(&st.pool, "flr").await;
-    let cme    = latest_from_cache(&st.pool, "cme").await;
-    let spacex = latest_from_cache(&st.pool, "spacex").await;
+    let summary = st.cache_service.get_space_summary().await
+        .map_err(|e| {
+            error!("Failed to build space summary: {:?}", e);
+            ApiError::internal_error("Failed to build space summary")
+        })?;
 
-    let iss_last = sqlx::query("SELECT fetched_at,payload FROM iss_fetch_log ORDER BY id DESC LIMIT 1")
-        .fetch_optional(&st.pool).await?
-        .map(|r| serde_json::json!({"at": r.get::<DateTime<chrono::Utc>,_>("fetched_at"), "payload": r.get::<Value,_>("payload")}))
+    let iss_last = summary.iss
+        .map(|i| serde_json::json!({"at": i.fetched_at, "payload": i.payload}))
         .unwrap_or(serde_json::json!({}));
 
-    let osdr_count: i64 = sqlx::query("SELECT count(*) AS c FROM osdr_items")
-        .fetch_one(&st.pool).await?
-        .get::<i64,_>("c");
+    info!("Space summary generated with {} OSDR items", summary.osdr_count);
+    let mut body = serde_json::Map::new();
+    for (name, cached) in summary.sources {
+        body.insert(name.to_string(), summary_entry(cached));
+    }
+    body.insert("iss".to_string(), iss_last);
+    body.insert("osdr_count".to_string(), summary.osdr_count.into());
+    Ok(Json(Value::Object(body)))
+}
 
-    info!("Space summary generated with {} OSDR items", osdr_count);
-    Ok(Json(serde_json::json!({
-        "apod": apod, "neo": neo, "flr": flr, "cme": cme, "spacex": spacex,
-        "iss": iss_last, "osdr_count": osdr_count
-    })))
+#[instrument(skip(st))]
+pub async fn cache_stats(State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let stats = st.cache_service.get_cache_stats();
+    Ok(Json(serde_json::json!({ "redis": st.redis_repo.is_some(), "sources": stats })))
 }
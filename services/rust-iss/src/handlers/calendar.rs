@@ -0,0 +1,92 @@
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use tracing::{error, info, instrument};
+
+use crate::{
+    AppState,
+    handlers::{ApiError, parse_day},
+    services::{
+        CalendarEvent, CalendarEventKind, CalendarFilter, cme_events, flare_events, iss_pass_events,
+        launch_events, render_ics,
+    },
+};
+
+/// iCalendar feed of cached launches, DONKI events and predicted ISS passes.
+///
+/// Query parameters:
+/// - `types` (or `kind`): comma-separated list of `launch`, `flr`, `cme`,
+///   `iss` (default: all)
+/// - `from`, `to`: inclusive date range as `YYYY-MM-DD`
+#[instrument(skip(st))]
+pub async fn calendar_ics(
+    Query(q): Query<HashMap<String, String>>,
+    State(st): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let filter = parse_calendar_filter(&q)?;
+    info!("Rendering calendar feed for {:?}", filter.kinds);
+
+    let mut events: Vec<CalendarEvent> = Vec::new();
+    for kind in &filter.kinds {
+        let (source, build): (&str, fn(&_) -> Vec<CalendarEvent>) = match kind {
+            CalendarEventKind::Launch => ("spacex", launch_events),
+            CalendarEventKind::SolarFlare => ("flr", flare_events),
+            CalendarEventKind::Cme => ("cme", cme_events),
+            CalendarEventKind::IssPass => {
+                // Passes are predicted from the two latest ISS fixes
+                let track = st.iss_service.get_iss_trend_points(2).await
+                    .map_err(|e| {
+                        error!("Failed to load ISS positions: {:?}", e);
+                        ApiError::internal_error("Failed to load calendar data")
+                    })?;
+                let passes = iss_pass_events(&track, &st.config.calendar.observers);
+                events.extend(passes.into_iter().filter(|e| filter.matches(e)));
+                continue;
+            }
+        };
+        let entry = st.cache_service.get_latest_cache_entry(source).await
+            .map_err(|e| {
+                error!("Failed to load cached {} data: {:?}", source, e);
+                ApiError::internal_error("Failed to load calendar data")
+            })?;
+        if let Some(entry) = entry {
+            events.extend(build(&entry).into_iter().filter(|e| filter.matches(e)));
+        }
+    }
+    events.sort_by_key(|e| e.starts_at);
+
+    info!("Calendar feed rendered with {} events", events.len());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "inline; filename=\"calendar.ics\""),
+        ],
+        render_ics(&events, Utc::now()),
+    ))
+}
+
+fn parse_calendar_filter(q: &HashMap<String, String>) -> Result<CalendarFilter, ApiError> {
+    let mut filter = CalendarFilter::default();
+
+    let types = q.get("types").or_else(|| q.get("kind"));
+    if let Some(types) = types.filter(|s| !s.trim().is_empty()) {
+        let mut kinds = Vec::new();
+        for t in types.split(',') {
+            let kind = CalendarEventKind::parse(t)
+                .ok_or_else(|| ApiError::bad_request(format!("Unknown event type: {}", t.trim())))?;
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        filter.kinds = kinds;
+    }
+
+    filter.from = q.get("from").map(|s| parse_day(s, "from", false)).transpose()?;
+    filter.to = q.get("to").map(|s| parse_day(s, "to", true)).transpose()?;
+
+    Ok(filter)
+}
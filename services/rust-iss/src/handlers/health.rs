@@ -0,0 +1,120 @@
+use axum::{extract::State, Json};
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{error, instrument};
+
+use crate::{AppState, clients::CircuitState, domain::MigrationState, handlers::ApiError, services::MigrationService};
+
+/// Schema migration status: `ok` when everything embedded is applied,
+/// `pending` when migrations are waiting, `error` on failed or edited ones
+#[instrument(skip(service))]
+pub async fn migration_status(
+    State(service): State<Arc<dyn MigrationService + Send + Sync>>,
+) -> Result<Json<Value>, ApiError> {
+    let migrations = service.migration_status().await
+        .map_err(|e| {
+            error!("Failed to read migration status: {:?}", e);
+            ApiError::internal_error("Failed to read migration status")
+        })?;
+
+    let count = |state: MigrationState| migrations.iter().filter(|m| m.state == state).count();
+    let pending = count(MigrationState::Pending);
+    let broken = count(MigrationState::Failed) + count(MigrationState::ChecksumMismatch);
+    let status = match (broken, pending) {
+        (0, 0) => "ok",
+        (0, _) => "pending",
+        _ => "error",
+    };
+    let current = migrations.iter()
+        .filter(|m| m.state == MigrationState::Applied)
+        .map(|m| m.version)
+        .max();
+
+    Ok(Json(serde_json::json!({
+        "status": status, "current_version": current, "pending": pending, "migrations": migrations
+    })))
+}
+
+/// Circuit breaker state of every upstream host called so far: `ok` when all
+/// circuits are closed, `degraded` while any host is failing fast or probing.
+/// Also reports the remaining hourly quota of each NASA API key.
+#[instrument(skip(st))]
+pub async fn upstream_status(State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let upstreams = st.circuit_breakers.snapshot();
+    let unavailable = upstreams.iter().filter(|u| u.state != CircuitState::Closed).count();
+    let status = if unavailable == 0 { "ok" } else { "degraded" };
+    Ok(Json(serde_json::json!({
+        "status": status, "unavailable": unavailable, "upstreams": upstreams,
+        "nasa_quota": st.nasa_quota.snapshot()
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::domain::MigrationStatus;
+    use crate::services::{Result as ServiceResult, ServiceError};
+
+    struct MockMigrationService {
+        status: ServiceResult<Vec<MigrationStatus>>,
+    }
+
+    #[async_trait]
+    impl MigrationService for MockMigrationService {
+        async fn apply_migrations(&self) -> ServiceResult<Vec<i64>> {
+            unimplemented!()
+        }
+
+        async fn revert_migrations(&self, _target: i64) -> ServiceResult<Vec<i64>> {
+            unimplemented!()
+        }
+
+        async fn migration_status(&self) -> ServiceResult<Vec<MigrationStatus>> {
+            self.status.clone()
+        }
+    }
+
+    fn migration(version: i64, state: MigrationState) -> MigrationStatus {
+        MigrationStatus {
+            version,
+            description: format!("migration {}", version),
+            state,
+            reversible: true,
+            checksum: String::new(),
+            installed_on: None,
+            execution_ms: None,
+        }
+    }
+
+    async fn status_of(status: ServiceResult<Vec<MigrationStatus>>) -> Result<Value, ApiError> {
+        let service: Arc<dyn MigrationService + Send + Sync> = Arc::new(MockMigrationService { status });
+        migration_status(State(service)).await.map(|Json(body)| body)
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_summarises_the_service_report() {
+        let body = status_of(Ok(vec![
+            migration(1, MigrationState::Applied),
+            migration(2, MigrationState::Applied),
+            migration(3, MigrationState::Pending),
+        ]))
+        .await
+        .unwrap();
+        assert_eq!(body["status"], "pending");
+        assert_eq!(body["current_version"], 2);
+        assert_eq!(body["pending"], 1);
+        assert_eq!(body["migrations"][2]["state"], "pending");
+
+        let body = status_of(Ok(vec![migration(1, MigrationState::ChecksumMismatch)])).await.unwrap();
+        assert_eq!(body["status"], "error");
+        assert_eq!(body["current_version"], Value::Null);
+
+        let body = status_of(Ok(vec![migration(1, MigrationState::Applied)])).await.unwrap();
+        assert_eq!(body["status"], "ok");
+
+        let err = status_of(Err(ServiceError::RepositoryError("connection refused".to_string()))).await.unwrap_err();
+        assert_eq!(err.status, 500);
+        assert_eq!(err.message, "Failed to read migration status");
+    }
+}
@@ -7,7 +7,7 @@ use serde::Serialize;
 use serde_json::Value;
 use tracing::{error, info, instrument};
 
-use crate::{AppState, domain::IssData, services::{IssService, IssPoint}, handlers::ApiError};
+use crate::{AppState, domain::IssData, services::IssPoint, handlers::ApiError};
 
 #[derive(Serialize)]
 pub struct IssResponse {
@@ -0,0 +1,49 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{error, instrument};
+
+use crate::{AppState, handlers::ApiError};
+
+const DEFAULT_RUN_LIMIT: i64 = 20;
+const MAX_RUN_LIMIT: i64 = 200;
+
+/// Registered background jobs with their schedule, next and last run
+#[instrument(skip(st))]
+pub async fn jobs_list(State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let jobs = st.scheduler.jobs().await
+        .map_err(|e| {
+            error!("Failed to read job runs: {:?}", e);
+            ApiError::internal_error("Failed to read job runs")
+        })?;
+    Ok(Json(serde_json::json!({ "count": jobs.len(), "jobs": jobs })))
+}
+
+/// Recorded runs of one job, newest first
+#[instrument(skip(st))]
+pub async fn job_runs(
+    Path(name): Path<String>,
+    Query(q): Query<HashMap<String, String>>,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    if !st.scheduler.has_job(&name) {
+        return Err(ApiError::not_found(format!("Unknown job '{}'", name)));
+    }
+    let limit = match q.get("limit") {
+        Some(raw) => raw.parse::<i64>()
+            .ok()
+            .filter(|l| (1..=MAX_RUN_LIMIT).contains(l))
+            .ok_or_else(|| ApiError::bad_request(format!("limit must be between 1 and {}", MAX_RUN_LIMIT)))?,
+        None => DEFAULT_RUN_LIMIT,
+    };
+
+    let runs = st.scheduler.runs(&name, limit).await
+        .map_err(|e| {
+            error!("Failed to read runs of job {}: {:?}", name, e);
+            ApiError::internal_error("Failed to read job runs")
+        })?;
+    Ok(Json(serde_json::json!({ "job": name, "count": runs.len(), "runs": runs })))
+}
@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{error, info, instrument};
+
+use crate::{AppState, domain::JwstFilter, handlers::ApiError};
+
+const JWST_INSTRUMENTS: [&str; 5] = ["NIRCAM", "MIRI", "NIRISS", "NIRSPEC", "FGS"];
+
+#[instrument(skip(st))]
+pub async fn jwst_sync(State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
+    info!("Starting JWST feed synchronization");
+    let written = st.jwst_service.sync_jwst_feed().await
+        .map_err(|e| {
+            error!("Failed to sync JWST feed: {:?}", e);
+            ApiError::internal_error("Failed to sync JWST feed")
+        })?;
+    info!("JWST sync completed with {} observations", written);
+    Ok(Json(serde_json::json!({ "message": "JWST sync completed", "written": written })))
+}
+
+/// JWST image feed served from Postgres.
+///
+/// Query parameters: `program`, `instrument`, `suffix`, `page`, `perPage`
+#[instrument(skip(st))]
+pub async fn jwst_feed(
+    Query(q): Query<HashMap<String, String>>,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    let program = q.get("program").map(|s| s.trim()).filter(|s| !s.is_empty());
+    if let Some(p) = program {
+        if !p.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ApiError::bad_request("program must be a numeric JWST program ID"));
+        }
+    }
+
+    let instrument = q.get("instrument").map(|s| s.trim().to_uppercase()).filter(|s| !s.is_empty());
+    if let Some(ref i) = instrument {
+        if !JWST_INSTRUMENTS.contains(&i.as_str()) {
+            return Err(ApiError::bad_request(format!("instrument must be one of: {}", JWST_INSTRUMENTS.join(", "))));
+        }
+    }
+
+    let suffix = q.get("suffix").map(|s| s.trim()).filter(|s| !s.is_empty());
+    if let Some(s) = suffix {
+        if s.len() > 32 || !s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(ApiError::bad_request("suffix must be a product suffix such as _i2d"));
+        }
+    }
+
+    let page = parse_positive(&q, "page", 1)?;
+    let per_page = parse_positive(&q, "perPage", 24)?;
+
+    let filter = JwstFilter {
+        program: program.map(|s| s.to_string()),
+        instrument,
+        suffix: suffix.map(|s| s.to_string()),
+    };
+    info!("Retrieving JWST feed with {:?}, page: {}, perPage: {}", filter, page, per_page);
+
+    let feed = st.jwst_service.get_jwst_feed(&filter, page, per_page).await
+        .map_err(|e| {
+            error!("Failed to retrieve JWST feed: {:?}", e);
+            ApiError::internal_error("Failed to retrieve JWST feed")
+        })?;
+
+    info!("Retrieved {} of {} JWST observations", feed.items.len(), feed.total);
+    Ok(Json(serde_json::to_value(feed).map_err(|e| ApiError::internal_error(e.to_string()))?))
+}
+
+fn parse_positive(q: &HashMap<String, String>, name: &str, default: i64) -> Result<i64, ApiError> {
+    match q.get(name) {
+        None => Ok(default),
+        Some(s) => s.trim().parse::<i64>().ok().filter(|v| *v >= 1)
+            .ok_or_else(|| ApiError::bad_request(format!("{} must be a positive integer", name))),
+    }
+}
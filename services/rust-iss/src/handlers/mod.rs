@@ -1,20 +1,35 @@
 pub mod iss;
 pub mod osdr;
 pub mod cache;
+pub mod calendar;
+pub mod jwst;
+pub mod astro;
+pub mod telemetry;
+pub mod admin;
+pub mod health;
+pub mod jobs;
 
 pub use iss::*;
 pub use osdr::*;
 pub use cache::*;
+pub use calendar::*;
+pub use jwst::*;
+pub use astro::*;
+pub use telemetry::*;
+pub use admin::*;
+pub use health::*;
+pub use jobs::*;
 
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::{NaiveDate, TimeZone, Utc};
 use serde::Serialize;
 use tracing::{error, warn};
 
-use crate::domain::DomainError;
+use crate::domain::{DomainError, Timestamp};
 
 #[derive(Debug, Serialize)]
 pub struct ApiError {
@@ -53,6 +68,14 @@ impl ApiError {
     pub fn service_unavailable(message: impl Into<String>) -> Self {
         Self::new(StatusCode::SERVICE_UNAVAILABLE, message)
     }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, message)
+    }
 }
 
 impl IntoResponse for ApiError {
@@ -90,13 +113,6 @@ impl From<anyhow::Error> for ApiError {
     }
 }
 
-impl From<sqlx::Error> for ApiError {
-    fn from(err: sqlx::Error) -> Self {
-        error!("Database error: {:?}", err);
-        ApiError::internal_error("Database operation failed")
-    }
-}
-
 impl From<reqwest::Error> for ApiError {
     fn from(err: reqwest::Error) -> Self {
         error!("HTTP client error: {:?}", err);
@@ -104,47 +120,51 @@ impl From<reqwest::Error> for ApiError {
     }
 }
 
+/// Parse a `YYYY-MM-DD` query parameter as the start (or end) of that day in UTC
+pub(crate) fn parse_day(s: &str, name: &str, end_of_day: bool) -> Result<Timestamp, ApiError> {
+    let date = NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+        .map_err(|_| ApiError::bad_request(format!("{} must be a date in YYYY-MM-DD format", name)))?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(Utc.from_utc_datetime(&time.expect("valid time of day")))
+}
+
 use crate::AppState;
-use crate::services::{IssService, OsdrService, CacheService};
+use crate::scheduler::JobResult;
 
-pub async fn fetch_and_store_iss(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.iss_service.trigger_iss_fetch().await?;
-    Ok(())
-}
+// Scheduled job bodies; each reports how many items it fetched or wrote
 
-pub async fn fetch_and_store_osdr(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.osdr_service.sync_osdr_data(&st.config.osdr.api_url).await?;
-    Ok(())
+pub async fn fetch_and_store_iss(st: &AppState) -> JobResult {
+    st.iss_service.trigger_iss_fetch().await?;
+    Ok(1)
 }
 
-pub async fn fetch_apod(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.cache_service.fetch_and_cache_apod(st.config.nasa.api_key.as_deref()).await?;
-    Ok(())
+pub async fn fetch_and_store_osdr(st: &AppState) -> JobResult {
+    Ok(st.osdr_service.sync_osdr_data().await? as u64)
 }
 
-pub async fn fetch_neo_feed(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.cache_service.fetch_and_cache_neo_feed(st.config.nasa.api_key.as_deref()).await?;
-    Ok(())
+pub async fn fetch_space_source(st: &AppState, source: &str) -> JobResult {
+    st.cache_service.fetch_and_cache_source(source).await?;
+    Ok(1)
 }
 
-pub async fn fetch_donki_flr(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.cache_service.fetch_donki_flr(st.config.nasa.api_key.as_deref()).await?;
-    Ok(())
+pub async fn fetch_jwst(st: &AppState) -> JobResult {
+    Ok(st.jwst_service.sync_jwst_feed().await? as u64)
 }
 
-pub async fn fetch_donki_cme(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.cache_service.fetch_donki_cme(st.config.nasa.api_key.as_deref()).await?;
-    Ok(())
+pub async fn fetch_astro_events(st: &AppState) -> JobResult {
+    Ok(st.astro_service.sync_astro_events().await? as u64)
 }
 
-pub async fn fetch_donki(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.cache_service.fetch_and_cache_donki_data(st.config.nasa.api_key.as_deref()).await?;
-    Ok(())
+pub async fn ingest_telemetry(st: &AppState) -> JobResult {
+    Ok(st.telemetry_service.ingest_drop_directory().await?.rows_inserted as u64)
 }
 
-pub async fn fetch_spacex_next(st: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    st.cache_service.fetch_and_cache_spacex_next().await?;
-    Ok(())
+pub async fn run_compaction(st: &AppState) -> JobResult {
+    Ok(st.retention_service.run_compaction().await?.rows_removed as u64)
 }
 
 #[cfg(test)]
@@ -169,6 +189,17 @@ mod tests {
         assert_eq!(error.trace_id, Some("trace-123".to_string()));
     }
 
+    #[test]
+    fn test_parse_day() {
+        let from = parse_day("2026-01-31", "from", false).unwrap();
+        let to = parse_day("2026-01-31", "to", true).unwrap();
+        assert_eq!(from.to_rfc3339(), "2026-01-31T00:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2026-01-31T23:59:59+00:00");
+
+        let err = parse_day("31.01.2026", "from", false).unwrap_err();
+        assert_eq!(err.status, 400);
+    }
+
     #[test]
     fn test_api_error_convenience_methods() {
         let not_found = ApiError::not_found("Not found");
@@ -182,5 +213,8 @@ mod tests {
         let unavailable = ApiError::service_unavailable("Service unavailable");
         assert_eq!(unavailable.status, 503);
         assert_eq!(unavailable.message, "Service unavailable");
+
+        assert_eq!(ApiError::unauthorized("Missing token").status, 401);
+        assert_eq!(ApiError::forbidden("Disabled").status, 403);
     }
 }
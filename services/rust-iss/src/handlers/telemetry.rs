@@ -0,0 +1,114 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{error, info, instrument};
+
+use crate::{
+    AppState,
+    domain::Timestamp,
+    handlers::{ApiError, parse_day},
+    services::ServiceError,
+};
+
+/// Upper bound on buckets per request to keep responses reasonably small
+const MAX_BUCKETS: i64 = 5000;
+
+/// Scan the legacy telemetry drop directory immediately
+#[instrument(skip(st))]
+pub async fn telemetry_ingest(State(st): State<AppState>) -> Result<Json<Value>, ApiError> {
+    info!("Starting legacy telemetry ingestion");
+    let report = st.telemetry_service.ingest_drop_directory().await
+        .map_err(|e| {
+            error!("Failed to ingest telemetry files: {:?}", e);
+            ApiError::internal_error("Failed to ingest telemetry files")
+        })?;
+    info!("Telemetry ingestion completed: {:?}", report);
+    Ok(Json(serde_json::to_value(report).map_err(|e| ApiError::internal_error(e.to_string()))?))
+}
+
+/// Aggregated legacy telemetry.
+///
+/// Query parameters:
+/// - `from`, `to`: RFC 3339 timestamps or `YYYY-MM-DD` dates (default: last 24 hours)
+/// - `bucket`: bucket width such as `30s`, `5m`, `1h`, `1d` (default: `5m`)
+#[instrument(skip(st))]
+pub async fn telemetry_summary(
+    Query(q): Query<HashMap<String, String>>,
+    State(st): State<AppState>,
+) -> Result<Json<Value>, ApiError> {
+    let to = q.get("to").map(|s| parse_bound(s, "to", true)).transpose()?.unwrap_or_else(Utc::now);
+    let from = q.get("from").map(|s| parse_bound(s, "from", false)).transpose()?
+        .unwrap_or_else(|| to - Duration::hours(24));
+    if from >= to {
+        return Err(ApiError::bad_request("from must be before to"));
+    }
+
+    let bucket_seconds = q.get("bucket").map(|s| parse_bucket(s)).transpose()?.unwrap_or(300);
+    if (to - from).num_seconds() / bucket_seconds > MAX_BUCKETS {
+        return Err(ApiError::bad_request(format!("range covers more than {} buckets, use a wider bucket", MAX_BUCKETS)));
+    }
+
+    info!("Aggregating telemetry from {} to {} in {}s buckets", from, to, bucket_seconds);
+    let summary = st.telemetry_service.get_telemetry_summary(from, to, bucket_seconds).await
+        .map_err(|e| match e {
+            ServiceError::ValidationError(msg) => ApiError::bad_request(msg),
+            e => {
+                error!("Failed to aggregate telemetry: {:?}", e);
+                ApiError::internal_error("Failed to aggregate telemetry")
+            }
+        })?;
+
+    info!("Aggregated {} telemetry readings into {} buckets", summary.readings, summary.buckets.len());
+    Ok(Json(serde_json::to_value(summary).map_err(|e| ApiError::internal_error(e.to_string()))?))
+}
+
+fn parse_bound(s: &str, name: &str, end_of_day: bool) -> Result<Timestamp, ApiError> {
+    match DateTime::parse_from_rfc3339(s.trim()) {
+        Ok(dt) => Ok(dt.with_timezone(&Utc)),
+        Err(_) => parse_day(s, name, end_of_day),
+    }
+}
+
+/// Parse a bucket width like `90s`, `5m`, `1h` or `1d` into seconds
+fn parse_bucket(s: &str) -> Result<i64, ApiError> {
+    let s = s.trim();
+    let invalid = || ApiError::bad_request("bucket must look like 30s, 5m, 1h or 1d");
+    let (digits, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let value: i64 = digits.parse().ok().filter(|v| *v > 0).ok_or_else(invalid)?;
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(invalid()),
+    };
+    value.checked_mul(multiplier).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bucket() {
+        assert_eq!(parse_bucket("5m").unwrap(), 300);
+        assert_eq!(parse_bucket("90").unwrap(), 90);
+        assert_eq!(parse_bucket("1d").unwrap(), 86400);
+        assert!(parse_bucket("0m").is_err());
+        assert!(parse_bucket("5w").is_err());
+        assert!(parse_bucket("m").is_err());
+    }
+
+    #[test]
+    fn test_parse_bound() {
+        let at = parse_bound("2026-01-01T12:00:00Z", "from", false).unwrap();
+        assert_eq!(at.timestamp(), 1767268800);
+        let day_end = parse_bound("2026-01-01", "to", true).unwrap();
+        assert_eq!(day_end.timestamp(), 1767311999);
+        assert!(parse_bound("yesterday", "from", false).is_err());
+    }
+}
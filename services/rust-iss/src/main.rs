@@ -1,13 +1,13 @@
-use std::{collections::HashMap, default::Default, time::Duration};
+use std::{collections::HashMap, default::Default, sync::Arc, time::Duration};
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::{FromRef, Path, Query, State},
     http::StatusCode,
     Json,
 };
 use chrono::{NaiveDateTime, TimeZone, Utc};
 use serde_json::Value;
-use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+use sqlx::postgres::PgPoolOptions;
 use tokio::signal;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
@@ -22,26 +22,45 @@ mod config;
 mod handlers;
 mod middleware;
 mod routes;
+mod scheduler;
 
 use domain::*;
 use repo::*;
 use services::*;
-use clients::{NasaClient, NasaClientImpl, IssClient, IssClientImpl, SpaceXClient, SpaceXClientImpl};
+use clients::{CircuitBreakers, NasaClient, NasaClientImpl, NasaQuota, IssClient, IssClientImpl, SpaceXClient, SpaceXClientImpl, JwstClientImpl, AstroClientImpl};
 use config::*;
+use scheduler::{Schedule, Scheduler};
 
+/// Handlers reach the database only through these services, so tests can
+/// swap any of them for a mock
 #[derive(Clone)]
 struct AppState {
-    pool: PgPool,
     redis_repo: Option<RedisRepos>,
-    iss_service: IssServiceImpl<PgRepos, IssClientImpl>,
-    osdr_service: OsdrServiceImpl<PgRepos, NasaClientImpl>,
-    cache_service: CacheServiceImpl<PgRepos, NasaClientImpl, SpaceXClientImpl>,
+    migration_service: Arc<dyn MigrationService + Send + Sync>,
+    iss_service: Arc<dyn IssService + Send + Sync>,
+    osdr_service: Arc<dyn OsdrService + Send + Sync>,
+    cache_service: Arc<dyn CacheService + Send + Sync>,
+    jwst_service: Arc<dyn JwstService + Send + Sync>,
+    astro_service: Arc<dyn AstroService + Send + Sync>,
+    telemetry_service: Arc<dyn TelemetryService + Send + Sync>,
+    retention_service: Arc<dyn RetentionService + Send + Sync>,
+    circuit_breakers: CircuitBreakers,
+    nasa_quota: NasaQuota,
     nasa_client: NasaClientImpl,
     iss_client: IssClientImpl,
     spacex_client: SpaceXClientImpl,
+    scheduler: Scheduler<PgRepos>,
     config: AppConfig,
 }
 
+/// Lets a handler extract only the service it uses, so it can be tested
+/// against a mock without building a whole `AppState`
+impl FromRef<AppState> for Arc<dyn MigrationService + Send + Sync> {
+    fn from_ref(st: &AppState) -> Self {
+        st.migration_service.clone()
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let subscriber = FmtSubscriber::builder()
@@ -58,6 +77,28 @@ async fn main() -> anyhow::Result<()> {
         .max_connections(config.database.max_connections)
         .connect(&config.database.url)
         .await?;
+    let migration_service = MigrationServiceImpl::new(PgRepos::new(pool.clone()));
+
+    // `rust_iss migrate ...` manages the schema and exits without serving
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = args.first() {
+        if command != "migrate" {
+            anyhow::bail!("Unknown command '{}'. {}", command, MIGRATE_USAGE);
+        }
+        return run_migrate_command(&migration_service, &args[1..]).await;
+    }
+
+    if config.database.migrate_on_startup {
+        let applied = migration_service.apply_migrations().await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        if applied.is_empty() {
+            info!("Database schema is up to date");
+        } else {
+            info!("Applied database migrations: {:?}", applied);
+        }
+    } else {
+        info!("DATABASE_MIGRATE_ON_STARTUP=false, skipping migrations");
+    }
 
     // Initialize Redis repository (optional)
     let redis_repo = if let Some(redis_config) = &config.redis {
@@ -80,41 +121,124 @@ async fn main() -> anyhow::Result<()> {
     let iss_repo = PgRepos::new(pool.clone());
     let osdr_repo = PgRepos::new(pool.clone());
     let cache_repo = PgRepos::new(pool.clone());
+    let jwst_repo = PgRepos::new(pool.clone());
+    let astro_repo = PgRepos::new(pool.clone());
+    let telemetry_repo = PgRepos::new(pool.clone());
+    let retention_repo = PgRepos::new(pool.clone());
+
+    // Initialize HTTP clients; each upstream may override the shared timeout
+    let http_config = &config.http_client;
+    // One circuit per upstream host, whichever client calls it
+    let circuit_breakers = CircuitBreakers::new(config.http_client.circuit_breaker.clone());
+    // Hourly quota of the NASA keys, shared by every source that needs one
+    let nasa_quota = NasaQuota::new(config.nasa.api_keys.clone())
+        .with_reserves(config.nasa.quota_reserve_normal, config.nasa.quota_reserve_low);
+    let nasa_client = NasaClientImpl::with_base_url(http_config.with_timeout(config.nasa.timeout), config.nasa.api_url.clone())
+        .with_osdr_url(config.osdr.api_url.clone())
+        .with_circuit_breakers(circuit_breakers.clone())
+        .with_quota(nasa_quota.clone());
+    let iss_client = IssClientImpl::new(http_config.with_timeout(config.iss.timeout))
+        .with_position_url(config.iss.api_url.clone())
+        .with_circuit_breakers(circuit_breakers.clone());
+    let spacex_client = SpaceXClientImpl::with_base_url(http_config.with_timeout(config.spacex.timeout), config.spacex.api_url.clone())
+        .with_circuit_breakers(circuit_breakers.clone());
+    let jwst_client = JwstClientImpl::with_base_url(http_config.with_timeout(config.jwst.timeout), config.jwst.api_url.clone())
+        .with_credentials(config.jwst.api_key.clone(), config.jwst.email.clone())
+        .with_circuit_breakers(circuit_breakers.clone());
+    let astro_client = AstroClientImpl::with_base_url(
+        http_config.with_timeout(config.astro.timeout),
+        config.astro.app_id.as_deref().unwrap_or_default(),
+        config.astro.app_secret.as_deref().unwrap_or_default(),
+        config.astro.api_url.clone(),
+    )
+    .with_circuit_breakers(circuit_breakers.clone());
 
-    // Initialize HTTP clients
-    let http_config = HttpClientConfig::default();
-    let nasa_client = NasaClientImpl::new(http_config.clone());
-    let iss_client = IssClientImpl::new(http_config.clone());
-    let spacex_client = SpaceXClientImpl::new(http_config.clone());
+    // Space sources fetched on a schedule into space_cache
+    let space_sources = SourceRegistry::new()
+        .register(ApodSource::new(nasa_client.clone())
+            .with_schedule(Duration::from_secs(config.nasa.fetch_intervals.apod)))
+        .register(NeoFeedSource::new(nasa_client.clone())
+            .with_schedule(Duration::from_secs(config.nasa.fetch_intervals.neo)))
+        .register(DonkiFlrSource::new(nasa_client.clone())
+            .with_schedule(Duration::from_secs(config.nasa.fetch_intervals.donki)))
+        .register(DonkiCmeSource::new(nasa_client.clone())
+            .with_schedule(Duration::from_secs(config.nasa.fetch_intervals.donki)))
+        .register(SpaceXNextSource::new(spacex_client.clone())
+            .with_schedule(Duration::from_secs(config.spacex.fetch_interval)));
 
     // Initialize services with dependency injection
-    let iss_service = IssServiceImpl::new(iss_repo, iss_client.clone());
-    let osdr_service = OsdrServiceImpl::new(osdr_repo, nasa_client.clone());
-    let mut cache_service = CacheServiceImpl::new(cache_repo, nasa_client.clone(), spacex_client.clone());
+    let cache_layer = CacheLayer::new(
+        redis_repo.clone().map(|r| Arc::new(r) as Arc<dyn RedisRepo + Send + Sync>),
+        &config.cache,
+    )
+    .with_default_ttls(space_sources.iter().map(|s| (s.name().to_string(), s.ttl().as_secs())));
+    let iss_service = IssServiceImpl::new(iss_repo, iss_client.clone())
+        .with_cache(cache_layer.clone());
+    let osdr_service = OsdrServiceImpl::new(osdr_repo, nasa_client.clone())
+        .with_cache(cache_layer.clone());
+    let cache_service = CacheServiceImpl::new(cache_repo, space_sources)
+        .with_cache(cache_layer.clone())
+        .with_quota(nasa_quota.clone());
+    let jwst_service = JwstServiceImpl::new(jwst_repo, jwst_client)
+        .with_programs(config.jwst.program_ids.clone(), config.jwst.page_size);
+    let astro_service = AstroServiceImpl::new(astro_repo, astro_client)
+        .with_observers(config.astro.observers.clone(), config.astro.horizon_days);
+    let telemetry_service = TelemetryServiceImpl::new(telemetry_repo)
+        .with_directories(
+            config.telemetry.drop_dir.clone(),
+            config.telemetry.archive_dir.clone(),
+            config.telemetry.quarantine_dir.clone(),
+        )
+        .with_settle_time(Duration::from_secs(config.telemetry.settle_seconds))
+        .with_envelope(config.telemetry.envelope);
+    let retention_service = RetentionServiceImpl::new(retention_repo, config.retention.clone());
+    // ISS inserts need a partition for the current month
+    if let Err(e) = retention_service.ensure_partitions().await {
+        warn!("Failed to create iss_fetch_log partitions: {}", e);
+    }
 
-    // Add Redis support to cache service if available
-    if let Some(ref redis_repo) = redis_repo {
-        cache_service = cache_service.with_redis(redis_repo.clone());
+    // Replicas sharing the database each run a job only while holding its lock
+    let mut scheduler = Scheduler::new(PgRepos::new(pool.clone())).with_config(&config.scheduler);
+    if config.scheduler.leader_election {
+        scheduler = scheduler.with_locks(Arc::new(PgJobLocks::new(pool.clone())));
+    } else {
+        info!("SCHEDULER_LEADER_ELECTION=false, every background job runs on this replica");
     }
 
     // Create application state
     let state = AppState {
-        pool: pool.clone(),
         redis_repo,
-        iss_service,
-        osdr_service,
-        cache_service,
+        migration_service: Arc::new(migration_service),
+        iss_service: Arc::new(iss_service),
+        osdr_service: Arc::new(osdr_service),
+        cache_service: Arc::new(cache_service),
+        jwst_service: Arc::new(jwst_service),
+        astro_service: Arc::new(astro_service),
+        telemetry_service: Arc::new(telemetry_service),
+        retention_service: Arc::new(retention_service),
+        circuit_breakers,
+        nasa_quota,
         nasa_client: nasa_client.clone(),
         iss_client: iss_client.clone(),
         spacex_client: spacex_client.clone(),
+        scheduler,
         config: config.clone(),
     };
 
     // Create cancellation token for graceful shutdown
     let shutdown_token = CancellationToken::new();
 
-    // Spawn background tasks with cancellation support
-    spawn_background_tasks(state.clone(), shutdown_token.clone());
+    // Schedule background jobs with cancellation support
+    register_jobs(&state);
+    state.scheduler.start(shutdown_token.clone());
+
+    // Drop in-process cache state when another replica purges Redis
+    if let Some(redis) = state.redis_repo.clone() {
+        let token = shutdown_token.clone();
+        tokio::spawn(async move {
+            cache_layer.listen_for_invalidations(redis, token).await;
+        });
+    }
 
     // Create Axum app
     let app = routes::create_routes()
@@ -146,139 +270,93 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Spawn all background tasks with cancellation support
-fn spawn_background_tasks(state: AppState, shutdown_token: CancellationToken) {
-    // OSDR background task
-    {
-        let st = state.clone();
-        let token = shutdown_token.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(st.config.osdr.fetch_interval));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        if let Err(e) = handlers::fetch_and_store_osdr(&st).await {
-                            error!("OSDR fetch error: {:?}", e);
-                        }
-                    }
-                    _ = token.cancelled() => {
-                        info!("OSDR background task shutting down");
-                        break;
-                    }
-                }
-            }
-        });
-    }
+/// Register the background jobs with their default schedules
+fn register_jobs(state: &AppState) {
+    let scheduler = &state.scheduler;
+    let every = |secs: u64| Some(Schedule::Every(Duration::from_secs(secs)));
 
-    // ISS background task
-    {
-        let st = state.clone();
-        let token = shutdown_token.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(st.config.iss.fetch_interval));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        if let Err(e) = handlers::fetch_and_store_iss(&st).await {
-                            error!("ISS fetch error: {:?}", e);
-                        }
-                    }
-                    _ = token.cancelled() => {
-                        info!("ISS background task shutting down");
-                        break;
-                    }
-                }
-            }
-        });
-    }
+    let st = state.clone();
+    scheduler.register("osdr", every(state.config.osdr.fetch_interval), true, move || {
+        let st = st.clone();
+        async move { handlers::fetch_and_store_osdr(&st).await }
+    });
 
-    // APOD background task
-    {
-        let st = state.clone();
-        let token = shutdown_token.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(st.config.nasa.fetch_intervals.apod));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        if let Err(e) = handlers::fetch_apod(&st).await {
-                            error!("APOD fetch error: {:?}", e);
-                        }
-                    }
-                    _ = token.cancelled() => {
-                        info!("APOD background task shutting down");
-                        break;
-                    }
-                }
-            }
-        });
-    }
+    let st = state.clone();
+    scheduler.register("iss", every(state.config.iss.fetch_interval), true, move || {
+        let st = st.clone();
+        async move { handlers::fetch_and_store_iss(&st).await }
+    });
 
-    // NeoWs background task
-    {
+    // One job per registered space source
+    for source in state.cache_service.sources().iter() {
+        let name = source.name();
         let st = state.clone();
-        let token = shutdown_token.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(st.config.nasa.fetch_intervals.neo));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        if let Err(e) = handlers::fetch_neo_feed(&st).await {
-                            error!("NeoWs fetch error: {:?}", e);
-                        }
-                    }
-                    _ = token.cancelled() => {
-                        info!("NeoWs background task shutting down");
-                        break;
-                    }
-                }
-            }
+        scheduler.register(name, Some(Schedule::Every(source.schedule())), true, move || {
+            let st = st.clone();
+            async move { handlers::fetch_space_source(&st, name).await }
         });
     }
 
-    // DONKI background task
-    {
+    let st = state.clone();
+    scheduler.register("jwst", every(state.config.jwst.fetch_interval), true, move || {
+        let st = st.clone();
+        async move { handlers::fetch_jwst(&st).await }
+    });
+
+    // Legacy telemetry drop directory scanner
+    let st = state.clone();
+    scheduler.register("telemetry", every(state.config.telemetry.scan_interval), true, move || {
+        let st = st.clone();
+        async move { handlers::ingest_telemetry(&st).await }
+    });
+
+    // Retention and compaction of append-only tables; the first run waits for
+    // the schedule rather than competing with startup fetches
+    let compaction_interval = state.config.retention.compaction_interval;
+    let st = state.clone();
+    scheduler.register("compaction", (compaction_interval > 0).then(|| Schedule::Every(Duration::from_secs(compaction_interval))), false, move || {
+        let st = st.clone();
+        async move { handlers::run_compaction(&st).await }
+    });
+
+    // AstronomyAPI events require application credentials
+    if state.config.astro.has_credentials() {
         let st = state.clone();
-        let token = shutdown_token.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(st.config.nasa.fetch_intervals.donki));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        if let Err(e) = handlers::fetch_donki(&st).await {
-                            error!("DONKI fetch error: {:?}", e);
-                        }
-                    }
-                    _ = token.cancelled() => {
-                        info!("DONKI background task shutting down");
-                        break;
-                    }
-                }
-            }
+        scheduler.register("astro", every(state.config.astro.fetch_interval), true, move || {
+            let st = st.clone();
+            async move { handlers::fetch_astro_events(&st).await }
         });
+    } else {
+        warn!("ASTRO_APP_ID or ASTRO_APP_SECRET not set, astronomical events ingestion disabled");
     }
+}
 
-    // SpaceX background task
-    {
-        let st = state.clone();
-        let token = shutdown_token.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(st.config.spacex.fetch_interval));
-            loop {
-                tokio::select! {
-                    _ = interval.tick() => {
-                        if let Err(e) = handlers::fetch_spacex_next(&st).await {
-                            error!("SpaceX fetch error: {:?}", e);
-                        }
-                    }
-                    _ = token.cancelled() => {
-                        info!("SpaceX background task shutting down");
-                        break;
-                    }
-                }
+const MIGRATE_USAGE: &str = "Usage: rust_iss [migrate [up | status | down <version>]]";
+
+/// Apply, revert or list schema migrations
+async fn run_migrate_command(service: &impl MigrationService, args: &[String]) -> anyhow::Result<()> {
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] | ["up"] => {
+            let applied = service.apply_migrations().await.map_err(|e| anyhow::anyhow!("{}", e))?;
+            println!("Applied {} migration(s): {:?}", applied.len(), applied);
+        }
+        ["down", target] => {
+            let target: i64 = target.parse()
+                .map_err(|_| anyhow::anyhow!("Target version must be an integer. {}", MIGRATE_USAGE))?;
+            let reverted = service.revert_migrations(target).await.map_err(|e| anyhow::anyhow!("{}", e))?;
+            println!("Reverted {} migration(s): {:?}", reverted.len(), reverted);
+        }
+        ["status"] => {
+            let migrations = service.migration_status().await.map_err(|e| anyhow::anyhow!("{}", e))?;
+            println!("{:<8} {:<18} {:<20} DESCRIPTION", "VERSION", "STATE", "INSTALLED");
+            for m in migrations {
+                let installed = m.installed_on.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_else(|| "-".to_string());
+                println!("{:<8} {:<18} {:<20} {}", m.version, format!("{:?}", m.state), installed, m.description);
             }
-        });
+        }
+        _ => anyhow::bail!("Invalid migrate arguments. {}", MIGRATE_USAGE),
     }
+    Ok(())
 }
 
 /// Listen for shutdown signals (SIGTERM, SIGINT)
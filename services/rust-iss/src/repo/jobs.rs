@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::domain::*;
+use super::{PgRepos, RepoError, Result};
+
+/// Scheduled job run history Repository trait
+#[async_trait]
+pub trait JobRepo {
+    /// Record a run that has just started, returning its id
+    async fn start_job_run(&self, run: &JobRun) -> Result<i64>;
+    async fn finish_job_run(&self, run: &JobRun) -> Result<()>;
+    /// Runs of `job`, newest first
+    async fn get_job_runs(&self, job: &str, limit: i64) -> Result<Vec<JobRun>>;
+    /// The newest run of every job that has one
+    async fn get_latest_job_runs(&self) -> Result<Vec<JobRun>>;
+}
+
+/// Ownership of scheduled jobs shared by every replica
+#[async_trait]
+pub trait JobLockRepo {
+    /// Take or confirm this replica's ownership of `job`, returning false
+    /// while another replica owns it. Ownership lasts until this replica's
+    /// lock session ends; an error means every lock may have been lost.
+    async fn try_lock_job(&self, job: &str) -> Result<bool>;
+}
+
+/// Advisory lock namespace of scheduled jobs (`pg_try_advisory_lock(ns, hashtext(job))`)
+const JOB_LOCK_NAMESPACE: i32 = 0x6a6f62;
+
+/// Session-level PostgreSQL advisory locks held on a dedicated connection.
+///
+/// The server releases them when the connection closes, so a replica that
+/// dies hands its jobs over to the first replica that tries them next.
+#[derive(Clone)]
+pub struct PgJobLocks {
+    pool: PgPool,
+    session: Arc<Mutex<LockSession>>,
+}
+
+#[derive(Default)]
+struct LockSession {
+    conn: Option<PgConnection>,
+    held: HashSet<String>,
+}
+
+impl PgJobLocks {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, session: Arc::new(Mutex::new(LockSession::default())) }
+    }
+}
+
+#[async_trait]
+impl JobLockRepo for PgJobLocks {
+    async fn try_lock_job(&self, job: &str) -> Result<bool> {
+        let mut guard = self.session.lock().await;
+        let session = &mut *guard;
+        let conn = match session.conn.as_mut() {
+            Some(conn) => conn,
+            None => {
+                // Locks never outlive the session they were taken on
+                session.held.clear();
+                let conn = self.pool.acquire().await.map_err(db_error)?.detach();
+                session.conn.insert(conn)
+            }
+        };
+
+        let locked = if session.held.contains(job) {
+            // Still ours for as long as the session is alive
+            sqlx::query("SELECT 1").execute(&mut *conn).await.map(|_| true)
+        } else {
+            sqlx::query_scalar::<_, bool>("SELECT pg_try_advisory_lock($1, hashtext($2))")
+                .bind(JOB_LOCK_NAMESPACE)
+                .bind(job)
+                .fetch_one(&mut *conn)
+                .await
+        };
+        match locked {
+            Ok(locked) => {
+                if locked {
+                    session.held.insert(job.to_string());
+                }
+                Ok(locked)
+            }
+            Err(e) => {
+                session.conn = None;
+                session.held.clear();
+                Err(db_error(e))
+            }
+        }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> RepoError {
+    RepoError::DatabaseError(e.to_string())
+}
+
+fn job_run_from_row(row: &PgRow) -> Result<JobRun> {
+    let status: String = row.get("status");
+    Ok(JobRun {
+        id: Some(row.get("id")),
+        job: row.get("job"),
+        started_at: row.get("started_at"),
+        finished_at: row.get("finished_at"),
+        status: status.parse().map_err(|e: DomainError| RepoError::DatabaseError(e.to_string()))?,
+        error: row.get("error"),
+        items: row.get("items"),
+        replica: row.get("replica"),
+    })
+}
+
+#[async_trait]
+impl JobRepo for PgRepos {
+    async fn start_job_run(&self, run: &JobRun) -> Result<i64> {
+        let row = sqlx::query(
+            "INSERT INTO job_runs (job, started_at, status, replica) VALUES ($1, $2, $3, $4) RETURNING id"
+        )
+        .bind(&run.job)
+        .bind(run.started_at)
+        .bind(run.status.as_str())
+        .bind(&run.replica)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(row.get("id"))
+    }
+
+    async fn finish_job_run(&self, run: &JobRun) -> Result<()> {
+        let id = run.id.ok_or_else(|| RepoError::ValidationError("job run has no id".to_string()))?;
+        let result = sqlx::query(
+            "UPDATE job_runs SET finished_at = $2, status = $3, error = $4, items = $5 WHERE id = $1"
+        )
+        .bind(id)
+        .bind(run.finished_at)
+        .bind(run.status.as_str())
+        .bind(&run.error)
+        .bind(run.items)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepoError::NotFound(format!("job run {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn get_job_runs(&self, job: &str, limit: i64) -> Result<Vec<JobRun>> {
+        let rows = sqlx::query(
+            "SELECT id, job, started_at, finished_at, status, error, items, replica FROM job_runs
+             WHERE job = $1 ORDER BY started_at DESC, id DESC LIMIT $2"
+        )
+        .bind(job)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        rows.iter().map(job_run_from_row).collect()
+    }
+
+    async fn get_latest_job_runs(&self) -> Result<Vec<JobRun>> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT ON (job) id, job, started_at, finished_at, status, error, items, replica FROM job_runs
+             ORDER BY job, started_at DESC, id DESC"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        rows.iter().map(job_run_from_row).collect()
+    }
+}
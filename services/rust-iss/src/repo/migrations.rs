@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::Row;
+use std::collections::HashMap;
+
+use crate::domain::{MigrationState, MigrationStatus};
+use super::{PgRepos, RepoError, Result};
+
+/// Versioned schema migrations embedded from `migrations/` at compile time.
+///
+/// Applied versions and their checksums are tracked in `_sqlx_migrations`;
+/// editing an applied migration makes startup fail with a checksum mismatch.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Migration Repository trait
+#[async_trait]
+pub trait MigrationRepo {
+    /// Apply all pending migrations, returning the versions applied
+    async fn apply_migrations(&self) -> Result<Vec<i64>>;
+    /// Revert applied migrations newer than `target`, returning the versions reverted
+    async fn revert_migrations(&self, target: i64) -> Result<Vec<i64>>;
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>>;
+}
+
+/// Row of `_sqlx_migrations`
+#[derive(Debug, Clone)]
+struct AppliedRow {
+    version: i64,
+    description: String,
+    installed_on: DateTime<Utc>,
+    success: bool,
+    checksum: Vec<u8>,
+    execution_ms: i64,
+}
+
+fn migrate_error(e: sqlx::migrate::MigrateError) -> RepoError {
+    RepoError::DatabaseError(format!("Migration failed: {}", e))
+}
+
+impl PgRepos {
+    async fn applied_migrations(&self) -> Result<Vec<AppliedRow>> {
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+        if !exists {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(
+            "SELECT version, description, installed_on, success, checksum, execution_time
+             FROM _sqlx_migrations ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AppliedRow {
+                version: row.get("version"),
+                description: row.get("description"),
+                installed_on: row.get("installed_on"),
+                success: row.get("success"),
+                checksum: row.get("checksum"),
+                execution_ms: row.get::<i64, _>("execution_time") / 1_000_000,
+            })
+            .collect())
+    }
+
+    async fn applied_versions(&self) -> Result<Vec<i64>> {
+        let mut conn = self.pool.acquire().await.map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+        conn.ensure_migrations_table().await.map_err(migrate_error)?;
+        let applied = conn.list_applied_migrations().await.map_err(migrate_error)?;
+        Ok(applied.into_iter().map(|m| m.version).collect())
+    }
+}
+
+#[async_trait]
+impl MigrationRepo for PgRepos {
+    async fn apply_migrations(&self) -> Result<Vec<i64>> {
+        let before = self.applied_versions().await?;
+        MIGRATOR.run(&self.pool).await.map_err(migrate_error)?;
+        let after = self.applied_versions().await?;
+        Ok(after.into_iter().filter(|v| !before.contains(v)).collect())
+    }
+
+    async fn revert_migrations(&self, target: i64) -> Result<Vec<i64>> {
+        let before = self.applied_versions().await?;
+        MIGRATOR.undo(&self.pool, target).await.map_err(migrate_error)?;
+        let after = self.applied_versions().await?;
+        let mut reverted: Vec<i64> = before.into_iter().filter(|v| !after.contains(v)).collect();
+        reverted.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(reverted)
+    }
+
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let applied = self.applied_migrations().await?;
+        Ok(compute_status(&MIGRATOR, &applied))
+    }
+}
+
+/// Compare embedded up-migrations with what the database has applied
+fn compute_status(migrator: &Migrator, applied: &[AppliedRow]) -> Vec<MigrationStatus> {
+    let mut applied_by_version: HashMap<i64, &AppliedRow> = applied.iter().map(|a| (a.version, a)).collect();
+    let reversible: Vec<i64> = migrator
+        .iter()
+        .filter(|m| m.migration_type.is_down_migration())
+        .map(|m| m.version)
+        .collect();
+
+    let mut statuses: Vec<MigrationStatus> = migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+        .map(|m| {
+            let row = applied_by_version.remove(&m.version);
+            let state = match row {
+                None => MigrationState::Pending,
+                Some(r) if !r.success => MigrationState::Failed,
+                Some(r) if r.checksum != *m.checksum => MigrationState::ChecksumMismatch,
+                Some(_) => MigrationState::Applied,
+            };
+            MigrationStatus {
+                version: m.version,
+                description: m.description.to_string(),
+                state,
+                reversible: reversible.contains(&m.version),
+                checksum: hex(&m.checksum),
+                installed_on: row.map(|r| r.installed_on),
+                execution_ms: row.map(|r| r.execution_ms),
+            }
+        })
+        .collect();
+
+    // Applied in the database but unknown to this build (e.g. after a downgrade)
+    statuses.extend(applied_by_version.into_values().map(|r| MigrationStatus {
+        version: r.version,
+        description: r.description.clone(),
+        state: MigrationState::Missing,
+        reversible: false,
+        checksum: hex(&r.checksum),
+        installed_on: Some(r.installed_on),
+        execution_ms: Some(r.execution_ms),
+    }));
+    statuses.sort_by_key(|s| s.version);
+    statuses
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(version: i64, checksum: Vec<u8>, success: bool) -> AppliedRow {
+        AppliedRow {
+            version,
+            description: format!("migration {}", version),
+            installed_on: Utc::now(),
+            success,
+            checksum,
+            execution_ms: 3,
+        }
+    }
+
+    #[test]
+    fn test_embedded_migrations_are_reversible_and_ordered() {
+        let ups: Vec<i64> = MIGRATOR.iter().filter(|m| m.migration_type.is_up_migration()).map(|m| m.version).collect();
+        let downs: Vec<i64> = MIGRATOR.iter().filter(|m| m.migration_type.is_down_migration()).map(|m| m.version).collect();
+        assert!(!ups.is_empty());
+        assert_eq!(ups, downs);
+        assert!(ups.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_status_classifies_migrations() {
+        let ups: Vec<_> = MIGRATOR.iter().filter(|m| m.migration_type.is_up_migration()).collect();
+        let rows = vec![
+            applied(ups[0].version, ups[0].checksum.to_vec(), true),
+            applied(ups[1].version, vec![0, 1, 2], true),
+            applied(ups[2].version, ups[2].checksum.to_vec(), false),
+            applied(99_999, vec![0xab], true),
+        ];
+
+        let statuses = compute_status(&MIGRATOR, &rows);
+        let state = |v: i64| statuses.iter().find(|s| s.version == v).unwrap().state;
+        assert_eq!(state(ups[0].version), MigrationState::Applied);
+        assert_eq!(state(ups[1].version), MigrationState::ChecksumMismatch);
+        assert_eq!(state(ups[2].version), MigrationState::Failed);
+        assert_eq!(state(ups[3].version), MigrationState::Pending);
+        assert_eq!(state(99_999), MigrationState::Missing);
+        assert_eq!(statuses.len(), ups.len() + 1);
+        assert!(statuses[0].reversible);
+        assert_eq!(statuses[0].checksum.len(), ups[0].checksum.len() * 2);
+    }
+}
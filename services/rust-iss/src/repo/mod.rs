@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Months, NaiveTime, Utc};
 use redis::{aio::ConnectionManager, AsyncCommands};
 use serde_json::Value;
 use sqlx::{PgPool, Row};
@@ -8,6 +8,14 @@ use std::sync::Arc;
 
 use crate::domain::*;
 
+mod jobs;
+mod migrations;
+mod retention;
+
+pub use jobs::{JobLockRepo, JobRepo, PgJobLocks};
+pub use migrations::MigrationRepo;
+pub use retention::{RetentionRepo, COMPACTED_TABLES};
+
 /// Common repository error type
 #[derive(Debug, Clone)]
 pub enum RepoError {
@@ -46,6 +54,7 @@ pub trait IssRepo {
 pub trait OsdrRepo {
     async fn insert_or_update_osdr_item(&self, item: &OsdrItem) -> Result<i64>;
     async fn get_osdr_items(&self, limit: i64) -> Result<Vec<OsdrItem>>;
+    async fn get_osdr_items_page(&self, limit: i64, offset: i64) -> Result<Vec<OsdrItem>>;
     async fn get_osdr_item_by_id(&self, dataset_id: &str) -> Result<Option<OsdrItem>>;
     async fn count_osdr_items(&self) -> Result<i64>;
 }
@@ -53,11 +62,41 @@ pub trait OsdrRepo {
 /// Cache Repository trait
 #[async_trait]
 pub trait CacheRepo {
-    async fn insert_cache_entry(&self, entry: &SpaceCache) -> Result<i64>;
+    /// Store a fetched payload, or only bump `last_seen_at` of the latest
+    /// entry of the source when the payload is unchanged
+    async fn insert_cache_entry(&self, entry: &SpaceCache) -> Result<CacheWrite>;
     async fn get_latest_cache_entry(&self, source: &str) -> Result<Option<SpaceCache>>;
+    /// Bump `last_seen_at` of the latest entry of a source the upstream reported unchanged
+    async fn touch_latest_cache_entry(&self, source: &str) -> Result<Option<SpaceCache>>;
+    /// Entries of a source whose payload differs from the previous one, newest first
     async fn get_cache_entries(&self, source: &str, limit: i64) -> Result<Vec<SpaceCache>>;
 }
 
+/// JWST Repository trait
+#[async_trait]
+pub trait JwstRepo {
+    async fn upsert_jwst_observation(&self, observation: &JwstObservation) -> Result<i64>;
+    async fn get_jwst_observations(&self, filter: &JwstFilter, limit: i64, offset: i64) -> Result<Vec<JwstObservation>>;
+    async fn count_jwst_observations(&self, filter: &JwstFilter) -> Result<i64>;
+}
+
+/// Astronomical events Repository trait
+#[async_trait]
+pub trait AstroRepo {
+    async fn upsert_astro_event(&self, event: &AstroEvent) -> Result<i64>;
+    async fn get_astro_events(&self, filter: &AstroEventFilter, limit: i64) -> Result<Vec<AstroEvent>>;
+}
+
+/// Telemetry Repository trait
+#[async_trait]
+pub trait TelemetryRepo {
+    /// Insert all readings of a source file atomically. Returns `None` when
+    /// readings for that `source_file` already exist.
+    async fn insert_telemetry_file(&self, source_file: &str, readings: &[TelemetryReading]) -> Result<Option<usize>>;
+    async fn get_telemetry_buckets(&self, from: Timestamp, to: Timestamp, bucket_seconds: i64, envelope: &TelemetryEnvelope) -> Result<Vec<TelemetryBucket>>;
+    async fn get_out_of_range_readings(&self, from: Timestamp, to: Timestamp, envelope: &TelemetryEnvelope, limit: i64) -> Result<Vec<TelemetryReading>>;
+}
+
 /// Redis Repository trait
 #[async_trait]
 pub trait RedisRepo {
@@ -65,6 +104,9 @@ pub trait RedisRepo {
     async fn get_cache(&self, key: &str) -> Result<Option<String>>;
     async fn delete_cache(&self, key: &str) -> Result<()>;
     async fn exists_cache(&self, key: &str) -> Result<bool>;
+    async fn list_keys(&self, pattern: &str, limit: usize) -> Result<Vec<CacheKeyInfo>>;
+    async fn delete_matching(&self, pattern: &str) -> Result<Vec<String>>;
+    async fn publish(&self, channel: &str, message: &str) -> Result<()>;
 }
 
 /// PostgreSQL implementation of repositories
@@ -83,6 +125,7 @@ impl PgRepos {
 #[derive(Clone)]
 pub struct RedisRepos {
     client: ConnectionManager,
+    pubsub_client: redis::Client,
 }
 
 impl RedisRepos {
@@ -90,70 +133,142 @@ impl RedisRepos {
         let client = redis::Client::open(redis_url)
             .map_err(|e| RepoError::DatabaseError(format!("Failed to create Redis client: {}", e)))?;
 
-        let connection_manager = ConnectionManager::new(client)
+        let connection_manager = ConnectionManager::new(client.clone())
             .await
             .map_err(|e| RepoError::DatabaseError(format!("Failed to create Redis connection manager: {}", e)))?;
 
         Ok(Self {
             client: connection_manager,
+            pubsub_client: client,
         })
     }
+
+    /// Open a dedicated connection subscribed to `channel`
+    pub async fn subscribe(&self, channel: &str) -> Result<redis::aio::PubSub> {
+        let mut pubsub = self.pubsub_client
+            .get_async_pubsub()
+            .await
+            .map_err(|e| RepoError::DatabaseError(format!("Redis pub/sub connection failed: {}", e)))?;
+        pubsub
+            .subscribe(channel)
+            .await
+            .map_err(|e| RepoError::DatabaseError(format!("Redis SUBSCRIBE failed: {}", e)))?;
+        Ok(pubsub)
+    }
+
+    /// Iterate keys matching `pattern` with SCAN, stopping after `limit` keys
+    async fn scan_keys(&self, pattern: &str, limit: usize) -> Result<Vec<String>> {
+        let mut conn = self.client.clone();
+        let mut cursor: u64 = 0;
+        let mut keys = Vec::new();
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(500)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| RepoError::DatabaseError(format!("Redis SCAN failed: {}", e)))?;
+            keys.extend(batch);
+            cursor = next;
+            if cursor == 0 || keys.len() >= limit {
+                break;
+            }
+        }
+        keys.truncate(limit);
+        Ok(keys)
+    }
 }
 
-#[async_trait]
-impl IssRepo for PgRepos {
-    async fn insert_iss_data(&self, data: &IssData) -> Result<i64> {
+/// Start of the previous UTC month: ISS reads look here first so that only the
+/// two newest iss_fetch_log partitions are scanned
+fn recent_iss_window_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    let month_start = now.date_naive().with_day(1).unwrap_or(now.date_naive());
+    month_start
+        .checked_sub_months(Months::new(1))
+        .unwrap_or(month_start)
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+}
+
+fn iss_data_from_row(row: &sqlx::postgres::PgRow) -> IssData {
+    IssData {
+        id: Some(row.get("id")),
+        fetched_at: row.get("fetched_at"),
+        source_url: row.get("source_url"),
+        payload: row.get("payload"),
+    }
+}
+
+/// Insert rejected because no iss_fetch_log partition covers the row
+fn is_missing_partition(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db) if db.code().as_deref() == Some("23514") && db.message().contains("no partition"))
+}
+
+impl PgRepos {
+    async fn insert_iss_row(&self, data: &IssData) -> result::Result<i64, sqlx::Error> {
         let row = sqlx::query(
             "INSERT INTO iss_fetch_log (source_url, payload) VALUES ($1, $2) RETURNING id"
         )
         .bind(&data.source_url)
         .bind(&data.payload)
         .fetch_one(&self.pool)
-        .await
-        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
-
+        .await?;
         Ok(row.get("id"))
     }
 
-    async fn get_latest_iss_data(&self) -> Result<Option<IssData>> {
-        let row_opt = sqlx::query(
-            "SELECT id, fetched_at, source_url, payload FROM iss_fetch_log ORDER BY id DESC LIMIT 1"
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+    /// Newest rows fetched at or after `bound` (`newer`) or before it, newest first.
+    /// A plain comparison on the partition key lets Postgres prune partitions.
+    async fn get_iss_rows_around(&self, bound: Timestamp, newer: bool, limit: i64) -> Result<Vec<IssData>> {
+        let sql = format!(
+            "SELECT id, fetched_at, source_url, payload FROM iss_fetch_log
+             WHERE fetched_at {} $1
+             ORDER BY fetched_at DESC, id DESC LIMIT $2",
+            if newer { ">=" } else { "<" }
+        );
+        let rows = sqlx::query(&sql)
+            .bind(bound)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
 
-        if let Some(row) = row_opt {
-            let data = IssData {
-                id: Some(row.get("id")),
-                fetched_at: row.get("fetched_at"),
-                source_url: row.get("source_url"),
-                payload: row.get("payload"),
-            };
-            Ok(Some(data))
-        } else {
-            Ok(None)
+        Ok(rows.iter().map(iss_data_from_row).collect())
+    }
+}
+
+#[async_trait]
+impl IssRepo for PgRepos {
+    async fn insert_iss_data(&self, data: &IssData) -> Result<i64> {
+        match self.insert_iss_row(data).await {
+            Err(e) if is_missing_partition(&e) => {
+                // Partitions are created ahead by the retention service; this
+                // only happens after a long outage or with compaction disabled
+                sqlx::query("SELECT iss_fetch_log_ensure_partition(now())")
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+                self.insert_iss_row(data).await
+            }
+            other => other,
         }
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))
     }
 
-    async fn get_iss_data_range(&self, limit: i64) -> Result<Vec<IssData>> {
-        let rows = sqlx::query(
-            "SELECT id, fetched_at, source_url, payload FROM iss_fetch_log ORDER BY id DESC LIMIT $1"
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+    async fn get_latest_iss_data(&self) -> Result<Option<IssData>> {
+        Ok(self.get_iss_data_range(1).await?.into_iter().next())
+    }
 
-        let mut results = Vec::new();
-        for row in rows {
-            let data = IssData {
-                id: Some(row.get("id")),
-                fetched_at: row.get("fetched_at"),
-                source_url: row.get("source_url"),
-                payload: row.get("payload"),
-            };
-            results.push(data);
+    async fn get_iss_data_range(&self, limit: i64) -> Result<Vec<IssData>> {
+        // Recent partitions usually hold enough rows; older ones are only
+        // scanned for the remainder
+        let since = recent_iss_window_start(Utc::now());
+        let mut results = self.get_iss_rows_around(since, true, limit).await?;
+        let remaining = limit - results.len() as i64;
+        if remaining > 0 {
+            results.extend(self.get_iss_rows_around(since, false, remaining).await?);
         }
         Ok(results)
     }
@@ -202,13 +317,18 @@ impl OsdrRepo for PgRepos {
     }
 
     async fn get_osdr_items(&self, limit: i64) -> Result<Vec<OsdrItem>> {
+        self.get_osdr_items_page(limit, 0).await
+    }
+
+    async fn get_osdr_items_page(&self, limit: i64, offset: i64) -> Result<Vec<OsdrItem>> {
         let rows = sqlx::query(
             "SELECT id, dataset_id, title, status, updated_at, inserted_at, raw
              FROM osdr_items
              ORDER BY inserted_at DESC
-             LIMIT $1"
+             LIMIT $1 OFFSET $2"
         )
         .bind(limit)
+        .bind(offset)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
@@ -268,22 +388,61 @@ impl OsdrRepo for PgRepos {
 
 #[async_trait]
 impl CacheRepo for PgRepos {
-    async fn insert_cache_entry(&self, entry: &SpaceCache) -> Result<i64> {
+    async fn insert_cache_entry(&self, entry: &SpaceCache) -> Result<CacheWrite> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        // Serialize writers of the same source so two identical fetches cannot both insert
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext('space_cache:' || $1))")
+            .bind(&entry.source)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        // Touch the latest row when its hash matches, insert otherwise
         let row = sqlx::query(
-            "INSERT INTO space_cache(source, payload) VALUES ($1,$2) RETURNING id"
+            "WITH latest AS (
+                 SELECT id FROM space_cache
+                 WHERE source = $1 AND payload_hash = md5($2::jsonb::text)
+                   AND id = (SELECT max(id) FROM space_cache WHERE source = $1)
+             ), touched AS (
+                 UPDATE space_cache SET last_seen_at = now()
+                 FROM latest WHERE space_cache.id = latest.id
+                 RETURNING space_cache.id, space_cache.fetched_at, space_cache.last_seen_at, space_cache.payload_hash
+             ), inserted AS (
+                 INSERT INTO space_cache(source, payload)
+                 SELECT $1, $2 WHERE NOT EXISTS (SELECT 1 FROM latest)
+                 RETURNING id, fetched_at, last_seen_at, payload_hash
+             )
+             SELECT *, false AS changed FROM touched
+             UNION ALL
+             SELECT *, true AS changed FROM inserted"
         )
         .bind(&entry.source)
         .bind(&entry.payload)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
 
-        Ok(row.get("id"))
+        tx.commit().await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(CacheWrite {
+            entry: SpaceCache {
+                id: Some(row.get("id")),
+                source: entry.source.clone(),
+                fetched_at: row.get("fetched_at"),
+                last_seen_at: row.get("last_seen_at"),
+                payload: entry.payload.clone(),
+                payload_hash: row.get("payload_hash"),
+            },
+            changed: row.get("changed"),
+        })
     }
 
     async fn get_latest_cache_entry(&self, source: &str) -> Result<Option<SpaceCache>> {
         let row_opt = sqlx::query(
-            "SELECT id, source, fetched_at, payload FROM space_cache
+            "SELECT id, source, fetched_at, last_seen_at, payload, payload_hash FROM space_cache
              WHERE source = $1 ORDER BY id DESC LIMIT 1"
         )
         .bind(source)
@@ -296,7 +455,9 @@ impl CacheRepo for PgRepos {
                 id: Some(row.get("id")),
                 source: row.get("source"),
                 fetched_at: row.get("fetched_at"),
+                last_seen_at: row.get("last_seen_at"),
                 payload: row.get("payload"),
+                payload_hash: row.get("payload_hash"),
             };
             Ok(Some(entry))
         } else {
@@ -304,10 +465,35 @@ impl CacheRepo for PgRepos {
         }
     }
 
+    async fn touch_latest_cache_entry(&self, source: &str) -> Result<Option<SpaceCache>> {
+        let row_opt = sqlx::query(
+            "UPDATE space_cache SET last_seen_at = now()
+             WHERE id = (SELECT max(id) FROM space_cache WHERE source = $1)
+             RETURNING id, source, fetched_at, last_seen_at, payload, payload_hash"
+        )
+        .bind(source)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(row_opt.map(|row| SpaceCache {
+            id: Some(row.get("id")),
+            source: row.get("source"),
+            fetched_at: row.get("fetched_at"),
+            last_seen_at: row.get("last_seen_at"),
+            payload: row.get("payload"),
+            payload_hash: row.get("payload_hash"),
+        }))
+    }
+
     async fn get_cache_entries(&self, source: &str, limit: i64) -> Result<Vec<SpaceCache>> {
+        // Rows stored before hashing may still repeat their predecessor until compacted
         let rows = sqlx::query(
-            "SELECT id, source, fetched_at, payload FROM space_cache
-             WHERE source = $1 ORDER BY id DESC LIMIT $2"
+            "SELECT id, source, fetched_at, last_seen_at, payload, payload_hash FROM (
+                 SELECT *, payload_hash IS DISTINCT FROM lag(payload_hash) OVER (ORDER BY id) AS changed
+                 FROM space_cache WHERE source = $1
+             ) history
+             WHERE changed ORDER BY id DESC LIMIT $2"
         )
         .bind(source)
         .bind(limit)
@@ -321,7 +507,9 @@ impl CacheRepo for PgRepos {
                 id: Some(row.get("id")),
                 source: row.get("source"),
                 fetched_at: row.get("fetched_at"),
+                last_seen_at: row.get("last_seen_at"),
                 payload: row.get("payload"),
+                payload_hash: row.get("payload_hash"),
             };
             results.push(entry);
         }
@@ -329,6 +517,266 @@ impl CacheRepo for PgRepos {
     }
 }
 
+#[async_trait]
+impl JwstRepo for PgRepos {
+    async fn upsert_jwst_observation(&self, observation: &JwstObservation) -> Result<i64> {
+        let row = sqlx::query(
+            "INSERT INTO jwst_observations(observation_id, program, instruments, suffix, image_url, thumbnail_url, raw)
+             VALUES($1,$2,$3,$4,$5,$6,$7)
+             ON CONFLICT (image_url) DO UPDATE
+             SET observation_id=EXCLUDED.observation_id, program=EXCLUDED.program,
+                 instruments=EXCLUDED.instruments, suffix=EXCLUDED.suffix,
+                 thumbnail_url=EXCLUDED.thumbnail_url, raw=EXCLUDED.raw, fetched_at=now()
+             RETURNING id"
+        )
+        .bind(&observation.observation_id)
+        .bind(&observation.program)
+        .bind(&observation.instruments)
+        .bind(&observation.suffix)
+        .bind(&observation.image_url)
+        .bind(&observation.thumbnail_url)
+        .bind(&observation.raw)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(row.get("id"))
+    }
+
+    async fn get_jwst_observations(&self, filter: &JwstFilter, limit: i64, offset: i64) -> Result<Vec<JwstObservation>> {
+        let rows = sqlx::query(
+            "SELECT id, observation_id, program, instruments, suffix, image_url, thumbnail_url, fetched_at, raw
+             FROM jwst_observations
+             WHERE ($1::text IS NULL OR program = $1)
+               AND ($2::text IS NULL OR $2 = ANY(instruments))
+               AND ($3::text IS NULL OR suffix = $3)
+             ORDER BY fetched_at DESC, id DESC
+             LIMIT $4 OFFSET $5"
+        )
+        .bind(&filter.program)
+        .bind(&filter.instrument)
+        .bind(&filter.suffix)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let observation = JwstObservation {
+                id: Some(row.get("id")),
+                observation_id: row.get("observation_id"),
+                program: row.get("program"),
+                instruments: row.get("instruments"),
+                suffix: row.get("suffix"),
+                image_url: row.get("image_url"),
+                thumbnail_url: row.get("thumbnail_url"),
+                fetched_at: row.get("fetched_at"),
+                raw: row.get("raw"),
+            };
+            results.push(observation);
+        }
+        Ok(results)
+    }
+
+    async fn count_jwst_observations(&self, filter: &JwstFilter) -> Result<i64> {
+        let row = sqlx::query(
+            "SELECT count(*) AS c FROM jwst_observations
+             WHERE ($1::text IS NULL OR program = $1)
+               AND ($2::text IS NULL OR $2 = ANY(instruments))
+               AND ($3::text IS NULL OR suffix = $3)"
+        )
+        .bind(&filter.program)
+        .bind(&filter.instrument)
+        .bind(&filter.suffix)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(row.get::<i64, _>("c"))
+    }
+}
+
+#[async_trait]
+impl AstroRepo for PgRepos {
+    async fn upsert_astro_event(&self, event: &AstroEvent) -> Result<i64> {
+        let row = sqlx::query(
+            "INSERT INTO astro_events(observer, body, event_type, occurs_at, ends_at, details)
+             VALUES($1,$2,$3,$4,$5,$6)
+             ON CONFLICT (observer, body, event_type, occurs_at) DO UPDATE
+             SET ends_at=EXCLUDED.ends_at, details=EXCLUDED.details, fetched_at=now()
+             RETURNING id"
+        )
+        .bind(&event.observer)
+        .bind(&event.body)
+        .bind(&event.event_type)
+        .bind(event.occurs_at)
+        .bind(event.ends_at)
+        .bind(&event.details)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(row.get("id"))
+    }
+
+    async fn get_astro_events(&self, filter: &AstroEventFilter, limit: i64) -> Result<Vec<AstroEvent>> {
+        let rows = sqlx::query(
+            "SELECT id, observer, body, event_type, occurs_at, ends_at, details, fetched_at
+             FROM astro_events
+             WHERE ($1::text IS NULL OR observer = $1)
+               AND ($2::text IS NULL OR body = $2)
+               AND ($3::text IS NULL OR event_type = $3)
+               AND ($4::timestamptz IS NULL OR occurs_at >= $4)
+               AND ($5::timestamptz IS NULL OR occurs_at <= $5)
+             ORDER BY occurs_at
+             LIMIT $6"
+        )
+        .bind(&filter.observer)
+        .bind(&filter.body)
+        .bind(&filter.event_type)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let event = AstroEvent {
+                id: Some(row.get("id")),
+                observer: row.get("observer"),
+                body: row.get("body"),
+                event_type: row.get("event_type"),
+                occurs_at: row.get("occurs_at"),
+                ends_at: row.get("ends_at"),
+                details: row.get("details"),
+                fetched_at: row.get("fetched_at"),
+            };
+            results.push(event);
+        }
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl TelemetryRepo for PgRepos {
+    async fn insert_telemetry_file(&self, source_file: &str, readings: &[TelemetryReading]) -> Result<Option<usize>> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        // Serialize concurrent ingestion of the same file across replicas
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(source_file)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM telemetry_legacy WHERE source_file = $1)")
+            .bind(source_file)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+        if exists {
+            return Ok(None);
+        }
+
+        for reading in readings {
+            sqlx::query(
+                "INSERT INTO telemetry_legacy (recorded_at, voltage, temp, source_file, is_valid)
+                 VALUES ($1, $2::numeric, $3::numeric, $4, $5)"
+            )
+            .bind(reading.recorded_at)
+            .bind(reading.voltage)
+            .bind(reading.temp)
+            .bind(source_file)
+            .bind(reading.is_valid)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(Some(readings.len()))
+    }
+
+    async fn get_telemetry_buckets(&self, from: Timestamp, to: Timestamp, bucket_seconds: i64, envelope: &TelemetryEnvelope) -> Result<Vec<TelemetryBucket>> {
+        let rows = sqlx::query(
+            "SELECT to_timestamp(floor(extract(epoch FROM recorded_at) / $3) * $3) AS bucket_start,
+                    count(*) AS readings,
+                    min(voltage)::float8 AS voltage_min,
+                    max(voltage)::float8 AS voltage_max,
+                    avg(voltage)::float8 AS voltage_avg,
+                    min(temp)::float8 AS temp_min,
+                    max(temp)::float8 AS temp_max,
+                    avg(temp)::float8 AS temp_avg,
+                    count(*) FILTER (WHERE is_valid) AS valid_readings,
+                    count(*) FILTER (WHERE voltage < $4 OR voltage > $5 OR temp < $6 OR temp > $7) AS out_of_range
+             FROM telemetry_legacy
+             WHERE recorded_at >= $1 AND recorded_at < $2
+             GROUP BY 1
+             ORDER BY 1"
+        )
+        .bind(from)
+        .bind(to)
+        .bind(bucket_seconds as f64)
+        .bind(envelope.voltage_min)
+        .bind(envelope.voltage_max)
+        .bind(envelope.temp_min)
+        .bind(envelope.temp_max)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|row| TelemetryBucket {
+            bucket_start: row.get("bucket_start"),
+            readings: row.get("readings"),
+            voltage_min: row.get("voltage_min"),
+            voltage_max: row.get("voltage_max"),
+            voltage_avg: row.get("voltage_avg"),
+            temp_min: row.get("temp_min"),
+            temp_max: row.get("temp_max"),
+            temp_avg: row.get("temp_avg"),
+            valid_readings: row.get("valid_readings"),
+            out_of_range: row.get("out_of_range"),
+        }).collect())
+    }
+
+    async fn get_out_of_range_readings(&self, from: Timestamp, to: Timestamp, envelope: &TelemetryEnvelope, limit: i64) -> Result<Vec<TelemetryReading>> {
+        let rows = sqlx::query(
+            "SELECT id, recorded_at, voltage::float8 AS voltage, temp::float8 AS temp, source_file, is_valid
+             FROM telemetry_legacy
+             WHERE recorded_at >= $1 AND recorded_at < $2
+               AND (voltage < $3 OR voltage > $4 OR temp < $5 OR temp > $6)
+             ORDER BY recorded_at DESC
+             LIMIT $7"
+        )
+        .bind(from)
+        .bind(to)
+        .bind(envelope.voltage_min)
+        .bind(envelope.voltage_max)
+        .bind(envelope.temp_min)
+        .bind(envelope.temp_max)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepoError::DatabaseError(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|row| TelemetryReading {
+            id: Some(row.get("id")),
+            recorded_at: row.get("recorded_at"),
+            voltage: row.get("voltage"),
+            temp: row.get("temp"),
+            source_file: row.get("source_file"),
+            is_valid: row.get("is_valid"),
+        }).collect())
+    }
+}
+
 #[async_trait]
 impl RedisRepo for RedisRepos {
     async fn set_cache(&self, key: &str, value: &str, ttl_seconds: Option<usize>) -> Result<()> {
@@ -384,4 +832,58 @@ impl RedisRepo for RedisRepos {
 
         Ok(exists > 0)
     }
+
+    async fn list_keys(&self, pattern: &str, limit: usize) -> Result<Vec<CacheKeyInfo>> {
+        let keys = self.scan_keys(pattern, limit).await?;
+        let mut conn = self.client.clone();
+        let mut result = Vec::with_capacity(keys.len());
+        for key in keys {
+            let ttl: i64 = redis::cmd("TTL")
+                .arg(&key)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| RepoError::DatabaseError(format!("Redis TTL failed: {}", e)))?;
+            // MEMORY USAGE is unavailable on some managed Redis offerings
+            let size: Option<i64> = redis::cmd("MEMORY")
+                .arg("USAGE")
+                .arg(&key)
+                .query_async(&mut conn)
+                .await
+                .unwrap_or(None);
+            // -2: key expired in between, -1: no expiry
+            if ttl == -2 {
+                continue;
+            }
+            result.push(CacheKeyInfo {
+                key,
+                ttl_seconds: (ttl >= 0).then_some(ttl),
+                size_bytes: size,
+            });
+        }
+        Ok(result)
+    }
+
+    async fn delete_matching(&self, pattern: &str) -> Result<Vec<String>> {
+        let keys = self.scan_keys(pattern, usize::MAX).await?;
+        let mut conn = self.client.clone();
+        for chunk in keys.chunks(500) {
+            let _: usize = redis::cmd("DEL")
+                .arg(chunk)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| RepoError::DatabaseError(format!("Redis DEL failed: {}", e)))?;
+        }
+        Ok(keys)
+    }
+
+    async fn publish(&self, channel: &str, message: &str) -> Result<()> {
+        let mut conn = self.client.clone();
+        let _: i64 = redis::cmd("PUBLISH")
+            .arg(channel)
+            .arg(message)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| RepoError::DatabaseError(format!("Redis PUBLISH failed: {}", e)))?;
+        Ok(())
+    }
 }
@@ -0,0 +1,344 @@
+use async_trait::async_trait;
+use chrono::{Months, NaiveDate, NaiveTime};
+use sqlx::{Executor, Row};
+
+use crate::domain::*;
+use super::{PgRepos, RepoError, Result};
+
+/// Tables the compaction job may vacuum
+pub const COMPACTED_TABLES: [&str; 3] = ["iss_fetch_log", "iss_position_hourly", "space_cache"];
+
+/// Monthly iss_fetch_log partitions are named `iss_fetch_log_pYYYY_MM` (UTC months)
+const ISS_PARTITION_PREFIX: &str = "iss_fetch_log_p";
+
+/// Retention and compaction Repository trait
+#[async_trait]
+pub trait RetentionRepo {
+    /// Create the iss_fetch_log partitions for the month of `from` and the
+    /// `months_ahead` following ones, returning those that did not exist yet
+    async fn ensure_iss_partitions(&self, from: Timestamp, months_ahead: u32) -> Result<Vec<String>>;
+    /// Fold ISS positions fetched before `before` into hourly aggregates and
+    /// remove them, dropping partitions that lie entirely before the cutoff
+    async fn downsample_iss_positions(&self, before: Timestamp) -> Result<CompactionStep>;
+    async fn expire_iss_hourly(&self, before: Timestamp) -> Result<CompactionStep>;
+    /// Collapse runs of space_cache rows with the same payload hash into their
+    /// first row, carrying over the latest `last_seen_at`
+    async fn dedupe_space_cache(&self) -> Result<Vec<CompactionStep>>;
+    async fn get_space_cache_sources(&self) -> Result<Vec<String>>;
+    /// Delete space_cache rows of `source` last seen before `before`, keeping the newest
+    async fn expire_space_cache(&self, source: &str, before: Timestamp) -> Result<CompactionStep>;
+    async fn table_size(&self, table: &str) -> Result<i64>;
+    async fn vacuum_table(&self, table: &str) -> Result<()>;
+    async fn insert_compaction_run(&self, report: &CompactionReport) -> Result<i64>;
+    async fn get_compaction_runs(&self, limit: i64) -> Result<Vec<CompactionReport>>;
+}
+
+fn db_error(e: sqlx::Error) -> RepoError {
+    RepoError::DatabaseError(e.to_string())
+}
+
+/// End of the month covered by an `iss_fetch_log_pYYYY_MM` partition
+fn iss_partition_end(name: &str) -> Option<Timestamp> {
+    let (year, month) = name.strip_prefix(ISS_PARTITION_PREFIX)?.split_once('_')?;
+    let digits = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    if !digits(year, 4) || !digits(month, 2) {
+        return None;
+    }
+    let start = NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, 1)?;
+    Some(start.checked_add_months(Months::new(1))?.and_time(NaiveTime::MIN).and_utc())
+}
+
+/// Partitions whose whole month lies before `before`, oldest first
+fn expired_iss_partitions(names: &[String], before: Timestamp) -> Vec<String> {
+    let mut expired: Vec<String> = names.iter()
+        .filter(|name| iss_partition_end(name).is_some_and(|end| end <= before))
+        .cloned()
+        .collect();
+    expired.sort();
+    expired
+}
+
+#[async_trait]
+impl RetentionRepo for PgRepos {
+    async fn ensure_iss_partitions(&self, from: Timestamp, months_ahead: u32) -> Result<Vec<String>> {
+        let mut created = Vec::new();
+        for offset in 0..=months_ahead {
+            let Some(at) = from.checked_add_months(Months::new(offset)) else {
+                break;
+            };
+            let name: Option<String> = sqlx::query_scalar("SELECT iss_fetch_log_ensure_partition($1)")
+                .bind(at)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(db_error)?;
+            created.extend(name);
+        }
+        Ok(created)
+    }
+
+    async fn downsample_iss_positions(&self, before: Timestamp) -> Result<CompactionStep> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        // Serialize runs across replicas so an hour is never aggregated twice
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext('iss_fetch_log:downsample'))")
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let row = sqlx::query(
+            "WITH points AS (
+                 SELECT fetched_at, pg_column_size(l.*) AS bytes,
+                        CASE WHEN jsonb_typeof(payload->'latitude') = 'number' THEN (payload->>'latitude')::float8 END AS lat,
+                        CASE WHEN jsonb_typeof(payload->'longitude') = 'number' THEN (payload->>'longitude')::float8 END AS lon,
+                        CASE WHEN jsonb_typeof(payload->'altitude') = 'number' THEN (payload->>'altitude')::float8 END AS alt,
+                        CASE WHEN jsonb_typeof(payload->'velocity') = 'number' THEN (payload->>'velocity')::float8 END AS vel
+                 FROM iss_fetch_log l
+                 WHERE fetched_at < $1
+             ), hourly AS (
+                 INSERT INTO iss_position_hourly AS h
+                     (hour, samples, first_at, last_at, latitude, longitude, latitude_min, latitude_max, altitude_avg, velocity_avg)
+                 SELECT date_trunc('hour', fetched_at), count(*), min(fetched_at), max(fetched_at),
+                        (array_agg(lat ORDER BY fetched_at))[1], (array_agg(lon ORDER BY fetched_at))[1],
+                        min(lat), max(lat), avg(alt), avg(vel)
+                 FROM points
+                 GROUP BY 1
+                 ON CONFLICT (hour) DO UPDATE SET
+                     samples = h.samples + EXCLUDED.samples,
+                     first_at = LEAST(h.first_at, EXCLUDED.first_at),
+                     last_at = GREATEST(h.last_at, EXCLUDED.last_at),
+                     latitude = CASE WHEN EXCLUDED.first_at < h.first_at THEN EXCLUDED.latitude ELSE h.latitude END,
+                     longitude = CASE WHEN EXCLUDED.first_at < h.first_at THEN EXCLUDED.longitude ELSE h.longitude END,
+                     latitude_min = LEAST(h.latitude_min, EXCLUDED.latitude_min),
+                     latitude_max = GREATEST(h.latitude_max, EXCLUDED.latitude_max),
+                     altitude_avg = COALESCE((h.altitude_avg * h.samples + EXCLUDED.altitude_avg * EXCLUDED.samples)
+                                             / (h.samples + EXCLUDED.samples), h.altitude_avg, EXCLUDED.altitude_avg),
+                     velocity_avg = COALESCE((h.velocity_avg * h.samples + EXCLUDED.velocity_avg * EXCLUDED.samples)
+                                             / (h.samples + EXCLUDED.samples), h.velocity_avg, EXCLUDED.velocity_avg)
+                 RETURNING 1
+             )
+             SELECT (SELECT count(*) FROM points) AS removed,
+                    (SELECT coalesce(sum(bytes), 0)::bigint FROM points) AS bytes,
+                    (SELECT count(*) FROM hourly) AS written"
+        )
+        .bind(before)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        // Months entirely before the cutoff go as whole partitions, which
+        // leaves no dead tuples behind; only the boundary month is deleted row by row
+        let partitions: Vec<String> = sqlx::query_scalar(
+            "SELECT c.relname::text FROM pg_inherits i JOIN pg_class c ON c.oid = i.inhrelid
+             WHERE i.inhparent = 'iss_fetch_log'::regclass"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let expired = expired_iss_partitions(&partitions, before);
+        for partition in &expired {
+            // Names were validated by `iss_partition_end`, so they are plain identifiers
+            (&mut *tx)
+                .execute(format!("DROP TABLE {}", partition).as_str())
+                .await
+                .map_err(db_error)?;
+        }
+        sqlx::query("DELETE FROM iss_fetch_log WHERE fetched_at < $1")
+            .bind(before)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        let mut step = CompactionStep::new("iss_fetch_log", None, CompactionAction::Downsample);
+        step.rows_removed = row.get("removed");
+        step.bytes_freed = row.get("bytes");
+        step.rows_written = row.get("written");
+        step.partitions_dropped = expired;
+        Ok(step)
+    }
+
+    async fn expire_iss_hourly(&self, before: Timestamp) -> Result<CompactionStep> {
+        let row = sqlx::query(
+            "WITH deleted AS (
+                 DELETE FROM iss_position_hourly WHERE hour < $1
+                 RETURNING pg_column_size(iss_position_hourly.*) AS bytes
+             )
+             SELECT count(*) AS removed, coalesce(sum(bytes), 0)::bigint AS bytes FROM deleted"
+        )
+        .bind(before)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let mut step = CompactionStep::new("iss_position_hourly", None, CompactionAction::Expire);
+        step.rows_removed = row.get("removed");
+        step.bytes_freed = row.get("bytes");
+        Ok(step)
+    }
+
+    async fn dedupe_space_cache(&self) -> Result<Vec<CompactionStep>> {
+        let rows = sqlx::query(
+            "WITH marked AS (
+                 SELECT id, source, last_seen_at,
+                        CASE WHEN payload_hash IS DISTINCT FROM lag(payload_hash) OVER (PARTITION BY source ORDER BY id)
+                             THEN 1 ELSE 0 END AS starts_run
+                 FROM space_cache
+             ), runs AS (
+                 SELECT id, source, last_seen_at,
+                        sum(starts_run) OVER (PARTITION BY source ORDER BY id) AS run
+                 FROM marked
+             ), grouped AS (
+                 SELECT id,
+                        min(id) OVER (PARTITION BY source, run) AS keep_id,
+                        max(last_seen_at) OVER (PARTITION BY source, run) AS seen
+                 FROM runs
+             ), touched AS (
+                 UPDATE space_cache s SET last_seen_at = g.seen
+                 FROM grouped g
+                 WHERE s.id = g.id AND g.id = g.keep_id AND s.last_seen_at < g.seen
+             ), deleted AS (
+                 DELETE FROM space_cache s USING grouped g
+                 WHERE s.id = g.id AND g.id <> g.keep_id
+                 RETURNING s.source, pg_column_size(s.*) AS bytes
+             )
+             SELECT source, count(*) AS removed, coalesce(sum(bytes), 0)::bigint AS bytes
+             FROM deleted GROUP BY source ORDER BY source"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows.into_iter().map(|row| {
+            let source: String = row.get("source");
+            let mut step = CompactionStep::new("space_cache", Some(&source), CompactionAction::Dedupe);
+            step.rows_removed = row.get("removed");
+            step.bytes_freed = row.get("bytes");
+            step
+        }).collect())
+    }
+
+    async fn get_space_cache_sources(&self) -> Result<Vec<String>> {
+        sqlx::query_scalar("SELECT DISTINCT source FROM space_cache ORDER BY source")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_error)
+    }
+
+    async fn expire_space_cache(&self, source: &str, before: Timestamp) -> Result<CompactionStep> {
+        let row = sqlx::query(
+            "WITH deleted AS (
+                 DELETE FROM space_cache
+                 WHERE source = $1 AND last_seen_at < $2
+                   AND id <> (SELECT max(id) FROM space_cache WHERE source = $1)
+                 RETURNING pg_column_size(space_cache.*) AS bytes
+             )
+             SELECT count(*) AS removed, coalesce(sum(bytes), 0)::bigint AS bytes FROM deleted"
+        )
+        .bind(source)
+        .bind(before)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let mut step = CompactionStep::new("space_cache", Some(source), CompactionAction::Expire);
+        step.rows_removed = row.get("removed");
+        step.bytes_freed = row.get("bytes");
+        Ok(step)
+    }
+
+    async fn table_size(&self, table: &str) -> Result<i64> {
+        // A partitioned parent has no storage of its own, so sum over its partitions
+        let size: Option<i64> = sqlx::query_scalar(
+            "WITH t AS (SELECT to_regclass($1) AS rel)
+             SELECT COALESCE(
+                 (SELECT sum(pg_total_relation_size(relid))::bigint FROM pg_partition_tree(t.rel)),
+                 pg_total_relation_size(t.rel))
+             FROM t"
+        )
+            .bind(table)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(size.unwrap_or(0))
+    }
+
+    async fn vacuum_table(&self, table: &str) -> Result<()> {
+        // VACUUM takes no bind parameters, so only known table names are accepted
+        if !COMPACTED_TABLES.contains(&table) {
+            return Err(RepoError::ValidationError(format!("Cannot vacuum unknown table '{}'", table)));
+        }
+        // Simple query protocol: VACUUM cannot run as a prepared statement in a transaction
+        self.pool
+            .execute(format!("VACUUM (ANALYZE) {}", table).as_str())
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    async fn insert_compaction_run(&self, report: &CompactionReport) -> Result<i64> {
+        let payload = serde_json::to_value(report)
+            .map_err(|e| RepoError::ValidationError(e.to_string()))?;
+        let row = sqlx::query(
+            "INSERT INTO compaction_runs (started_at, finished_at, rows_removed, bytes_reclaimed, report)
+             VALUES ($1, $2, $3, $4, $5) RETURNING id"
+        )
+        .bind(report.started_at)
+        .bind(report.finished_at)
+        .bind(report.rows_removed)
+        .bind(report.bytes_reclaimed)
+        .bind(payload)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(row.get("id"))
+    }
+
+    async fn get_compaction_runs(&self, limit: i64) -> Result<Vec<CompactionReport>> {
+        let rows = sqlx::query(
+            "SELECT id, report FROM compaction_runs ORDER BY started_at DESC, id DESC LIMIT $1"
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        rows.into_iter().map(|row| {
+            let mut report: CompactionReport = serde_json::from_value(row.get("report"))
+                .map_err(|e| RepoError::DatabaseError(format!("Invalid compaction report: {}", e)))?;
+            report.id = Some(row.get("id"));
+            Ok(report)
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn ts(s: &str) -> Timestamp {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_iss_partition_end() {
+        assert_eq!(iss_partition_end("iss_fetch_log_p2026_01"), Some(ts("2026-02-01T00:00:00Z")));
+        assert_eq!(iss_partition_end("iss_fetch_log_p2025_12"), Some(ts("2026-01-01T00:00:00Z")));
+        assert_eq!(iss_partition_end("iss_fetch_log_p2026_13"), None);
+        assert_eq!(iss_partition_end("iss_fetch_log_p2026_1"), None);
+        assert_eq!(iss_partition_end("iss_fetch_log_p+026_01"), None);
+        assert_eq!(iss_partition_end("iss_fetch_log_default"), None);
+    }
+
+    #[test]
+    fn test_expired_iss_partitions_keep_the_boundary_month() {
+        let names: Vec<String> = ["iss_fetch_log_p2026_03", "iss_fetch_log_p2026_01", "iss_fetch_log_p2026_02", "other"]
+            .iter().map(|s| s.to_string()).collect();
+        // The cutoff falls inside March, so March rows are deleted individually
+        assert_eq!(expired_iss_partitions(&names, ts("2026-03-10T12:00:00Z")), vec!["iss_fetch_log_p2026_01", "iss_fetch_log_p2026_02"]);
+        assert_eq!(expired_iss_partitions(&names, ts("2026-03-01T00:00:00Z")), vec!["iss_fetch_log_p2026_01", "iss_fetch_log_p2026_02"]);
+        assert!(expired_iss_partitions(&names, ts("2026-01-31T23:00:00Z")).is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use axum::{
     http::{HeaderMap, Request},
-    routing::get,
+    routing::{get, post},
     middleware::Next,
     response::Response,
     Router,
@@ -28,6 +28,13 @@ async fn health() -> axum::Json<Health> {
     })
 }
 
+pub fn health_routes() -> Router<AppState> {
+    Router::new()
+        .route("/health", get(health))
+        .route("/health/migrations", get(handlers::migration_status))
+        .route("/health/upstreams", get(handlers::upstream_status))
+}
+
 pub fn iss_routes() -> Router<AppState> {
     Router::new()
         .route("/last", get(handlers::last_iss))
@@ -45,16 +52,60 @@ pub fn osdr_routes() -> Router<AppState> {
 pub fn cache_routes() -> Router<AppState> {
     Router::new()
         .route("/space/:src/latest", get(handlers::space_latest))
+        .route("/space/:src/history", get(handlers::space_history))
         .route("/space/refresh", get(handlers::space_refresh))
         .route("/space/summary", get(handlers::space_summary))
+        .route("/cache/stats", get(handlers::cache_stats))
+}
+
+pub fn jwst_routes() -> Router<AppState> {
+    Router::new()
+        .route("/jwst/sync", get(handlers::jwst_sync))
+        .route("/jwst/feed", get(handlers::jwst_feed))
+}
+
+pub fn astro_routes() -> Router<AppState> {
+    Router::new()
+        .route("/astro/sync", get(handlers::astro_sync))
+        .route("/astro/events", get(handlers::astro_events))
+}
+
+pub fn telemetry_routes() -> Router<AppState> {
+    Router::new()
+        .route("/telemetry", get(handlers::telemetry_summary))
+        .route("/telemetry/ingest", get(handlers::telemetry_ingest))
+}
+
+pub fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/admin/cache", get(handlers::admin_cache_list).delete(handlers::admin_cache_purge))
+        .route("/admin/cache/warm", post(handlers::admin_cache_warm))
+        .route("/admin/compaction", get(handlers::admin_compaction_runs).post(handlers::admin_compaction_run))
+}
+
+pub fn jobs_routes() -> Router<AppState> {
+    Router::new()
+        .route("/jobs", get(handlers::jobs_list))
+        .route("/jobs/:name/runs", get(handlers::job_runs))
+}
+
+pub fn calendar_routes() -> Router<AppState> {
+    Router::new()
+        .route("/calendar.ics", get(handlers::calendar_ics))
 }
 
 pub fn create_routes() -> Router<AppState> {
     Router::new()
-        .route("/health", get(health))
+        .merge(health_routes())
         .merge(iss_routes())
         .merge(osdr_routes())
         .merge(cache_routes())
+        .merge(jwst_routes())
+        .merge(astro_routes())
+        .merge(telemetry_routes())
+        .merge(calendar_routes())
+        .merge(jobs_routes())
+        .merge(admin_routes())
         .layer(axum::middleware::from_fn(rate_limit_middleware))
         .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .layer(PropagateRequestIdLayer::x_request_id())
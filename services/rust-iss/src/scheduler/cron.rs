@@ -0,0 +1,218 @@
+//! Job schedules: fixed intervals and five-field cron expressions (UTC).
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, DurationRound, Months, NaiveTime, Timelike, Utc};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How far ahead `next_after` looks before giving up on an expression
+const SEARCH_YEARS: u32 = 5;
+
+/// When a job runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// A fixed delay between the end of one run and the start of the next
+    Every(Duration),
+    Cron(Cron),
+}
+
+impl Schedule {
+    /// The first run time strictly after `after`, `None` when there is none
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(period) => after.checked_add_signed(ChronoDuration::from_std(*period).ok()?),
+            Schedule::Cron(cron) => cron.next_after(after),
+        }
+    }
+}
+
+/// Plain seconds (`300`), an alias (`@hourly`, `@daily`, `@midnight`,
+/// `@weekly`, `@monthly`) or `minute hour day-of-month month day-of-week`
+impl FromStr for Schedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            return match s.parse::<u64>() {
+                Ok(secs) if secs > 0 => Ok(Schedule::Every(Duration::from_secs(secs))),
+                _ => Err(format!("interval '{}' must be a positive number of seconds", s)),
+            };
+        }
+        let expr = match s {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        Cron::parse(expr).map(Schedule::Cron)
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Every(period) => write!(f, "every {}s", period.as_secs()),
+            Schedule::Cron(cron) => f.write_str(&cron.expr),
+        }
+    }
+}
+
+/// A parsed cron expression; each field is a bitmask of allowed values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    expr: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether day-of-month / day-of-week were `*`; when both are
+    /// restricted a day matching either runs, as in classic cron
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return Err(format!("cron expression '{}' must have 5 fields", expr));
+        };
+        let mut weekdays = parse_field(weekday, 0, 7, "day-of-week")?;
+        // Both 0 and 7 are Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        let cron = Self {
+            expr: fields.join(" "),
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days: parse_field(day, 1, 31, "day-of-month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            weekdays,
+            any_day: *day == "*",
+            any_weekday: *weekday == "*",
+        };
+        // Rejects expressions such as `0 0 30 2 *`
+        let epoch = DateTime::<Utc>::from_timestamp(946_684_800, 0).expect("valid epoch");
+        if cron.next_after(epoch).is_none() {
+            return Err(format!("cron expression '{}' never fires", expr));
+        }
+        Ok(cron)
+    }
+
+    fn day_matches(&self, at: DateTime<Utc>) -> bool {
+        let day = self.days & (1 << at.day()) != 0;
+        let weekday = self.weekdays & (1 << at.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut at = after.duration_trunc(ChronoDuration::minutes(1)).ok()? + ChronoDuration::minutes(1);
+        let limit = after.checked_add_months(Months::new(12 * SEARCH_YEARS))?;
+        while at <= limit {
+            if self.months & (1 << at.month()) == 0 {
+                let month_start = at.date_naive().with_day(1)?.and_time(NaiveTime::MIN).and_utc();
+                at = month_start.checked_add_months(Months::new(1))?;
+            } else if !self.day_matches(at) {
+                at = (at.date_naive().succ_opt()?).and_time(NaiveTime::MIN).and_utc();
+            } else if self.hours & (1 << at.hour()) == 0 {
+                at = at.duration_trunc(ChronoDuration::hours(1)).ok()? + ChronoDuration::hours(1);
+            } else if self.minutes & (1 << at.minute()) == 0 {
+                at += ChronoDuration::minutes(1);
+            } else {
+                return Some(at);
+            }
+        }
+        None
+    }
+}
+
+/// Parse one field (`*`, `n`, `a-b`, `*/step`, `a-b/step` or a comma list
+/// of those) into a bitmask of the values it allows
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid cron {} field '{}'", name, field);
+    let number = |s: &str| -> Result<u32, String> {
+        s.parse::<u32>().ok().filter(|n| (min..=max).contains(n)).ok_or_else(|| {
+            format!("cron {} value '{}' must be between {} and {}", name, s, min, max)
+        })
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0).ok_or_else(invalid)?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((a, b)) => (number(a)?, number(b)?),
+                // `n/step` runs from n to the end of the range
+                None if step > 1 => (number(range)?, max),
+                None => (number(range)?, number(range)?),
+            },
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn next(expr: &str, after: DateTime<Utc>) -> DateTime<Utc> {
+        expr.parse::<Schedule>().unwrap().next_after(after).unwrap()
+    }
+
+    #[test]
+    fn test_parses_intervals_and_aliases() {
+        assert_eq!("300".parse::<Schedule>().unwrap(), Schedule::Every(Duration::from_secs(300)));
+        assert_eq!("300".parse::<Schedule>().unwrap().to_string(), "every 300s");
+        assert!("0".parse::<Schedule>().is_err());
+        assert_eq!("@hourly".parse::<Schedule>().unwrap().to_string(), "0 * * * *");
+        assert_eq!(" */5  * * * * ".parse::<Schedule>().unwrap().to_string(), "*/5 * * * *");
+
+        for bad in ["", "* * * *", "60 * * * *", "* 24 * * *", "* * 0 * *", "5-1 * * * *", "*/0 * * * *", "0 0 30 2 *"] {
+            assert!(bad.parse::<Schedule>().is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_next_after() {
+        let start = at(2026, 1, 31, 10, 7);
+        assert_eq!(next("*/15 * * * *", start), at(2026, 1, 31, 10, 15));
+        assert_eq!(next("0 * * * *", start), at(2026, 1, 31, 11, 0));
+        assert_eq!(next("30 2 * * *", start), at(2026, 2, 1, 2, 30));
+        // Strictly after, even on a matching minute
+        assert_eq!(next("7 10 * * *", start), at(2026, 2, 1, 10, 7));
+        assert_eq!(next("0 0 29 2 *", start), at(2028, 2, 29, 0, 0));
+        assert_eq!(next("0 9 * * 1-5", at(2026, 1, 30, 12, 0)), at(2026, 2, 2, 9, 0));
+        assert_eq!(next("0 0 * * 7", start), at(2026, 2, 1, 0, 0));
+        assert_eq!(next("0 12 1,15 * *", start), at(2026, 2, 1, 12, 0));
+        // Day-of-month and day-of-week restricted together match either
+        assert_eq!(next("0 0 13 * 5", at(2026, 2, 1, 0, 0)), at(2026, 2, 6, 0, 0));
+        assert_eq!(next("@monthly", at(2026, 12, 15, 0, 0)), at(2027, 1, 1, 0, 0));
+
+        let every = "90".parse::<Schedule>().unwrap();
+        assert_eq!(every.next_after(start), Some(start + ChronoDuration::seconds(90)));
+    }
+}
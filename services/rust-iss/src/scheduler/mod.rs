@@ -0,0 +1,497 @@
+//! Background job scheduler.
+//!
+//! Every registered job runs in its own loop: it sleeps until the next slot
+//! of its schedule plus a random jitter, runs, and only then computes the
+//! next slot, so a slow run skips the slots it overlapped instead of
+//! queueing them. Runs are recorded in `job_runs`.
+//!
+//! With several replicas, a job only runs on the replica holding its lock.
+//! Every replica keeps the schedule; at each slot a replica that does not
+//! own the job tries to take it over and skips the slot if it cannot, so
+//! the jobs of a replica that died move to the others at their next slot.
+
+use chrono::Utc;
+use futures_util::future::BoxFuture;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::clients::jitter;
+use crate::config::SchedulerConfig;
+use crate::domain::{JobRun, JobStatus, Timestamp};
+use crate::repo::{JobLockRepo, JobRepo, Result as RepoResult};
+
+pub mod cron;
+
+pub use cron::Schedule;
+
+/// Outcome of a job: the number of items it fetched or wrote
+pub type JobResult = Result<u64, Box<dyn std::error::Error + Send + Sync>>;
+
+type JobFn = Arc<dyn Fn() -> BoxFuture<'static, JobResult> + Send + Sync>;
+
+struct Job {
+    name: String,
+    schedule: Schedule,
+    /// Run once right after start instead of waiting for the first slot
+    run_at_startup: bool,
+    run: JobFn,
+    state: Mutex<JobState>,
+}
+
+#[derive(Default)]
+struct JobState {
+    running: bool,
+    /// Whether this replica owned the job at its last slot
+    owned: bool,
+    next_run: Option<Timestamp>,
+    last_run: Option<JobRun>,
+}
+
+/// Current state of a registered job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobInfo {
+    pub name: String,
+    pub schedule: String,
+    pub running: bool,
+    /// Whether this replica runs the job; false while another replica owns it
+    pub owned: bool,
+    pub next_run: Option<Timestamp>,
+    pub last_run: Option<JobRun>,
+}
+
+#[derive(Clone)]
+pub struct Scheduler<R: JobRepo + Clone> {
+    repo: R,
+    config: SchedulerConfig,
+    locks: Option<Arc<dyn JobLockRepo + Send + Sync>>,
+    jobs: Arc<RwLock<Vec<Arc<Job>>>>,
+}
+
+impl<R: JobRepo + Clone + Send + Sync + 'static> Scheduler<R> {
+    pub fn new(repo: R) -> Self {
+        Self {
+            repo,
+            config: SchedulerConfig {
+                jitter: Duration::ZERO,
+                schedules: HashMap::new(),
+                leader_election: false,
+                replica: "rust_iss".to_string(),
+            },
+            locks: None,
+            jobs: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Jitter and per-job schedule overrides from `SCHEDULER_*` / `JOB_SCHEDULE_*`
+    pub fn with_config(mut self, config: &SchedulerConfig) -> Self {
+        self.config = config.clone();
+        self
+    }
+
+    /// Only run the jobs this replica holds the lock of
+    pub fn with_locks(mut self, locks: Arc<dyn JobLockRepo + Send + Sync>) -> Self {
+        self.locks = Some(locks);
+        self
+    }
+
+    /// Register a job under `name`. A configured schedule replaces `default`;
+    /// a job left without one is not scheduled.
+    pub fn register<F, Fut>(&self, name: &str, default: Option<Schedule>, run_at_startup: bool, run: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = JobResult> + Send + 'static,
+    {
+        let Some(schedule) = self.config.schedule_for(name, default) else {
+            info!("Job {} is disabled", name);
+            return;
+        };
+        let job = Job {
+            name: name.to_string(),
+            schedule,
+            run_at_startup,
+            run: Arc::new(move || Box::pin(run()) as BoxFuture<'static, JobResult>),
+            state: Mutex::new(JobState::default()),
+        };
+        self.jobs.write().expect("job list lock").push(Arc::new(job));
+    }
+
+    fn job(&self, name: &str) -> Option<Arc<Job>> {
+        self.jobs.read().expect("job list lock").iter().find(|j| j.name == name).cloned()
+    }
+
+    pub fn has_job(&self, name: &str) -> bool {
+        self.job(name).is_some()
+    }
+
+    /// Spawn the loop of every registered job
+    pub fn start(&self, token: CancellationToken) {
+        let jobs = self.jobs.read().expect("job list lock").clone();
+        for job in jobs {
+            info!("Scheduling job {} ({})", job.name, job.schedule);
+            let scheduler = self.clone();
+            let token = token.clone();
+            tokio::spawn(async move {
+                scheduler.run_loop(job, token).await;
+            });
+        }
+    }
+
+    async fn run_loop(&self, job: Arc<Job>, token: CancellationToken) {
+        let mut slot = if job.run_at_startup {
+            Some(Utc::now())
+        } else {
+            job.schedule.next_after(Utc::now())
+        };
+        while let Some(at) = slot {
+            let at = at + chrono::Duration::from_std(self.config.jitter.mul_f64(jitter())).unwrap_or_default();
+            job.state.lock().expect("job state lock").next_run = Some(at);
+            let wait = (at - Utc::now()).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = token.cancelled() => {
+                    info!("Job {} shutting down", job.name);
+                    return;
+                }
+            }
+            self.run_slot(&job).await;
+            slot = job.schedule.next_after(Utc::now());
+        }
+        warn!("Job {} has no further runs scheduled", job.name);
+    }
+
+    /// Whether this replica owns `job`, taking it over when no replica does
+    async fn owns(&self, job: &Job) -> bool {
+        let Some(locks) = &self.locks else {
+            return true;
+        };
+        let owned = match locks.try_lock_job(&job.name).await {
+            Ok(owned) => owned,
+            Err(e) => {
+                warn!("Failed to check ownership of job {}: {}", job.name, e);
+                false
+            }
+        };
+        let mut state = job.state.lock().expect("job state lock");
+        if owned != state.owned {
+            if owned {
+                info!("Replica {} now runs job {}", self.config.replica, job.name);
+            } else {
+                info!("Job {} is no longer run by replica {}", job.name, self.config.replica);
+            }
+        } else if !owned {
+            debug!("Job {} is owned by another replica, skipping this slot", job.name);
+        }
+        state.owned = owned;
+        owned
+    }
+
+    /// Run a job at one of its slots if this replica owns it
+    async fn run_slot(&self, job: &Job) -> Option<JobRun> {
+        if !self.owns(job).await {
+            return None;
+        }
+        self.run_once(job).await
+    }
+
+    /// Run a job now and record the outcome; `None` when it is already running
+    async fn run_once(&self, job: &Job) -> Option<JobRun> {
+        {
+            let mut state = job.state.lock().expect("job state lock");
+            if state.running {
+                warn!("Job {} is still running, skipping this slot", job.name);
+                return None;
+            }
+            state.running = true;
+            state.next_run = None;
+        }
+
+        let mut run = JobRun::started(&job.name, &self.config.replica, Utc::now());
+        match self.repo.start_job_run(&run).await {
+            Ok(id) => run.id = Some(id),
+            Err(e) => warn!("Failed to record start of job {}: {}", job.name, e),
+        }
+
+        // A panicking job fails its run rather than the scheduler loop
+        let outcome = match tokio::spawn((job.run)()).await {
+            Ok(outcome) => outcome,
+            Err(e) => Err(format!("job panicked: {}", e).into()),
+        };
+        run.finished_at = Some(Utc::now());
+        match outcome {
+            Ok(items) => {
+                run.status = JobStatus::Succeeded;
+                run.items = Some(items as i64);
+            }
+            Err(e) => {
+                error!("Job {} failed: {}", job.name, e);
+                run.status = JobStatus::Failed;
+                run.error = Some(e.to_string());
+            }
+        }
+        if run.id.is_some() {
+            if let Err(e) = self.repo.finish_job_run(&run).await {
+                warn!("Failed to record outcome of job {}: {}", job.name, e);
+            }
+        }
+
+        let mut state = job.state.lock().expect("job state lock");
+        state.running = false;
+        state.last_run = Some(run.clone());
+        Some(run)
+    }
+
+    /// Every registered job; jobs that have not run since startup report
+    /// their last recorded run
+    pub async fn jobs(&self) -> RepoResult<Vec<JobInfo>> {
+        let jobs = self.jobs.read().expect("job list lock").clone();
+        let mut infos: Vec<JobInfo> = jobs.iter().map(|job| {
+            let state = job.state.lock().expect("job state lock");
+            JobInfo {
+                name: job.name.clone(),
+                schedule: job.schedule.to_string(),
+                running: state.running,
+                owned: self.locks.is_none() || state.owned,
+                next_run: state.next_run,
+                last_run: state.last_run.clone(),
+            }
+        }).collect();
+
+        if infos.iter().any(|info| info.last_run.is_none()) {
+            let mut latest: HashMap<String, JobRun> = self.repo.get_latest_job_runs().await?
+                .into_iter()
+                .map(|run| (run.job.clone(), run))
+                .collect();
+            for info in infos.iter_mut().filter(|info| info.last_run.is_none()) {
+                info.last_run = latest.remove(&info.name);
+            }
+        }
+        Ok(infos)
+    }
+
+    /// Recorded runs of a job, newest first
+    pub async fn runs(&self, name: &str, limit: i64) -> RepoResult<Vec<JobRun>> {
+        self.repo.get_job_runs(name, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Clone, Default)]
+    struct MockJobRepo {
+        runs: Arc<Mutex<Vec<JobRun>>>,
+    }
+
+    #[async_trait]
+    impl JobRepo for MockJobRepo {
+        async fn start_job_run(&self, run: &JobRun) -> RepoResult<i64> {
+            let mut runs = self.runs.lock().unwrap();
+            let mut run = run.clone();
+            run.id = Some(runs.len() as i64 + 1);
+            runs.push(run);
+            Ok(runs.len() as i64)
+        }
+
+        async fn finish_job_run(&self, run: &JobRun) -> RepoResult<()> {
+            let mut runs = self.runs.lock().unwrap();
+            let stored = runs.iter_mut().find(|r| r.id == run.id).unwrap();
+            *stored = run.clone();
+            Ok(())
+        }
+
+        async fn get_job_runs(&self, job: &str, limit: i64) -> RepoResult<Vec<JobRun>> {
+            let runs = self.runs.lock().unwrap();
+            Ok(runs.iter().rev().filter(|r| r.job == job).take(limit as usize).cloned().collect())
+        }
+
+        async fn get_latest_job_runs(&self) -> RepoResult<Vec<JobRun>> {
+            let runs = self.runs.lock().unwrap();
+            let mut latest: HashMap<String, JobRun> = HashMap::new();
+            for run in runs.iter() {
+                latest.insert(run.job.clone(), run.clone());
+            }
+            Ok(latest.into_values().collect())
+        }
+    }
+
+    /// Locks shared by the replicas of a test, keyed by job
+    #[derive(Clone, Default)]
+    struct MockLockTable {
+        owners: Arc<Mutex<HashMap<String, String>>>,
+        unreachable: Arc<Mutex<bool>>,
+    }
+
+    impl MockLockTable {
+        fn replica(&self, name: &str) -> Arc<dyn JobLockRepo + Send + Sync> {
+            Arc::new(MockJobLocks { table: self.clone(), replica: name.to_string() })
+        }
+
+        /// The replica's lock session ended
+        fn release(&self, replica: &str) {
+            self.owners.lock().unwrap().retain(|_, owner| owner != replica);
+        }
+    }
+
+    struct MockJobLocks {
+        table: MockLockTable,
+        replica: String,
+    }
+
+    #[async_trait]
+    impl JobLockRepo for MockJobLocks {
+        async fn try_lock_job(&self, job: &str) -> RepoResult<bool> {
+            if *self.table.unreachable.lock().unwrap() {
+                return Err(crate::repo::RepoError::DatabaseError("connection reset".to_string()));
+            }
+            let mut owners = self.table.owners.lock().unwrap();
+            let owner = owners.entry(job.to_string()).or_insert_with(|| self.replica.clone());
+            Ok(*owner == self.replica)
+        }
+    }
+
+    fn hourly() -> Option<Schedule> {
+        Some(Schedule::Every(Duration::from_secs(3600)))
+    }
+
+    #[tokio::test]
+    async fn test_records_successful_and_failed_runs() {
+        let repo = MockJobRepo::default();
+        let scheduler = Scheduler::new(repo.clone());
+        scheduler.register("ok", hourly(), false, || async { Ok(3) });
+        scheduler.register("broken", hourly(), false, || async { Err("upstream down".into()) });
+
+        let ok = scheduler.run_once(&scheduler.job("ok").unwrap()).await.unwrap();
+        assert_eq!(ok.status, JobStatus::Succeeded);
+        assert_eq!(ok.items, Some(3));
+        let broken = scheduler.run_once(&scheduler.job("broken").unwrap()).await.unwrap();
+        assert_eq!(broken.status, JobStatus::Failed);
+        assert_eq!(broken.error.as_deref(), Some("upstream down"));
+
+        let runs = scheduler.runs("broken", 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, JobStatus::Failed);
+        assert!(runs[0].finished_at.is_some());
+
+        let jobs = scheduler.jobs().await.unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].schedule, "every 3600s");
+        assert_eq!(jobs[0].last_run.as_ref().unwrap().items, Some(3));
+        assert!(!jobs[1].running);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_run_is_skipped() {
+        let scheduler = Scheduler::new(MockJobRepo::default());
+        let release = Arc::new(tokio::sync::Notify::new());
+        let wait = release.clone();
+        scheduler.register("slow", hourly(), false, move || {
+            let wait = wait.clone();
+            async move {
+                wait.notified().await;
+                Ok(1)
+            }
+        });
+        let job = scheduler.job("slow").unwrap();
+
+        let first = tokio::spawn({
+            let scheduler = scheduler.clone();
+            let job = job.clone();
+            async move { scheduler.run_once(&job).await }
+        });
+        while !job.state.lock().unwrap().running {
+            tokio::task::yield_now().await;
+        }
+        assert!(scheduler.run_once(&job).await.is_none());
+        release.notify_one();
+        assert_eq!(first.await.unwrap().unwrap().status, JobStatus::Succeeded);
+        assert_eq!(scheduler.runs("slow", 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_fails_its_run() {
+        let scheduler = Scheduler::new(MockJobRepo::default());
+        scheduler.register("panics", hourly(), false, || async { panic!("boom") });
+        let run = scheduler.run_once(&scheduler.job("panics").unwrap()).await.unwrap();
+        assert_eq!(run.status, JobStatus::Failed);
+        assert!(run.error.unwrap().contains("panicked"));
+        assert!(!scheduler.job("panics").unwrap().state.lock().unwrap().running);
+    }
+
+    #[tokio::test]
+    async fn test_only_the_owning_replica_runs_a_job() {
+        let repo = MockJobRepo::default();
+        let locks = MockLockTable::default();
+        let replica = |name: &str| {
+            let config = SchedulerConfig {
+                jitter: Duration::ZERO,
+                schedules: HashMap::new(),
+                leader_election: true,
+                replica: name.to_string(),
+            };
+            let scheduler = Scheduler::new(repo.clone()).with_config(&config).with_locks(locks.replica(name));
+            scheduler.register("iss", hourly(), true, || async { Ok(1) });
+            scheduler
+        };
+        let (a, b) = (replica("a"), replica("b"));
+        let (job_a, job_b) = (a.job("iss").unwrap(), b.job("iss").unwrap());
+
+        assert_eq!(a.run_slot(&job_a).await.unwrap().replica.as_deref(), Some("a"));
+        assert!(b.run_slot(&job_b).await.is_none());
+        assert!(a.jobs().await.unwrap()[0].owned);
+        assert!(!b.jobs().await.unwrap()[0].owned);
+
+        // Replica a went away; b takes the job over at its next slot
+        locks.release("a");
+        assert_eq!(b.run_slot(&job_b).await.unwrap().replica.as_deref(), Some("b"));
+        assert!(a.run_slot(&job_a).await.is_none());
+
+        // Without the lock database nobody runs the job
+        *locks.unreachable.lock().unwrap() = true;
+        assert!(b.run_slot(&job_b).await.is_none());
+        assert!(!b.jobs().await.unwrap()[0].owned);
+
+        let runs = repo.get_job_runs("iss", 10).await.unwrap();
+        let replicas: Vec<_> = runs.iter().map(|r| r.replica.as_deref().unwrap()).collect();
+        assert_eq!(replicas, vec!["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_start_runs_jobs_until_cancelled() {
+        let mut config = SchedulerConfig {
+            jitter: Duration::ZERO,
+            schedules: HashMap::new(),
+            leader_election: true,
+            replica: "a".to_string(),
+        };
+        config.schedules.insert("disabled".to_string(), None);
+        let scheduler = Scheduler::new(MockJobRepo::default()).with_config(&config);
+        let count = Arc::new(AtomicU64::new(0));
+        let counter = count.clone();
+        scheduler.register("tick", Some(Schedule::Every(Duration::from_millis(20))), true, move || {
+            let counter = counter.clone();
+            async move { Ok(counter.fetch_add(1, Ordering::SeqCst)) }
+        });
+        scheduler.register("disabled", hourly(), true, || async { Ok(0) });
+        assert!(!scheduler.has_job("disabled"));
+
+        let token = CancellationToken::new();
+        scheduler.start(token.clone());
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        token.cancel();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        let ran = count.load(Ordering::SeqCst);
+        assert!(ran >= 2, "ran {} times", ran);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(count.load(Ordering::SeqCst), ran);
+        assert!(scheduler.jobs().await.unwrap()[0].next_run.is_some());
+    }
+}
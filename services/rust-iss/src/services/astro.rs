@@ -0,0 +1,299 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::config::ObserverLocation;
+use crate::domain::*;
+use crate::repo::*;
+use crate::services::*;
+use crate::clients::AstroClient;
+
+/// Bodies whose events (eclipses with rise/set times) are ingested
+const EVENT_BODIES: [&str; 2] = ["sun", "moon"];
+
+/// Moon phases that are stored as events when the phase changes
+const PRINCIPAL_MOON_PHASES: [&str; 5] = ["New Moon", "First Quarter", "Full Moon", "Last Quarter", "Third Quarter"];
+
+/// Implementation of astronomical events Service
+#[derive(Clone)]
+pub struct AstroServiceImpl<R: AstroRepo + Clone, C: AstroClient + Clone> {
+    repo: R,
+    client: C,
+    observers: Vec<ObserverLocation>,
+    horizon_days: u64,
+}
+
+impl<R: AstroRepo + Clone, C: AstroClient + Clone> AstroServiceImpl<R, C> {
+    pub fn new(repo: R, client: C) -> Self {
+        Self {
+            repo,
+            client,
+            observers: Vec::new(),
+            horizon_days: 30,
+        }
+    }
+
+    /// Configure observer locations and how many days ahead to ingest
+    pub fn with_observers(mut self, observers: Vec<ObserverLocation>, horizon_days: u64) -> Self {
+        self.observers = observers;
+        self.horizon_days = horizon_days;
+        self
+    }
+
+    /// Fetch and store all events for a single observer
+    async fn sync_observer(&self, observer: &ObserverLocation, from: &str, to: &str) -> crate::services::Result<usize> {
+        let mut events = Vec::new();
+
+        for body in EVENT_BODIES {
+            let json = self.client
+                .fetch_body_events(body, observer, from, to)
+                .await
+                .map_err(|e| ServiceError::ExternalApiError(format!("AstronomyAPI {} events request failed: {}", body, e)))?;
+            events.extend(parse_body_events(&json, &observer.name));
+        }
+
+        let json = self.client
+            .fetch_body_positions(observer, from, to)
+            .await
+            .map_err(|e| ServiceError::ExternalApiError(format!("AstronomyAPI positions request failed: {}", e)))?;
+        events.extend(parse_moon_phases(&json, &observer.name));
+
+        let mut written = 0usize;
+        for event in events {
+            if let Err(e) = event.validate() {
+                warn!("Skipping invalid astronomical event {}/{}: {}", event.body, event.event_type, e);
+                continue;
+            }
+
+            self.repo
+                .upsert_astro_event(&event)
+                .await
+                .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+#[async_trait]
+impl<R: AstroRepo + Clone + Sync, C: AstroClient + Clone + Sync> AstroService for AstroServiceImpl<R, C> {
+    async fn sync_astro_events(&self) -> crate::services::Result<usize> {
+        let today = Utc::now().date_naive();
+        let from = today.to_string();
+        let to = (today + chrono::Days::new(self.horizon_days)).to_string();
+
+        let mut written = 0usize;
+        for observer in &self.observers {
+            let count = self.sync_observer(observer, &from, &to).await?;
+            info!("Stored {} astronomical events for observer {}", count, observer.name);
+            written += count;
+        }
+        Ok(written)
+    }
+
+    async fn get_astro_events(&self, filter: &AstroEventFilter, limit: i64) -> crate::services::Result<Vec<AstroEvent>> {
+        let mut filter = filter.clone();
+        if filter.observer.is_none() {
+            filter.observer = self.observers.first().map(|o| o.name.clone());
+        }
+
+        self.repo
+            .get_astro_events(&filter, limit)
+            .await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+    }
+}
+
+/// Parse a `bodies/events` response into eclipse and rise/set events
+fn parse_body_events(json: &Value, observer: &str) -> Vec<AstroEvent> {
+    let mut events = Vec::new();
+    let rows = json.pointer("/data/rows").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+
+    for row in rows {
+        let Some(body) = row.pointer("/body/id").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        for event in row.get("events").and_then(|v| v.as_array()).into_iter().flatten() {
+            let event_type = event.get("type").and_then(|v| v.as_str()).unwrap_or("unknown");
+            let highlights = event.get("eventHighlights").cloned().unwrap_or(Value::Null);
+
+            let starts_at = highlight_date(&highlights, "partialStart")
+                .or_else(|| highlight_date(&highlights, "peak"));
+            if let Some(starts_at) = starts_at {
+                let mut eclipse = AstroEvent::new(
+                    observer.to_string(),
+                    body.to_string(),
+                    event_type.to_string(),
+                    starts_at,
+                    serde_json::json!({
+                        "peak": highlight_date(&highlights, "peak"),
+                        "highlights": highlights,
+                        "extra": event.get("extraInfo").cloned().unwrap_or(Value::Null),
+                    }),
+                );
+                eclipse.ends_at = highlight_date(&highlights, "partialEnd");
+                events.push(eclipse);
+            }
+
+            for kind in ["rise", "set"] {
+                if let Some(at) = event.get(kind).and_then(|v| v.as_str()).and_then(parse_date) {
+                    events.push(AstroEvent::new(
+                        observer.to_string(),
+                        body.to_string(),
+                        kind.to_string(),
+                        at,
+                        serde_json::json!({ "related_event": event_type }),
+                    ));
+                }
+            }
+        }
+    }
+    events
+}
+
+/// Parse a `bodies/positions` response into moon phase change events
+fn parse_moon_phases(json: &Value, observer: &str) -> Vec<AstroEvent> {
+    let rows = json.pointer("/data/table/rows").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    let Some(moon) = rows.iter().find(|r| r.pointer("/entry/id").and_then(|v| v.as_str()) == Some("moon")) else {
+        return Vec::new();
+    };
+
+    let mut events = Vec::new();
+    let mut previous: Option<String> = None;
+    for cell in moon.get("cells").and_then(|v| v.as_array()).into_iter().flatten() {
+        let phase = cell.pointer("/extraInfo/phase/string").and_then(|v| v.as_str()).map(|s| s.to_string());
+        let at = cell.get("date").and_then(|v| v.as_str()).and_then(parse_date);
+
+        if let (Some(phase), Some(at)) = (phase.as_ref(), at) {
+            let changed = previous.as_deref() != Some(phase.as_str());
+            if changed && PRINCIPAL_MOON_PHASES.contains(&phase.as_str()) {
+                events.push(AstroEvent::new(
+                    observer.to_string(),
+                    "moon".to_string(),
+                    "moon_phase".to_string(),
+                    at,
+                    serde_json::json!({
+                        "phase": phase,
+                        "fraction": cell.pointer("/extraInfo/phase/fraction"),
+                        "angle": cell.pointer("/extraInfo/phase/angel"),
+                    }),
+                ));
+            }
+        }
+        previous = phase;
+    }
+    events
+}
+
+fn highlight_date(highlights: &Value, key: &str) -> Option<DateTime<Utc>> {
+    highlights.get(key)?.get("date")?.as_str().and_then(parse_date)
+}
+
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s).ok().map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::Result as ClientResult;
+
+    fn events_response() -> Value {
+        serde_json::json!({"data": {"rows": [{
+            "body": {"id": "sun", "name": "Sun"},
+            "events": [{
+                "type": "total_solar_eclipse",
+                "eventHighlights": {
+                    "partialStart": {"date": "2026-08-12T17:00:00.000+00:00", "altitude": 10.0},
+                    "peak": {"date": "2026-08-12T18:00:00.000+00:00", "altitude": 5.0},
+                    "partialEnd": {"date": "2026-08-12T19:00:00.000+00:00", "altitude": 1.0}
+                },
+                "rise": "2026-08-12T03:00:00.000+00:00",
+                "set": "2026-08-12T20:00:00.000+00:00",
+                "extraInfo": {"obscuration": 1}
+            }]
+        }]}})
+    }
+
+    fn positions_response() -> Value {
+        let cell = |date: &str, phase: &str| serde_json::json!({
+            "date": date, "id": "moon",
+            "extraInfo": {"phase": {"angel": "90", "fraction": "0.5", "string": phase}}
+        });
+        serde_json::json!({"data": {"table": {"rows": [
+            {"entry": {"id": "sun"}, "cells": []},
+            {"entry": {"id": "moon"}, "cells": [
+                cell("2026-08-01T00:00:00.000+00:00", "Waxing Gibbous"),
+                cell("2026-08-02T00:00:00.000+00:00", "Full Moon"),
+                cell("2026-08-03T00:00:00.000+00:00", "Full Moon"),
+                cell("2026-08-04T00:00:00.000+00:00", "Waning Gibbous")
+            ]}
+        ]}}})
+    }
+
+    #[derive(Clone)]
+    struct MockAstroRepo;
+
+    #[async_trait]
+    impl AstroRepo for MockAstroRepo {
+        async fn upsert_astro_event(&self, _event: &AstroEvent) -> crate::repo::Result<i64> {
+            Ok(1)
+        }
+
+        async fn get_astro_events(&self, _filter: &AstroEventFilter, _limit: i64) -> crate::repo::Result<Vec<AstroEvent>> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockAstroClient;
+
+    #[async_trait]
+    impl AstroClient for MockAstroClient {
+        async fn fetch_body_events(&self, body: &str, _observer: &ObserverLocation, _from_date: &str, _to_date: &str) -> ClientResult<Value> {
+            if body == "sun" {
+                Ok(events_response())
+            } else {
+                Ok(serde_json::json!({"data": {"rows": []}}))
+            }
+        }
+
+        async fn fetch_body_positions(&self, _observer: &ObserverLocation, _from_date: &str, _to_date: &str) -> ClientResult<Value> {
+            Ok(positions_response())
+        }
+    }
+
+    #[test]
+    fn test_parse_body_events() {
+        let events = parse_body_events(&events_response(), "default");
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].event_type, "total_solar_eclipse");
+        assert!(events[0].ends_at.is_some());
+        assert_eq!(events[1].event_type, "rise");
+        assert_eq!(events[2].event_type, "set");
+    }
+
+    #[test]
+    fn test_parse_moon_phases_only_on_change() {
+        let events = parse_moon_phases(&positions_response(), "default");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].details["phase"], "Full Moon");
+    }
+
+    #[tokio::test]
+    async fn test_sync_astro_events() {
+        let observer = ObserverLocation {
+            name: "default".to_string(),
+            latitude: 65.9558,
+            longitude: 37.6171,
+            elevation: 7.0,
+        };
+        let service = AstroServiceImpl::new(MockAstroRepo, MockAstroClient)
+            .with_observers(vec![observer], 30);
+        let result = service.sync_astro_events().await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 4);
+    }
+}
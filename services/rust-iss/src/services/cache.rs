@@ -1,313 +1,324 @@
 use async_trait::async_trait;
-use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
-use serde_json::Value;
-use std::time::Duration;
+use std::future::Future;
+use tracing::{debug, info, warn};
 
 use crate::domain::*;
-use crate::repo::{RepoError, IssRepo, OsdrRepo, CacheRepo};
+use crate::repo::{IssRepo, OsdrRepo, CacheRepo};
 use crate::services::*;
-use crate::clients::{NasaClient, SpaceXClient, Result as ClientResult};
+use crate::clients::{NasaQuota, Result as ClientResult};
 
 /// Implementation of Cache Service
 #[derive(Clone)]
-pub struct CacheServiceImpl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Clone, N: NasaClient + Clone + Sync, S: SpaceXClient + Clone + Sync> {
+pub struct CacheServiceImpl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Clone> {
     repo: R,
-    nasa_client: N,
-    spacex_client: S,
-    redis_repo: Option<crate::repo::RedisRepos>,
+    sources: SourceRegistry,
+    cache: CacheLayer,
+    local: LocalCache<SpaceCache>,
+    quota: Option<NasaQuota>,
 }
 
-impl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Clone, N: NasaClient + Clone + Sync, S: SpaceXClient + Clone + Sync> CacheServiceImpl<R, N, S> {
-    pub fn new(repo: R, nasa_client: N, spacex_client: S) -> Self {
+impl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Clone> CacheServiceImpl<R> {
+    pub fn new(repo: R, sources: SourceRegistry) -> Self {
+        let cache = CacheLayer::default();
         Self {
             repo,
-            nasa_client,
-            spacex_client,
-            redis_repo: None,
+            sources,
+            local: cache.local_tier(),
+            cache,
+            quota: None,
         }
     }
 
-    pub fn with_redis(mut self, redis_repo: crate::repo::RedisRepos) -> Self {
-        self.redis_repo = Some(redis_repo);
+    /// Draw NASA API keys from a shared quota, deferring calls it cannot afford
+    pub fn with_quota(mut self, quota: NasaQuota) -> Self {
+        self.quota = Some(quota);
         self
     }
-}
 
-#[async_trait]
-impl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Clone, N: NasaClient + Clone + Sync, S: SpaceXClient + Clone + Sync> CacheService for CacheServiceImpl<R, N, S> {
-    async fn fetch_and_cache_apod(&self, api_key: Option<&str>) -> Result<SpaceCache> {
-        let json = self.nasa_client
-            .fetch_apod(api_key)
-            .await
-            .map_err(|e| ServiceError::ExternalApiError(format!("APOD API request failed: {}", e)))?;
+    /// Read and write cached sources through the Redis cache layer and an
+    /// in-process tier sized by its config
+    pub fn with_cache(mut self, cache: CacheLayer) -> Self {
+        self.local = cache.local_tier();
+        self.cache = cache;
+        self
+    }
+
+    /// Refuse to call an upstream API that failed within the negative TTL
+    fn check_recent_failure(&self, source: &str, what: &str) -> Result<()> {
+        match self.cache.recent_failure(source) {
+            Some(failure) => Err(ServiceError::ExternalApiError(format!("{} skipped, failed recently: {}", what, failure))),
+            None => Ok(()),
+        }
+    }
 
-        let json_str = serde_json::to_string(&json).unwrap();
+    /// Call an upstream API, remembering new failures
+    async fn call_upstream<T, F>(&self, source: &str, what: &str, call: F) -> Result<T>
+    where
+        F: Future<Output = ClientResult<T>> + Send,
+    {
+        match call.await {
+            Ok(json) => {
+                self.cache.clear_failure(source);
+                Ok(json)
+            }
+            Err(e) => {
+                let message = format!("{} request failed: {}", what, e);
+                self.cache.record_failure(source, &message);
+                Err(ServiceError::ExternalApiError(message))
+            }
+        }
+    }
 
-        let cache_entry = SpaceCache::new("apod".to_string(), json);
-        cache_entry
-            .validate()
+    /// Validate, persist and write a fresh entry through to the cache.
+    /// An unchanged payload only refreshes `last_seen_at` of the stored entry.
+    async fn store_entry(&self, cache_entry: SpaceCache) -> Result<SpaceCache> {
+        self.sources
+            .validate(&cache_entry)
             .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
 
-        self.repo
+        let write = self.repo
             .insert_cache_entry(&cache_entry)
             .await
             .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
-
-        // Cache in Redis if available (TTL: 1 hour)
-        if let Some(ref redis_repo) = self.redis_repo {
-            let _ = redis_repo.set_cache("apod", &json_str, Some(3600)).await;
+        if !write.changed {
+            debug!("{} payload unchanged since {}", cache_entry.source, write.entry.fetched_at);
         }
-
-        Ok(cache_entry)
+        self.write_through(write.entry).await
     }
 
-    async fn fetch_and_cache_neo_feed(&self, api_key: Option<&str>) -> Result<SpaceCache> {
-        let today = Utc::now().date_naive();
-        let start_date = today - chrono::Days::new(2);
-
-        let json = self.nasa_client
-            .fetch_neo_feed(&start_date.to_string(), &today.to_string(), api_key)
-            .await
-            .map_err(|e| ServiceError::ExternalApiError(format!("NEO API request failed: {}", e)))?;
-
-        let json_str = serde_json::to_string(&json).unwrap();
-
-        let cache_entry = SpaceCache::new("neo".to_string(), json);
-        cache_entry
-            .validate()
-            .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
-
-        self.repo
-            .insert_cache_entry(&cache_entry)
+    /// Bump `last_seen_at` of the stored entry of a source the upstream
+    /// reported unchanged; `None` when nothing is stored yet
+    async fn touch_entry(&self, source: &str) -> Result<Option<SpaceCache>> {
+        let touched = self.repo
+            .touch_latest_cache_entry(source)
             .await
             .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
-
-        // Cache in Redis if available (TTL: 1 hour)
-        if let Some(ref redis_repo) = self.redis_repo {
-            let _ = redis_repo.set_cache("neo", &json_str, Some(3600)).await;
+        match touched {
+            Some(entry) => {
+                debug!("{} not modified since {}", source, entry.fetched_at);
+                Ok(Some(self.write_through(entry).await?))
+            }
+            None => Ok(None),
         }
+    }
 
+    async fn write_through(&self, cache_entry: SpaceCache) -> Result<SpaceCache> {
+        let key = space_key(&cache_entry.source);
+        self.cache.put(&cache_entry.source, &key, &cache_entry).await;
+        self.local.insert(&key, cache_entry.clone());
         Ok(cache_entry)
     }
+}
 
-    async fn fetch_and_cache_donki_data(&self, api_key: Option<&str>) -> Result<Vec<SpaceCache>> {
-        let mut results = Vec::new();
-
-        // Fetch FLR data
-        if let Ok(flr) = self.fetch_donki_flr(api_key).await {
-            results.push(flr);
-        }
-
-        // Fetch CME data
-        if let Ok(cme) = self.fetch_donki_cme(api_key).await {
-            results.push(cme);
-        }
-
-        Ok(results)
+#[async_trait]
+impl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Send + Clone + 'static> CacheService for CacheServiceImpl<R> {
+    fn sources(&self) -> &SourceRegistry {
+        &self.sources
     }
 
-    async fn fetch_and_cache_spacex_next(&self) -> Result<SpaceCache> {
-        let json = self.spacex_client
-            .fetch_next_launch()
-            .await
-            .map_err(|e| ServiceError::ExternalApiError(format!("SpaceX API request failed: {}", e)))?;
-
-        let json_str = serde_json::to_string(&json).unwrap();
-
-        let cache_entry = SpaceCache::new("spacex".to_string(), json);
-        cache_entry
-            .validate()
-            .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
-
-        self.repo
-            .insert_cache_entry(&cache_entry)
-            .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
-
-        // Cache in Redis if available (TTL: 1 hour)
-        if let Some(ref redis_repo) = self.redis_repo {
-            let _ = redis_repo.set_cache("spacex", &json_str, Some(3600)).await;
+    async fn fetch_and_cache_source(&self, source: &str) -> Result<SpaceCache> {
+        let Some(space_source) = self.sources.get(source) else {
+            return Err(ServiceError::ValidationError(format!(
+                "Unknown source '{}'. Valid sources: {}", source, self.sources.names().join(", ")
+            )));
+        };
+
+        // Checked before drawing a key, which only the HTTP client gives back
+        self.check_recent_failure(source, space_source.title())?;
+        let api_key = match (&self.quota, space_source.quota_priority()) {
+            (Some(quota), Some(priority)) => Some(quota.acquire(priority).map_err(|deferred| {
+                ServiceError::ExternalApiError(format!("{} deferred: {}", space_source.title(), deferred))
+            })?),
+            _ => None,
+        };
+        let fetched = self
+            .call_upstream(source, space_source.title(), space_source.fetch(api_key.as_deref()))
+            .await?;
+
+        if !fetched.changed {
+            if let Some(entry) = self.touch_entry(source).await? {
+                return Ok(entry);
+            }
         }
-
-        Ok(cache_entry)
+        self.store_entry(SpaceCache::new(source.to_string(), fetched.value)).await
     }
 
     async fn get_latest_cache_entry(&self, source: &str) -> Result<Option<SpaceCache>> {
-        // Try Redis cache first
-        if let Some(ref redis_repo) = self.redis_repo {
-            if let Ok(Some(cached_data)) = redis_repo.get_cache(source).await {
-                if let Ok(json) = serde_json::from_str::<Value>(&cached_data) {
-                    let cache_entry = SpaceCache::new(source.to_string(), json);
-                    if cache_entry.validate().is_ok() {
-                        return Ok(Some(cache_entry));
-                    }
-                }
+        let key = space_key(source);
+        let (result, lookup) = self.local.get_or_load(&key, || async {
+            // Try Redis cache first
+            if let Some(cached) = self.cache.get::<SpaceCache>(source, &key).await {
+                return Ok(Some(cached));
             }
-        }
 
-        // Fallback to PostgreSQL
-        self.repo
-            .get_latest_cache_entry(source)
-            .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+            // Fallback to PostgreSQL and populate the cache
+            let latest = self.repo
+                .get_latest_cache_entry(source)
+                .await
+                .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+            if let Some(ref entry) = latest {
+                self.cache.put(source, &key, entry).await;
+            }
+            Ok(latest)
+        }).await;
+        self.cache.record_lookup(source, lookup);
+        result
     }
 
-    async fn refresh_multiple_sources(&self, sources: Vec<String>, api_key: Option<&str>) -> Result<Vec<String>> {
+    async fn refresh_multiple_sources(&self, sources: Vec<String>) -> Result<Vec<String>> {
         let mut refreshed = Vec::new();
 
         for source in sources {
-            let result = match source.to_lowercase().as_str() {
-                "apod" => {
-                    let _ = self.fetch_and_cache_apod(api_key).await;
-                    Some("apod".to_string())
-                }
-                "neo" => {
-                    let _ = self.fetch_and_cache_neo_feed(api_key).await;
-                    Some("neo".to_string())
-                }
-                "flr" => {
-                    let _ = self.fetch_donki_flr(api_key).await;
-                    Some("flr".to_string())
-                }
-                "cme" => {
-                    let _ = self.fetch_donki_cme(api_key).await;
-                    Some("cme".to_string())
-                }
-                "spacex" => {
-                    let _ = self.fetch_and_cache_spacex_next().await;
-                    Some("spacex".to_string())
-                }
-                _ => None,
-            };
-
-            if let Some(refreshed_source) = result {
-                refreshed.push(refreshed_source);
+            let source = source.to_lowercase();
+            if self.fetch_and_cache_source(&source).await.is_ok() {
+                refreshed.push(source);
             }
         }
 
         Ok(refreshed)
     }
 
+    async fn get_cached_source(&self, source: &str) -> Result<Option<CachedSource>> {
+        let Some(entry) = self.get_latest_cache_entry(source).await? else {
+            return Ok(None);
+        };
+
+        let freshness = self.cache.freshness(source, entry.last_seen_at);
+        if freshness.stale {
+            self.spawn_revalidation(source);
+        }
+        Ok(Some(CachedSource { entry, age_seconds: freshness.age_seconds, stale: freshness.stale }))
+    }
+
     async fn get_space_summary(&self) -> Result<SpaceSummary> {
-        let apod = self.get_latest_cache_entry("apod").await?;
-        let neo = self.get_latest_cache_entry("neo").await?;
-        let flr = self.get_latest_cache_entry("flr").await?;
-        let cme = self.get_latest_cache_entry("cme").await?;
-        let spacex = self.get_latest_cache_entry("spacex").await?;
-        let iss = self.repo
-            .get_latest_iss_data()
-            .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+        let mut sources = Vec::new();
+        for name in self.sources.names() {
+            sources.push((name, self.get_cached_source(name).await?));
+        }
+        let iss = match self.cache.get::<IssData>("iss", "iss:last").await {
+            Some(cached) => Some(cached),
+            None => self.repo
+                .get_latest_iss_data()
+                .await
+                .map_err(|e| ServiceError::RepositoryError(e.to_string()))?,
+        };
         let osdr_count = self.repo
             .count_osdr_items()
             .await
             .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
 
         Ok(SpaceSummary {
-            apod,
-            neo,
-            flr,
-            cme,
-            spacex,
+            sources,
             iss,
             osdr_count,
         })
     }
 
-    async fn store_space_cache(&self, source: String, payload: serde_json::Value) -> Result<()> {
-        let cache_entry = SpaceCache::new(source, payload);
-        cache_entry
-            .validate()
-            .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
-
+    async fn get_source_history(&self, source: &str, limit: i64) -> Result<Vec<SpaceCache>> {
         self.repo
-            .insert_cache_entry(&cache_entry)
+            .get_cache_entries(source, limit)
             .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+    }
 
+    async fn store_space_cache(&self, source: String, payload: serde_json::Value) -> Result<()> {
+        self.store_entry(SpaceCache::new(source, payload)).await?;
         Ok(())
     }
-}
-
-impl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Clone, N: NasaClient + Clone + Sync, S: SpaceXClient + Clone + Sync> CacheServiceImpl<R, N, S> {
-    /// Fetch DONKI FLR data
-    pub async fn fetch_donki_flr(&self, api_key: Option<&str>) -> Result<SpaceCache> {
-        let (from, to) = get_last_days_range(5);
-
-        let json = self.nasa_client
-            .fetch_donki_flr(&from, &to, api_key)
-            .await
-            .map_err(|e| ServiceError::ExternalApiError(format!("DONKI FLR request failed: {}", e)))?;
-
-        let cache_entry = SpaceCache::new("flr".to_string(), json.clone());
-        cache_entry
-            .validate()
-            .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
 
-        self.repo
-            .insert_cache_entry(&cache_entry)
-            .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
-
-        // Cache in Redis if available (TTL: 1 hour)
-        if let Some(ref redis_repo) = self.redis_repo {
-            let _ = redis_repo.set_cache("flr", &serde_json::to_string(&json).unwrap(), Some(3600)).await;
-        }
-
-        Ok(cache_entry)
+    fn get_cache_stats(&self) -> BTreeMap<String, CacheCounters> {
+        self.cache.stats()
     }
 
-    /// Fetch DONKI CME data
-    pub async fn fetch_donki_cme(&self, api_key: Option<&str>) -> Result<SpaceCache> {
-        let (from, to) = get_last_days_range(5);
-
-        let json = self.nasa_client
-            .fetch_donki_cme(&from, &to, api_key)
-            .await
-            .map_err(|e| ServiceError::ExternalApiError(format!("DONKI CME request failed: {}", e)))?;
-
-        let cache_entry = SpaceCache::new("cme".to_string(), json);
-        cache_entry
-            .validate()
-            .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
+    async fn list_cache_keys(&self, pattern: &str, limit: usize) -> Result<Vec<CacheKeyInfo>> {
+        self.cache.list_keys(pattern, limit).await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+    }
 
-        self.repo
-            .insert_cache_entry(&cache_entry)
-            .await
+    async fn purge_cache(&self, pattern: &str) -> Result<Vec<String>> {
+        let deleted = self.cache.purge(pattern).await
             .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
-
-        Ok(cache_entry)
+        info!("Purged {} cache keys matching {}", deleted.len(), pattern);
+        Ok(deleted)
     }
 }
 
+impl<R: CacheRepo + IssRepo + OsdrRepo + Sync + Send + Clone + 'static> CacheServiceImpl<R> {
+    /// Refresh a stale source in the background unless a refresh is already running
+    /// or the upstream failed recently
+    fn spawn_revalidation(&self, source: &str) {
+        if !self.cache.begin_revalidation(source) {
+            return;
+        }
 
+        let service = self.clone();
+        let source = source.to_string();
+        tokio::spawn(async move {
+            match service.fetch_and_cache_source(&source).await {
+                Ok(_) => info!("Revalidated stale {} cache entry", source),
+                Err(e) => warn!("Background revalidation of {} failed: {}", source, e),
+            }
+            service.cache.end_revalidation(&source);
+        });
+    }
+}
 
-/// Get date range for last N days
-fn get_last_days_range(days: i64) -> (String, String) {
-    let to = Utc::now().date_naive();
-    let from = to - chrono::Days::new(days as u64);
-    (from.to_string(), to.to_string())
+/// Redis key of the latest entry for a space_cache source
+fn space_key(source: &str) -> String {
+    format!("space:{}", source)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clients::{Fetched, NasaClient, QuotaPriority, SpaceXClient};
+    use std::time::Duration;
+    use serde_json::Value;
     use crate::repo::RepoError;
 
-    // Mock repository for testing
-    #[derive(Clone)]
-    struct MockCacheRepo;
+    fn registry<N: NasaClient + Clone + Send + Sync + 'static>(nasa: N) -> SourceRegistry {
+        SourceRegistry::new()
+            .register(ApodSource::new(nasa.clone()))
+            .register(NeoFeedSource::new(nasa.clone()))
+            .register(DonkiFlrSource::new(nasa.clone()))
+            .register(DonkiCmeSource::new(nasa))
+            .register(SpaceXNextSource::new(MockSpaceXClient))
+    }
+
+    // Mock repository for testing; remembers the last write to emulate touches
+    #[derive(Clone, Default)]
+    struct MockCacheRepo {
+        written: std::sync::Arc<std::sync::Mutex<Vec<SpaceCache>>>,
+    }
 
     #[async_trait]
     impl CacheRepo for MockCacheRepo {
-        async fn insert_cache_entry(&self, _entry: &SpaceCache) -> crate::repo::Result<i64> {
-            Ok(1)
+        async fn insert_cache_entry(&self, entry: &SpaceCache) -> crate::repo::Result<CacheWrite> {
+            let mut written = self.written.lock().unwrap();
+            if let Some(latest) = written.iter_mut().rev().find(|e| e.source == entry.source) {
+                if latest.payload == entry.payload {
+                    latest.last_seen_at = entry.last_seen_at;
+                    return Ok(CacheWrite { entry: latest.clone(), changed: false });
+                }
+            }
+            let mut entry = entry.clone();
+            entry.id = Some(written.len() as i64 + 1);
+            written.push(entry.clone());
+            Ok(CacheWrite { entry, changed: true })
         }
 
         async fn get_latest_cache_entry(&self, _source: &str) -> crate::repo::Result<Option<SpaceCache>> {
             Ok(None)
         }
 
+        async fn touch_latest_cache_entry(&self, source: &str) -> crate::repo::Result<Option<SpaceCache>> {
+            let mut written = self.written.lock().unwrap();
+            Ok(written.iter_mut().rev().find(|e| e.source == source).map(|latest| {
+                latest.last_seen_at = chrono::Utc::now();
+                latest.clone()
+            }))
+        }
+
         async fn get_cache_entries(&self, _source: &str, _limit: i64) -> crate::repo::Result<Vec<SpaceCache>> {
             Ok(vec![])
         }
@@ -342,6 +353,10 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_osdr_items_page(&self, _limit: i64, _offset: i64) -> crate::repo::Result<Vec<OsdrItem>> {
+            Ok(vec![])
+        }
+
         async fn get_osdr_item_by_id(&self, _dataset_id: &str) -> crate::repo::Result<Option<OsdrItem>> {
             Ok(None)
         }
@@ -357,24 +372,24 @@ mod tests {
 
     #[async_trait]
     impl NasaClient for MockNasaClient {
-        async fn fetch_osdr_datasets(&self) -> ClientResult<Value> {
-            Ok(serde_json::json!({}))
+        async fn fetch_osdr_datasets(&self) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
         }
 
-        async fn fetch_apod(&self, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!({}))
+        async fn fetch_apod(&self, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
         }
 
-        async fn fetch_neo_feed(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!({}))
+        async fn fetch_neo_feed(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
         }
 
-        async fn fetch_donki_flr(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!({}))
+        async fn fetch_donki_flr(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
         }
 
-        async fn fetch_donki_cme(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!({}))
+        async fn fetch_donki_cme(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
         }
     }
 
@@ -384,8 +399,8 @@ mod tests {
 
     #[async_trait]
     impl SpaceXClient for MockSpaceXClient {
-        async fn fetch_next_launch(&self) -> ClientResult<Value> {
-            Ok(serde_json::json!({}))
+        async fn fetch_next_launch(&self) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
         }
 
         async fn fetch_latest_launch(&self) -> ClientResult<Value> {
@@ -397,21 +412,221 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_get_last_days_range() {
-        let (from, to) = get_last_days_range(5);
-        assert!(!from.is_empty());
-        assert!(!to.is_empty());
-        assert!(from < to);
+    // NASA client whose APOD endpoint always fails, counting calls
+    #[derive(Clone, Default)]
+    struct FailingNasaClient {
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl NasaClient for FailingNasaClient {
+        async fn fetch_osdr_datasets(&self) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
+        }
+
+        async fn fetch_apod(&self, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(crate::clients::ClientError::HttpError("503 Service Unavailable".to_string()))
+        }
+
+        async fn fetch_neo_feed(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
+        }
+
+        async fn fetch_donki_flr(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
+        }
+
+        async fn fetch_donki_cme(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({})))
+        }
+    }
+
+    // NASA client whose server always answers 304 Not Modified
+    #[derive(Clone)]
+    struct NotModifiedNasaClient;
+
+    #[async_trait]
+    impl NasaClient for NotModifiedNasaClient {
+        async fn fetch_osdr_datasets(&self) -> ClientResult<Fetched> {
+            Ok(Fetched::unchanged(serde_json::json!([])))
+        }
+
+        async fn fetch_apod(&self, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::unchanged(serde_json::json!({"title": "M31"})))
+        }
+
+        async fn fetch_neo_feed(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::unchanged(serde_json::json!({})))
+        }
+
+        async fn fetch_donki_flr(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::unchanged(serde_json::json!([])))
+        }
+
+        async fn fetch_donki_cme(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::unchanged(serde_json::json!([])))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upstream_failures_are_negatively_cached() {
+        let client = FailingNasaClient::default();
+        let service = CacheServiceImpl::new(MockCacheRepo::default(), registry(client.clone()));
+
+        assert!(service.fetch_and_cache_source("apod").await.is_err());
+        let second = service.fetch_and_cache_source("apod").await.unwrap_err().to_string();
+        assert!(second.contains("skipped"));
+        assert_eq!(client.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let refreshed = service.refresh_multiple_sources(vec!["apod".to_string()]).await.unwrap();
+        assert!(refreshed.is_empty());
+        assert_eq!(client.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    /// Low priority DONKI backfill, which may wait for the next quota window
+    struct DonkiBackfillSource;
+
+    #[async_trait]
+    impl SpaceSource for DonkiBackfillSource {
+        fn name(&self) -> &'static str {
+            "flr_backfill"
+        }
+
+        fn title(&self) -> &'static str {
+            "DONKI FLR backfill"
+        }
+
+        async fn fetch(&self, api_key: Option<&str>) -> ClientResult<Fetched> {
+            MockNasaClient.fetch_donki_flr("2024-01-01", "2024-01-31", api_key).await
+        }
+
+        fn validate(&self, _payload: &Value) -> std::result::Result<(), DomainError> {
+            Ok(())
+        }
+
+        fn schedule(&self) -> Duration {
+            Duration::from_secs(86400)
+        }
+
+        fn ttl(&self) -> Duration {
+            Duration::from_secs(86400)
+        }
+
+        fn quota_priority(&self) -> Option<QuotaPriority> {
+            Some(QuotaPriority::Low)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_low_priority_sources_wait_for_quota() {
+        let quota = NasaQuota::new(vec!["KEY".to_string()]).with_reserves(0.1, 0.5);
+        quota.acquire(QuotaPriority::High).unwrap();
+        quota.observe("KEY", Some(100), 20);
+        let service = CacheServiceImpl::new(MockCacheRepo::default(), registry(MockNasaClient).register(DonkiBackfillSource))
+            .with_quota(quota.clone());
+
+        // Backfills may not dig into the reserved half of the quota
+        let err = service.fetch_and_cache_source("flr_backfill").await.unwrap_err();
+        assert!(matches!(err, ServiceError::ExternalApiError(_)));
+        assert!(err.to_string().contains("deferred"));
+        // Scheduled fetches may, and sources without a NASA key never ask
+        service.fetch_and_cache_source("neo").await.unwrap();
+        service.fetch_and_cache_source("apod").await.unwrap();
+        service.fetch_and_cache_source("spacex").await.unwrap();
+        assert_eq!(quota.snapshot()[0].calls, 3);
+    }
+
+    #[tokio::test]
+    async fn test_negatively_cached_sources_leave_the_quota_alone() {
+        let quota = NasaQuota::new(vec!["KEY".to_string()]);
+        quota.observe("KEY", Some(100), 50);
+        let service = CacheServiceImpl::new(MockCacheRepo::default(), registry(FailingNasaClient::default()))
+            .with_quota(quota.clone());
+        assert!(service.fetch_and_cache_source("apod").await.is_err());
+        let before = quota.snapshot();
+
+        for _ in 0..3 {
+            let err = service.fetch_and_cache_source("apod").await.unwrap_err();
+            assert!(err.to_string().contains("skipped"));
+        }
+        let after = quota.snapshot();
+        assert_eq!(after[0].calls, before[0].calls);
+        assert_eq!(after[0].remaining, before[0].remaining);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_sources_are_rejected() {
+        let service = CacheServiceImpl::new(MockCacheRepo::default(), registry(MockNasaClient));
+        let err = service.fetch_and_cache_source("hubble").await.unwrap_err();
+        assert!(matches!(err, ServiceError::ValidationError(_)));
+        assert!(service.store_space_cache("hubble".to_string(), serde_json::json!({})).await.is_err());
+        // DONKI feeds are arrays; the mock client returns an object
+        assert!(service.fetch_and_cache_source("flr").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_written_entries_are_served_from_local_tier() {
+        let service = CacheServiceImpl::new(MockCacheRepo::default(), registry(MockNasaClient));
+        service.store_space_cache("apod".to_string(), serde_json::json!({"title": "M31"})).await.unwrap();
+
+        // The mock repository has nothing, so this can only come from memory
+        let entry = service.get_latest_cache_entry("apod").await.unwrap().unwrap();
+        assert_eq!(entry.payload["title"], "M31");
+        assert_eq!(service.get_cache_stats()["apod"].local_hits, 1);
+
+        service.cache.invalidate_local("space:apod", &[]);
+        assert!(service.get_latest_cache_entry("apod").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_payloads_only_refresh_last_seen() {
+        let repo = MockCacheRepo::default();
+        let service = CacheServiceImpl::new(repo.clone(), registry(MockNasaClient));
+        service.store_space_cache("apod".to_string(), serde_json::json!({"title": "M31"})).await.unwrap();
+        repo.written.lock().unwrap()[0].fetched_at -= chrono::Duration::days(2);
+
+        service.store_space_cache("apod".to_string(), serde_json::json!({"title": "M31"})).await.unwrap();
+        assert_eq!(repo.written.lock().unwrap().len(), 1);
+
+        // Freshness follows the last fetch, not the last change
+        let cached = service.get_cached_source("apod").await.unwrap().unwrap();
+        assert_eq!(cached.entry.id, Some(1));
+        assert!(cached.entry.last_seen_at > cached.entry.fetched_at);
+        assert!(!cached.stale);
+
+        service.store_space_cache("apod".to_string(), serde_json::json!({"title": "M33"})).await.unwrap();
+        assert_eq!(repo.written.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_touches_the_stored_entry() {
+        let repo = MockCacheRepo::default();
+        let service = CacheServiceImpl::new(repo.clone(), registry(NotModifiedNasaClient));
+
+        // Nothing stored yet: the remembered body is written as usual
+        let first = service.fetch_and_cache_source("apod").await.unwrap();
+        assert_eq!(first.payload["title"], "M31");
+        repo.written.lock().unwrap()[0].payload = serde_json::json!({"title": "M33"});
+
+        // Afterwards a 304 only refreshes the latest row, whatever it holds
+        let touched = service.fetch_and_cache_source("apod").await.unwrap();
+        assert_eq!(repo.written.lock().unwrap().len(), 1);
+        assert_eq!(touched.payload["title"], "M33");
+        assert!(touched.last_seen_at >= first.last_seen_at);
+        let cached = service.get_latest_cache_entry("apod").await.unwrap().unwrap();
+        assert_eq!(cached.payload["title"], "M33");
     }
 
     #[tokio::test]
     async fn test_get_space_summary() {
-        let service = CacheServiceImpl::new(MockCacheRepo, MockNasaClient, MockSpaceXClient);
+        let service = CacheServiceImpl::new(MockCacheRepo::default(), registry(MockNasaClient));
         let result = service.get_space_summary().await;
         assert!(result.is_ok());
         let summary = result.unwrap();
         assert_eq!(summary.osdr_count, 0);
-        assert!(summary.apod.is_none());
+        let names: Vec<&str> = summary.sources.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["apod", "neo", "flr", "cme", "spacex"]);
+        assert!(summary.sources.iter().all(|(_, cached)| cached.is_none()));
     }
 }
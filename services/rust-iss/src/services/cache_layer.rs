@@ -0,0 +1,681 @@
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::config::CacheConfig;
+use crate::domain::CacheKeyInfo;
+use crate::repo::{RedisRepo, RedisRepos, RepoError, Result as RepoResult};
+use super::local_cache::{LocalCache, Lookup};
+
+/// Redis channel replicas use to tell each other to drop in-process cache state
+pub const INVALIDATION_CHANNEL: &str = "rust_iss:cache:invalidate";
+
+/// Sources caching several derived keys under `{source}:*`; every other
+/// source caches its latest entry under `space:{source}`
+pub const DERIVED_SOURCES: [&str; 2] = ["iss", "osdr"];
+
+/// Hit/miss counters for one cached source
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
+    pub errors: u64,
+    pub stale: u64,
+    pub upstream_skipped: u64,
+    pub local_hits: u64,
+    pub coalesced: u64,
+}
+
+/// Age of a cached entry relative to its source's freshness window
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct Freshness {
+    pub age_seconds: i64,
+    pub stale: bool,
+}
+
+/// Recent upstream failure remembered for negative caching
+#[derive(Debug, Clone)]
+struct UpstreamFailure {
+    at: Instant,
+    message: String,
+}
+
+type InvalidationHook = Box<dyn Fn(&str) + Send + Sync>;
+
+/// Redis read-through/write-through layer shared by the services.
+///
+/// Without Redis every read is a miss and writes are no-ops, so services
+/// fall back to Postgres transparently.
+#[derive(Clone)]
+pub struct CacheLayer {
+    redis: Option<Arc<dyn RedisRepo + Send + Sync>>,
+    default_ttl: u64,
+    source_ttls: Arc<HashMap<String, u64>>,
+    soft_ttl_ratio: f64,
+    source_soft_ttls: Arc<HashMap<String, u64>>,
+    negative_ttl: Duration,
+    counters: Arc<Mutex<BTreeMap<String, CacheCounters>>>,
+    failures: Arc<Mutex<HashMap<String, UpstreamFailure>>>,
+    revalidating: Arc<Mutex<HashSet<String>>>,
+    local_max_entries: usize,
+    local_ttl: Duration,
+    local_tiers: Arc<Mutex<Vec<InvalidationHook>>>,
+}
+
+impl Default for CacheLayer {
+    fn default() -> Self {
+        Self {
+            redis: None,
+            default_ttl: 3600,
+            source_ttls: Arc::new(HashMap::new()),
+            soft_ttl_ratio: 0.5,
+            source_soft_ttls: Arc::new(HashMap::new()),
+            negative_ttl: Duration::from_secs(60),
+            counters: Arc::new(Mutex::new(BTreeMap::new())),
+            failures: Arc::new(Mutex::new(HashMap::new())),
+            revalidating: Arc::new(Mutex::new(HashSet::new())),
+            local_max_entries: 256,
+            local_ttl: Duration::from_secs(15),
+            local_tiers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl CacheLayer {
+    pub fn new(redis: Option<Arc<dyn RedisRepo + Send + Sync>>, config: &CacheConfig) -> Self {
+        Self {
+            redis,
+            default_ttl: config.default_ttl,
+            source_ttls: Arc::new(config.source_ttls.clone()),
+            soft_ttl_ratio: config.soft_ttl_ratio,
+            source_soft_ttls: Arc::new(config.source_soft_ttls.clone()),
+            negative_ttl: Duration::from_secs(config.negative_ttl),
+            local_max_entries: config.local_max_entries,
+            local_ttl: Duration::from_secs(config.local_ttl),
+            ..Self::default()
+        }
+    }
+
+    /// Fill in TTLs for sources without an explicit `CACHE_TTL_<SOURCE>`
+    pub fn with_default_ttls(mut self, defaults: impl IntoIterator<Item = (String, u64)>) -> Self {
+        let mut ttls = (*self.source_ttls).clone();
+        for (source, ttl) in defaults {
+            ttls.entry(source).or_insert(ttl);
+        }
+        self.source_ttls = Arc::new(ttls);
+        self
+    }
+
+    /// In-process tier with the configured bounds, dropped together with
+    /// Redis keys on purge or remote invalidation
+    pub fn local_tier<V: Clone + Send + 'static>(&self) -> LocalCache<V> {
+        let local = LocalCache::new(self.local_max_entries, self.local_ttl);
+        if let Ok(mut tiers) = self.local_tiers.lock() {
+            let hook = local.clone();
+            tiers.push(Box::new(move |pattern| hook.invalidate_matching(pattern)));
+        }
+        local
+    }
+
+    /// Count a lookup answered by an in-process tier
+    pub fn record_lookup(&self, source: &str, lookup: Lookup) {
+        match lookup {
+            Lookup::Hit => self.record(source, |c| c.local_hits += 1),
+            Lookup::Coalesced => self.record(source, |c| c.coalesced += 1),
+            Lookup::Loaded => {}
+        }
+    }
+
+    /// TTL in seconds used for entries of `source`
+    pub fn ttl_for(&self, source: &str) -> u64 {
+        self.source_ttls.get(source).copied().unwrap_or(self.default_ttl)
+    }
+
+    /// Age in seconds after which entries of `source` are stale: its
+    /// `CACHE_SOFT_TTL_<SOURCE>` or a share of the hard TTL, never past it
+    pub fn soft_ttl_for(&self, source: &str) -> u64 {
+        let ttl = self.ttl_for(source);
+        self.source_soft_ttls
+            .get(source)
+            .copied()
+            .unwrap_or_else(|| (ttl as f64 * self.soft_ttl_ratio).ceil() as u64)
+            .min(ttl)
+    }
+
+    /// Compare an entry's age against the soft TTL of `source`
+    pub fn freshness(&self, source: &str, fetched_at: DateTime<Utc>) -> Freshness {
+        let age_seconds = (Utc::now() - fetched_at).num_seconds().max(0);
+        let stale = age_seconds as u64 > self.soft_ttl_for(source);
+        if stale {
+            self.record(source, |c| c.stale += 1);
+        }
+        Freshness { age_seconds, stale }
+    }
+
+    /// Error message of an upstream failure for `source` still within the negative TTL
+    pub fn recent_failure(&self, source: &str) -> Option<String> {
+        let mut failures = self.failures.lock().ok()?;
+        match failures.get(source) {
+            Some(f) if f.at.elapsed() < self.negative_ttl => {
+                let retry_in = (self.negative_ttl - f.at.elapsed()).as_secs();
+                let message = format!("{} (retry in {}s)", f.message, retry_in);
+                drop(failures);
+                self.record(source, |c| c.upstream_skipped += 1);
+                Some(message)
+            }
+            Some(_) => {
+                failures.remove(source);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Remember that fetching `source` failed so callers back off for a while
+    pub fn record_failure(&self, source: &str, message: &str) {
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.insert(source.to_string(), UpstreamFailure { at: Instant::now(), message: message.to_string() });
+        }
+    }
+
+    pub fn clear_failure(&self, source: &str) {
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.remove(source);
+        }
+    }
+
+    /// Claim the background revalidation of `source`; false if one is already running
+    pub fn begin_revalidation(&self, source: &str) -> bool {
+        self.revalidating.lock().map(|mut r| r.insert(source.to_string())).unwrap_or(false)
+    }
+
+    pub fn end_revalidation(&self, source: &str) {
+        if let Ok(mut revalidating) = self.revalidating.lock() {
+            revalidating.remove(source);
+        }
+    }
+
+    /// Read a cached value, counting a hit or miss for `source`
+    pub async fn get<T: DeserializeOwned>(&self, source: &str, key: &str) -> Option<T> {
+        let Some(ref redis) = self.redis else {
+            self.record(source, |c| c.misses += 1);
+            return None;
+        };
+
+        match redis.get_cache(key).await {
+            Ok(Some(raw)) => match serde_json::from_str(&raw) {
+                Ok(value) => {
+                    debug!("Cache hit for {}", key);
+                    self.record(source, |c| c.hits += 1);
+                    Some(value)
+                }
+                Err(e) => {
+                    warn!("Discarding undecodable cache entry {}: {}", key, e);
+                    self.record(source, |c| c.errors += 1);
+                    None
+                }
+            },
+            Ok(None) => {
+                self.record(source, |c| c.misses += 1);
+                None
+            }
+            Err(e) => {
+                warn!("Redis read failed for {}: {}", key, e);
+                self.record(source, |c| c.errors += 1);
+                None
+            }
+        }
+    }
+
+    /// Store a value with the TTL configured for `source`
+    pub async fn put<T: Serialize + Sync>(&self, source: &str, key: &str, value: &T) {
+        let Some(ref redis) = self.redis else {
+            return;
+        };
+        let raw = match serde_json::to_string(value) {
+            Ok(raw) => raw,
+            Err(e) => {
+                warn!("Failed to encode cache entry {}: {}", key, e);
+                return;
+            }
+        };
+        if let Err(e) = redis.set_cache(key, &raw, Some(self.ttl_for(source) as usize)).await {
+            warn!("Redis write failed for {}: {}", key, e);
+            self.record(source, |c| c.errors += 1);
+        }
+    }
+
+    /// Current generation of a source's derived entries (lists, trends).
+    ///
+    /// Derived keys embed the generation, so bumping it invalidates all of
+    /// them at once without scanning Redis.
+    pub async fn generation(&self, source: &str) -> String {
+        let Some(ref redis) = self.redis else {
+            return "0".to_string();
+        };
+        redis.get_cache(&generation_key(source)).await.ok().flatten().unwrap_or_else(|| "0".to_string())
+    }
+
+    /// Invalidate all derived entries of `source`
+    pub async fn bump_generation(&self, source: &str) {
+        let Some(ref redis) = self.redis else {
+            return;
+        };
+        let generation = Utc::now().timestamp_micros().to_string();
+        if let Err(e) = redis.set_cache(&generation_key(source), &generation, None).await {
+            warn!("Failed to bump cache generation for {}: {}", source, e);
+        }
+    }
+
+    /// Keys currently held in Redis matching `pattern`, with TTL and size.
+    ///
+    /// A plain key is checked with EXISTS first so that a miss skips the SCAN.
+    pub async fn list_keys(&self, pattern: &str, limit: usize) -> RepoResult<Vec<CacheKeyInfo>> {
+        let Some(ref redis) = self.redis else {
+            return Ok(Vec::new());
+        };
+        if is_plain_key(pattern) && !redis.exists_cache(pattern).await? {
+            return Ok(Vec::new());
+        }
+        redis.list_keys(pattern, limit).await
+    }
+
+    /// Delete Redis keys matching `pattern` and tell every replica to drop
+    /// its in-process state for the affected sources.
+    ///
+    /// Returns the deleted keys.
+    pub async fn purge(&self, pattern: &str) -> RepoResult<Vec<String>> {
+        let Some(ref redis) = self.redis else {
+            return Err(RepoError::DatabaseError("Redis is not configured".to_string()));
+        };
+        let deleted = if is_plain_key(pattern) {
+            // Single keys (e.g. `space:apod`) are deleted directly instead of scanning
+            if redis.exists_cache(pattern).await? {
+                redis.delete_cache(pattern).await?;
+                vec![pattern.to_string()]
+            } else {
+                Vec::new()
+            }
+        } else {
+            redis.delete_matching(pattern).await?
+        };
+
+        let mut sources: Vec<String> = deleted.iter().map(|k| key_source(k).to_string()).collect();
+        sources.sort();
+        sources.dedup();
+        let message = InvalidationMessage { pattern: pattern.to_string(), sources };
+        self.invalidate_local(&message.pattern, &message.sources);
+
+        let raw = serde_json::to_string(&message).unwrap_or_default();
+        if let Err(e) = redis.publish(INVALIDATION_CHANNEL, &raw).await {
+            warn!("Failed to broadcast cache invalidation for {}: {}", pattern, e);
+        }
+        Ok(deleted)
+    }
+
+    /// Drop in-process state (local tiers, negative cache, revalidation
+    /// claims) for `sources` and for any source whose keys match `pattern`
+    pub fn invalidate_local(&self, pattern: &str, sources: &[String]) {
+        if let Ok(tiers) = self.local_tiers.lock() {
+            for invalidate in tiers.iter() {
+                invalidate(pattern);
+            }
+        }
+        let affected = |source: &String| {
+            sources.contains(source) || glob_match(pattern, &source_pattern(source).replace('*', ""))
+        };
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.retain(|source, _| !affected(source));
+        }
+        if let Ok(mut revalidating) = self.revalidating.lock() {
+            revalidating.retain(|source| !affected(source));
+        }
+        debug!("Invalidated in-process cache state for pattern {}", pattern);
+    }
+
+    /// Apply invalidations published by other replicas until `token` is cancelled.
+    ///
+    /// Reconnects with a short delay if the subscription drops.
+    pub async fn listen_for_invalidations(&self, redis: RedisRepos, token: CancellationToken) {
+        loop {
+            let mut pubsub = tokio::select! {
+                result = redis.subscribe(INVALIDATION_CHANNEL) => match result {
+                    Ok(pubsub) => pubsub,
+                    Err(e) => {
+                        warn!("Cache invalidation subscription failed: {}", e);
+                        tokio::select! {
+                            _ = tokio::time::sleep(Duration::from_secs(5)) => continue,
+                            _ = token.cancelled() => return,
+                        }
+                    }
+                },
+                _ = token.cancelled() => return,
+            };
+            info!("Listening for cache invalidations on {}", INVALIDATION_CHANNEL);
+
+            let mut messages = pubsub.on_message();
+            loop {
+                tokio::select! {
+                    msg = messages.next() => {
+                        let Some(msg) = msg else {
+                            warn!("Cache invalidation subscription closed, reconnecting");
+                            break;
+                        };
+                        let payload: String = msg.get_payload().unwrap_or_default();
+                        match serde_json::from_str::<InvalidationMessage>(&payload) {
+                            Ok(m) => self.invalidate_local(&m.pattern, &m.sources),
+                            Err(e) => warn!("Ignoring malformed cache invalidation {:?}: {}", payload, e),
+                        }
+                    }
+                    _ = token.cancelled() => {
+                        info!("Cache invalidation listener shutting down");
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Snapshot of hit/miss counters per source
+    pub fn stats(&self) -> BTreeMap<String, CacheCounters> {
+        self.counters.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    fn record(&self, source: &str, update: impl FnOnce(&mut CacheCounters)) {
+        if let Ok(mut counters) = self.counters.lock() {
+            update(counters.entry(source.to_string()).or_default());
+        }
+    }
+}
+
+fn generation_key(source: &str) -> String {
+    format!("{}:generation", source)
+}
+
+/// Broadcast on [`INVALIDATION_CHANNEL`] after a purge
+#[derive(Debug, Serialize, Deserialize)]
+struct InvalidationMessage {
+    pattern: String,
+    sources: Vec<String>,
+}
+
+/// Redis key pattern covering every entry cached for `source`
+pub fn source_pattern(source: &str) -> String {
+    if DERIVED_SOURCES.contains(&source) {
+        format!("{}:*", source)
+    } else {
+        format!("space:{}", source)
+    }
+}
+
+/// Source a cache key belongs to (`space:apod` -> `apod`, `osdr:list:0:1:20` -> `osdr`)
+fn key_source(key: &str) -> &str {
+    let mut parts = key.split(':');
+    match (parts.next(), parts.next()) {
+        (Some("space"), Some(source)) => source,
+        (Some(source), _) => source,
+        _ => key,
+    }
+}
+
+/// Whether `pattern` names a single key rather than a Redis glob
+fn is_plain_key(pattern: &str) -> bool {
+    !pattern.contains(['*', '?', '[', '\\'])
+}
+
+/// Redis-style glob match supporting `*` and `?`
+pub(super) fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut pi, mut ti) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star, matched)) = backtrack {
+            pi = star + 1;
+            ti = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct MockRedisRepo {
+        entries: Mutex<HashMap<String, String>>,
+        published: Mutex<Vec<(String, String)>>,
+        scans: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl RedisRepo for MockRedisRepo {
+        async fn set_cache(&self, key: &str, value: &str, _ttl_seconds: Option<usize>) -> crate::repo::Result<()> {
+            self.entries.lock().unwrap().insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        async fn get_cache(&self, key: &str) -> crate::repo::Result<Option<String>> {
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+
+        async fn delete_cache(&self, key: &str) -> crate::repo::Result<()> {
+            self.entries.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn exists_cache(&self, key: &str) -> crate::repo::Result<bool> {
+            Ok(self.entries.lock().unwrap().contains_key(key))
+        }
+
+        async fn list_keys(&self, pattern: &str, limit: usize) -> crate::repo::Result<Vec<CacheKeyInfo>> {
+            *self.scans.lock().unwrap() += 1;
+            let entries = self.entries.lock().unwrap();
+            let mut keys: Vec<CacheKeyInfo> = entries.iter()
+                .filter(|(k, _)| glob_match(pattern, k))
+                .map(|(k, v)| CacheKeyInfo { key: k.clone(), ttl_seconds: None, size_bytes: Some(v.len() as i64) })
+                .collect();
+            keys.sort_by(|a, b| a.key.cmp(&b.key));
+            keys.truncate(limit);
+            Ok(keys)
+        }
+
+        async fn delete_matching(&self, pattern: &str) -> crate::repo::Result<Vec<String>> {
+            *self.scans.lock().unwrap() += 1;
+            let mut entries = self.entries.lock().unwrap();
+            let keys: Vec<String> = entries.keys().filter(|k| glob_match(pattern, k)).cloned().collect();
+            for key in &keys {
+                entries.remove(key);
+            }
+            Ok(keys)
+        }
+
+        async fn publish(&self, channel: &str, message: &str) -> crate::repo::Result<()> {
+            self.published.lock().unwrap().push((channel.to_string(), message.to_string()));
+            Ok(())
+        }
+    }
+
+    fn config() -> CacheConfig {
+        CacheConfig {
+            default_ttl: 3600,
+            source_ttls: HashMap::from([("iss".to_string(), 60)]),
+            soft_ttl_ratio: 0.5,
+            source_soft_ttls: HashMap::new(),
+            negative_ttl: 60,
+            local_max_entries: 16,
+            local_ttl: 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_through_counts_hits_and_misses() {
+        let cache = CacheLayer::new(Some(Arc::new(MockRedisRepo::default())), &config());
+        assert_eq!(cache.get::<Vec<i32>>("iss", "iss:last").await, None);
+
+        cache.put("iss", "iss:last", &vec![1, 2, 3]).await;
+        assert_eq!(cache.get::<Vec<i32>>("iss", "iss:last").await, Some(vec![1, 2, 3]));
+
+        let stats = cache.stats();
+        assert_eq!(stats["iss"], CacheCounters { hits: 1, misses: 1, ..Default::default() });
+        assert_eq!(cache.ttl_for("iss"), 60);
+        assert_eq!(cache.ttl_for("apod"), 3600);
+    }
+
+    #[tokio::test]
+    async fn test_generation_bump_changes_keys() {
+        let cache = CacheLayer::new(Some(Arc::new(MockRedisRepo::default())), &config());
+        let before = cache.generation("osdr").await;
+        cache.bump_generation("osdr").await;
+        assert_ne!(cache.generation("osdr").await, before);
+    }
+
+    #[test]
+    fn test_freshness_uses_source_ttl() {
+        let cache = CacheLayer::new(None, &config());
+        let fresh = cache.freshness("iss", Utc::now() - chrono::Duration::seconds(30));
+        assert!(!fresh.stale);
+        let stale = cache.freshness("iss", Utc::now() - chrono::Duration::seconds(90));
+        assert!(stale.stale);
+        assert!(stale.age_seconds >= 90);
+        assert_eq!(cache.stats()["iss"].stale, 1);
+    }
+
+    #[test]
+    fn test_entries_go_stale_before_redis_drops_them() {
+        let mut config = config();
+        config.source_soft_ttls.insert("apod".to_string(), 600);
+        config.source_soft_ttls.insert("osdr".to_string(), 99_999);
+        let cache = CacheLayer::new(None, &config);
+        // Half of the 60s hard TTL by default
+        assert_eq!(cache.soft_ttl_for("iss"), 30);
+        assert!(cache.freshness("iss", Utc::now() - chrono::Duration::seconds(45)).stale);
+        assert_eq!(cache.soft_ttl_for("apod"), 600);
+        assert!(!cache.freshness("apod", Utc::now() - chrono::Duration::seconds(500)).stale);
+        // Never later than the hard TTL
+        assert_eq!(cache.soft_ttl_for("osdr"), cache.ttl_for("osdr"));
+    }
+
+    #[test]
+    fn test_negative_caching_and_revalidation_claims() {
+        let cache = CacheLayer::new(None, &config());
+        assert!(cache.recent_failure("apod").is_none());
+        cache.record_failure("apod", "APOD API request failed");
+        assert!(cache.recent_failure("apod").unwrap().starts_with("APOD API request failed"));
+        cache.clear_failure("apod");
+        assert!(cache.recent_failure("apod").is_none());
+
+        assert!(cache.begin_revalidation("apod"));
+        assert!(!cache.begin_revalidation("apod"));
+        cache.end_revalidation("apod");
+        assert!(cache.begin_revalidation("apod"));
+    }
+
+    #[test]
+    fn test_glob_match_and_key_sources() {
+        assert!(glob_match("space:*", "space:apod"));
+        assert!(glob_match("osdr:list:*:1:?0", "osdr:list:123:1:20"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("iss:*", "space:iss"));
+        assert!(!glob_match("space:apo", "space:apod"));
+        assert_eq!(key_source("space:apod"), "apod");
+        assert_eq!(key_source("osdr:list:0:1:20"), "osdr");
+        assert_eq!(source_pattern("neo"), "space:neo");
+        assert_eq!(source_pattern("iss"), "iss:*");
+    }
+
+    #[tokio::test]
+    async fn test_purge_deletes_keys_and_broadcasts() {
+        let redis = Arc::new(MockRedisRepo::default());
+        let cache = CacheLayer::new(Some(redis.clone()), &config());
+        cache.put("apod", "space:apod", &1).await;
+        cache.put("neo", "space:neo", &2).await;
+        cache.put("iss", "iss:last", &3).await;
+        cache.record_failure("apod", "APOD API request failed");
+        cache.record_failure("iss", "ISS API request failed");
+
+        let listed = cache.list_keys("space:*", 10).await.unwrap();
+        assert_eq!(listed.iter().map(|k| k.key.as_str()).collect::<Vec<_>>(), vec!["space:apod", "space:neo"]);
+
+        let deleted = cache.purge(&source_pattern("apod")).await.unwrap();
+        assert_eq!(deleted, vec!["space:apod".to_string()]);
+        assert_eq!(cache.get::<i32>("apod", "space:apod").await, None);
+        assert_eq!(cache.get::<i32>("neo", "space:neo").await, Some(2));
+        assert!(cache.recent_failure("apod").is_none());
+        assert!(cache.recent_failure("iss").is_some());
+
+        let published = redis.published.lock().unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].0, INVALIDATION_CHANNEL);
+        let message: InvalidationMessage = serde_json::from_str(&published[0].1).unwrap();
+        assert_eq!(message.sources, vec!["apod".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_plain_keys_skip_scan() {
+        let redis = Arc::new(MockRedisRepo::default());
+        let cache = CacheLayer::new(Some(redis.clone()), &config());
+        cache.put("apod", "space:apod", &1).await;
+        cache.put("iss", "iss:last", &2).await;
+
+        assert!(cache.list_keys("space:neo", 10).await.unwrap().is_empty());
+        assert!(cache.purge("space:neo").await.unwrap().is_empty());
+        assert_eq!(cache.purge("space:apod").await.unwrap(), vec!["space:apod".to_string()]);
+        assert_eq!(cache.get::<i32>("apod", "space:apod").await, None);
+        assert_eq!(*redis.scans.lock().unwrap(), 0);
+
+        assert_eq!(cache.purge("iss:*").await.unwrap(), vec!["iss:last".to_string()]);
+        assert_eq!(*redis.scans.lock().unwrap(), 1);
+        assert!(is_plain_key("osdr:list:0:1:20"));
+        assert!(!is_plain_key("space:[an]*"));
+    }
+
+    #[test]
+    fn test_remote_invalidation_clears_matching_sources() {
+        let cache = CacheLayer::new(None, &config());
+        cache.record_failure("osdr", "OSDR request failed");
+        cache.record_failure("cme", "DONKI request failed");
+        assert!(cache.begin_revalidation("cme"));
+
+        // Another replica purged a pattern whose keys no longer exist here
+        cache.invalidate_local("space:*", &[]);
+        assert!(cache.recent_failure("cme").is_none());
+        assert!(cache.begin_revalidation("cme"));
+        assert!(cache.recent_failure("osdr").is_some());
+    }
+
+    #[test]
+    fn test_invalidation_reaches_local_tiers() {
+        let cache = CacheLayer::new(None, &config());
+        let local = cache.local_tier::<i32>();
+        local.insert("space:apod", 1);
+        local.insert("iss:last", 2);
+
+        cache.invalidate_local("space:apod", &["apod".to_string()]);
+        assert_eq!(local.get("space:apod"), None);
+        assert_eq!(local.get("iss:last"), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_without_redis_everything_misses() {
+        let cache = CacheLayer::default();
+        cache.put("apod", "space:apod", &1).await;
+        assert_eq!(cache.get::<i32>("apod", "space:apod").await, None);
+        assert_eq!(cache.stats()["apod"].misses, 1);
+    }
+}
@@ -0,0 +1,672 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::f64::consts::FRAC_PI_2;
+
+use crate::config::ObserverLocation;
+use crate::domain::*;
+use crate::services::IssPoint;
+
+/// Domain used in the right-hand side of generated UIDs
+const UID_DOMAIN: &str = "rust-iss";
+
+/// RFC 5545 recommends folding content lines longer than 75 octets
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Kind of calendar event, used for filtering and categorisation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarEventKind {
+    Launch,
+    SolarFlare,
+    Cme,
+    /// Predicted ISS pass over a configured observer
+    IssPass,
+}
+
+impl CalendarEventKind {
+    /// All kinds, in the order they are rendered
+    pub const ALL: [CalendarEventKind; 4] = [
+        CalendarEventKind::Launch,
+        CalendarEventKind::SolarFlare,
+        CalendarEventKind::Cme,
+        CalendarEventKind::IssPass,
+    ];
+
+    /// Parse a kind from its query parameter name
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "launch" | "launches" | "spacex" => Some(CalendarEventKind::Launch),
+            "flr" | "flare" | "flares" => Some(CalendarEventKind::SolarFlare),
+            "cme" | "cmes" => Some(CalendarEventKind::Cme),
+            "iss" | "pass" | "passes" | "iss_pass" => Some(CalendarEventKind::IssPass),
+            _ => None,
+        }
+    }
+
+    /// Value used for the CATEGORIES property
+    fn category(&self) -> &'static str {
+        match self {
+            CalendarEventKind::Launch => "LAUNCH",
+            CalendarEventKind::SolarFlare => "SOLAR FLARE",
+            CalendarEventKind::Cme => "CME",
+            CalendarEventKind::IssPass => "ISS PASS",
+        }
+    }
+}
+
+/// Single calendar event built from cached space data or ISS pass predictions
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub kind: CalendarEventKind,
+    pub summary: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub starts_at: Timestamp,
+    pub ends_at: Option<Timestamp>,
+    pub all_day: bool,
+    pub last_modified: Timestamp,
+}
+
+/// Filter applied to calendar events before rendering
+#[derive(Debug, Clone)]
+pub struct CalendarFilter {
+    pub kinds: Vec<CalendarEventKind>,
+    pub from: Option<Timestamp>,
+    pub to: Option<Timestamp>,
+}
+
+impl Default for CalendarFilter {
+    fn default() -> Self {
+        Self {
+            kinds: CalendarEventKind::ALL.to_vec(),
+            from: None,
+            to: None,
+        }
+    }
+}
+
+impl CalendarFilter {
+    /// Check whether an event passes the filter
+    pub fn matches(&self, event: &CalendarEvent) -> bool {
+        if !self.kinds.contains(&event.kind) {
+            return false;
+        }
+        let ends_at = event.ends_at.unwrap_or(event.starts_at);
+        if let Some(from) = self.from {
+            if ends_at < from {
+                return false;
+            }
+        }
+        if let Some(to) = self.to {
+            if event.starts_at > to {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Build launch events from a cached SpaceX payload (single launch or list)
+pub fn launch_events(entry: &SpaceCache) -> Vec<CalendarEvent> {
+    payload_items(&entry.payload)
+        .filter_map(|launch| {
+            let id = launch.get("id").and_then(|v| v.as_str())?;
+            let starts_at = launch.get("date_utc").and_then(|v| v.as_str()).and_then(parse_timestamp)?;
+            let name = launch.get("name").and_then(|v| v.as_str()).unwrap_or("SpaceX launch");
+            let precision = launch.get("date_precision").and_then(|v| v.as_str()).unwrap_or("hour");
+
+            let mut description = Vec::new();
+            if let Some(flight) = launch.get("flight_number").and_then(|v| v.as_i64()) {
+                description.push(format!("Flight #{}", flight));
+            }
+            if precision != "hour" {
+                description.push(format!("Date precision: {}", precision));
+            }
+            if let Some(details) = launch.get("details").and_then(|v| v.as_str()) {
+                description.push(details.to_string());
+            }
+
+            let url = launch
+                .pointer("/links/webcast")
+                .or_else(|| launch.pointer("/links/wikipedia"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
+            Some(CalendarEvent {
+                uid: format!("spacex-launch-{}@{}", id, UID_DOMAIN),
+                kind: CalendarEventKind::Launch,
+                summary: format!("SpaceX launch: {}", name),
+                description: join_description(description),
+                url,
+                starts_at,
+                ends_at: if precision == "hour" { Some(starts_at + Duration::hours(1)) } else { None },
+                all_day: precision != "hour",
+                last_modified: entry.fetched_at,
+            })
+        })
+        .collect()
+}
+
+/// Build solar flare events from a cached DONKI FLR payload
+pub fn flare_events(entry: &SpaceCache) -> Vec<CalendarEvent> {
+    payload_items(&entry.payload)
+        .filter_map(|flare| {
+            let id = flare.get("flrID").and_then(|v| v.as_str())?;
+            let starts_at = flare.get("beginTime").and_then(|v| v.as_str()).and_then(parse_timestamp)?;
+            let ends_at = flare.get("endTime").and_then(|v| v.as_str()).and_then(parse_timestamp);
+            let class_type = flare.get("classType").and_then(|v| v.as_str()).unwrap_or("unknown");
+
+            let mut description = Vec::new();
+            if let Some(peak) = flare.get("peakTime").and_then(|v| v.as_str()) {
+                description.push(format!("Peak: {}", peak));
+            }
+            if let Some(location) = flare.get("sourceLocation").and_then(|v| v.as_str()) {
+                description.push(format!("Source location: {}", location));
+            }
+            if let Some(region) = flare.get("activeRegionNum").and_then(|v| v.as_i64()) {
+                description.push(format!("Active region: {}", region));
+            }
+
+            Some(CalendarEvent {
+                uid: format!("donki-flr-{}@{}", id, UID_DOMAIN),
+                kind: CalendarEventKind::SolarFlare,
+                summary: format!("Solar flare {}", class_type),
+                description: join_description(description),
+                url: flare.get("link").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                starts_at,
+                ends_at,
+                all_day: false,
+                last_modified: entry.fetched_at,
+            })
+        })
+        .collect()
+}
+
+/// Build coronal mass ejection events from a cached DONKI CME payload
+pub fn cme_events(entry: &SpaceCache) -> Vec<CalendarEvent> {
+    payload_items(&entry.payload)
+        .filter_map(|cme| {
+            let id = cme.get("activityID").and_then(|v| v.as_str())?;
+            let starts_at = cme.get("startTime").and_then(|v| v.as_str()).and_then(parse_timestamp)?;
+
+            let mut description = Vec::new();
+            if let Some(location) = cme.get("sourceLocation").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+                description.push(format!("Source location: {}", location));
+            }
+            if let Some(note) = cme.get("note").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+                description.push(note.to_string());
+            }
+
+            Some(CalendarEvent {
+                uid: format!("donki-cme-{}@{}", id, UID_DOMAIN),
+                kind: CalendarEventKind::Cme,
+                summary: "Coronal mass ejection".to_string(),
+                description: join_description(description),
+                url: cme.get("link").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                starts_at,
+                ends_at: None,
+                all_day: false,
+                last_modified: entry.fetched_at,
+            })
+        })
+        .collect()
+}
+
+/// Observers see a pass once the ISS climbs this far above their horizon
+const PASS_MIN_ELEVATION_DEG: f64 = 10.0;
+
+/// How far past the latest fix passes are predicted; the circular-orbit
+/// model drifts by minutes per day, so longer horizons are not useful
+const PASS_HORIZON_HOURS: i64 = 48;
+
+/// Step of the elevation scan
+const PASS_STEP_SECONDS: i64 = 20;
+
+/// Fixed period used to number ISS revolutions since the Unix epoch. Pass
+/// UIDs carry the revolution number, which unlike the pass time does not
+/// move when a newer fix shifts the prediction by a few minutes.
+const ISS_NOMINAL_PERIOD_SECONDS: f64 = 5_570.0;
+
+const ISS_INCLINATION_DEG: f64 = 51.64;
+const ISS_DEFAULT_ALTITUDE_KM: f64 = 420.0;
+const EARTH_RADIUS_KM: f64 = 6371.0;
+/// Standard gravitational parameter of the Earth, km^3/s^2
+const EARTH_MU: f64 = 398_600.441_8;
+/// Sidereal rotation rate of the Earth, rad/s
+const EARTH_ROTATION: f64 = 7.292_115e-5;
+/// J2 regression of the ISS ascending node, about 5 degrees per day westward
+const ISS_NODAL_REGRESSION_DEG_PER_DAY: f64 = -5.0;
+
+/// Circular orbit through an ISS fix, precise enough for pass times
+/// to within a few minutes over the prediction horizon
+struct IssOrbit {
+    epoch: Timestamp,
+    radius_km: f64,
+    inclination: f64,
+    /// Argument of latitude at the epoch
+    u0: f64,
+    /// Longitude of the ascending node at the epoch, Earth-fixed
+    node0: f64,
+    mean_motion: f64,
+    node_rate: f64,
+}
+
+impl IssOrbit {
+    /// Fit an orbit to the two latest fixes; the earlier one only gives
+    /// the direction of travel (northbound or southbound)
+    fn from_fixes(previous: &IssPoint, latest: &IssPoint) -> Option<Self> {
+        if latest.at <= previous.at || latest.lat == previous.lat {
+            return None;
+        }
+        let inclination = ISS_INCLINATION_DEG.to_radians();
+        let lat = latest.lat.to_radians();
+        let mut u0 = (lat.sin() / inclination.sin()).clamp(-1.0, 1.0).asin();
+        if latest.lat < previous.lat {
+            u0 = std::f64::consts::PI - u0;
+        }
+        let node0 = latest.lon.to_radians() - (inclination.cos() * u0.sin()).atan2(u0.cos());
+        let altitude = latest.altitude.filter(|a| *a > 0.0).unwrap_or(ISS_DEFAULT_ALTITUDE_KM);
+        let radius_km = EARTH_RADIUS_KM + altitude;
+        Some(Self {
+            epoch: latest.at,
+            radius_km,
+            inclination,
+            u0,
+            node0,
+            mean_motion: (EARTH_MU / radius_km.powi(3)).sqrt(),
+            node_rate: ISS_NODAL_REGRESSION_DEG_PER_DAY.to_radians() / 86_400.0 - EARTH_ROTATION,
+        })
+    }
+
+    /// Revolution the ISS is on at `at`, counted from the Unix epoch.
+    /// Revolutions start at argument of latitude `start` rather than at the
+    /// ascending node, so callers can put the boundary where passes never are.
+    fn revolution(&self, at: Timestamp, start: f64) -> i64 {
+        let tau = std::f64::consts::TAU;
+        let u0 = self.u0.rem_euclid(tau);
+        // Ascending nodes sit one real period apart, so numbering the last one
+        // with a fixed period gives every fix the same count; only the node
+        // time estimate (seconds off at most) feeds the rounding
+        let node_at = self.epoch.timestamp_millis() as f64 / 1000.0 - u0 / self.mean_motion;
+        let node = (node_at / ISS_NOMINAL_PERIOD_SECONDS).round() as i64;
+        let dt = (at - self.epoch).num_milliseconds() as f64 / 1000.0;
+        node + ((u0 + self.mean_motion * dt - start) / tau).floor() as i64
+    }
+
+    /// Sub-satellite latitude and longitude in radians
+    fn ground_point(&self, at: Timestamp) -> (f64, f64) {
+        let dt = (at - self.epoch).num_milliseconds() as f64 / 1000.0;
+        let u = self.u0 + self.mean_motion * dt;
+        let lat = (self.inclination.sin() * u.sin()).asin();
+        let lon = self.node0 + self.node_rate * dt + (self.inclination.cos() * u.sin()).atan2(u.cos());
+        (lat, lon)
+    }
+
+    /// Elevation of the ISS above an observer's horizon, in degrees
+    fn elevation_from(&self, observer: &ObserverLocation, at: Timestamp) -> f64 {
+        let (lat, lon) = self.ground_point(at);
+        let up = unit_vector(observer.latitude.to_radians(), observer.longitude.to_radians());
+        let station = up.map(|c| c * (EARTH_RADIUS_KM + observer.elevation / 1000.0));
+        let iss = unit_vector(lat, lon).map(|c| c * self.radius_km);
+        let range = [iss[0] - station[0], iss[1] - station[1], iss[2] - station[2]];
+        let distance = range.iter().map(|c| c * c).sum::<f64>().sqrt();
+        let height = range.iter().zip(up).map(|(r, u)| r * u).sum::<f64>();
+        (height / distance).asin().to_degrees()
+    }
+}
+
+fn unit_vector(lat: f64, lon: f64) -> [f64; 3] {
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Predict ISS passes over each observer from the latest ISS fixes
+/// (oldest first, as returned by `get_iss_trend_points`)
+pub fn iss_pass_events(track: &[IssPoint], observers: &[ObserverLocation]) -> Vec<CalendarEvent> {
+    let [.., previous, latest] = track else {
+        return Vec::new();
+    };
+    let Some(orbit) = IssOrbit::from_fixes(previous, latest) else {
+        return Vec::new();
+    };
+
+    let step = Duration::seconds(PASS_STEP_SECONDS);
+    let until = orbit.epoch + Duration::hours(PASS_HORIZON_HOURS);
+    let mut events = Vec::new();
+    for observer in observers {
+        // (rise, culmination, max elevation) of the pass in progress
+        let mut pass: Option<(Timestamp, Timestamp, f64)> = None;
+        let mut at = orbit.epoch;
+        while at <= until {
+            let elevation = orbit.elevation_from(observer, at);
+            if elevation < PASS_MIN_ELEVATION_DEG {
+                if let Some((rise, culmination, max)) = pass.take() {
+                    // Northern observers never see the ISS near its southernmost
+                    // point and vice versa, so revolutions are split there
+                    let apex = if observer.latitude >= 0.0 { -FRAC_PI_2 } else { FRAC_PI_2 };
+                    let revolution = orbit.revolution(culmination, apex);
+                    events.push(iss_pass_event(observer, revolution, rise, culmination, at, max, orbit.epoch));
+                }
+            } else if let Some((_, culmination, max)) = pass.as_mut() {
+                if elevation > *max {
+                    *culmination = at;
+                    *max = elevation;
+                }
+            } else {
+                pass = Some((at, at, elevation));
+            }
+            at += step;
+        }
+    }
+    events
+}
+
+fn iss_pass_event(
+    observer: &ObserverLocation,
+    revolution: i64,
+    rise: Timestamp,
+    culmination: Timestamp,
+    set: Timestamp,
+    max_elevation: f64,
+    predicted_at: Timestamp,
+) -> CalendarEvent {
+    let slug: String = observer
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    CalendarEvent {
+        uid: format!("iss-pass-{}-{}@{}", slug, revolution, UID_DOMAIN),
+        kind: CalendarEventKind::IssPass,
+        summary: format!("ISS pass over {}, max {:.0}°", observer.name, max_elevation),
+        description: join_description(vec![
+            format!("Rise: {}", rise.format("%H:%M:%SZ")),
+            format!("Max elevation {:.0}° at {}", max_elevation, culmination.format("%H:%M:%SZ")),
+            format!("Set: {}", set.format("%H:%M:%SZ")),
+            format!("Predicted from the ISS position at {}", predicted_at.format("%Y-%m-%d %H:%M:%SZ")),
+        ]),
+        url: None,
+        starts_at: rise,
+        ends_at: Some(set),
+        all_day: false,
+        last_modified: predicted_at,
+    }
+}
+
+/// Render events as an RFC 5545 VCALENDAR document
+pub fn render_ics(events: &[CalendarEvent], generated_at: Timestamp) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Cassiopeia//rust_iss//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Space events".to_string(),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", format_utc(generated_at)));
+        lines.push(format!("LAST-MODIFIED:{}", format_utc(event.last_modified)));
+        if event.all_day {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", format_date(event.starts_at.date_naive())));
+        } else {
+            lines.push(format!("DTSTART:{}", format_utc(event.starts_at)));
+            if let Some(ends_at) = event.ends_at.filter(|end| *end > event.starts_at) {
+                lines.push(format!("DTEND:{}", format_utc(ends_at)));
+            }
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(ref description) = event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(ref url) = event.url {
+            lines.push(format!("URL:{}", url));
+        }
+        lines.push(format!("CATEGORIES:{}", event.kind.category()));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in lines {
+        out.push_str(&fold_line(&line));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Iterate over payload items, accepting both a single object and an array
+fn payload_items(payload: &Value) -> Box<dyn Iterator<Item = &Value> + '_> {
+    match payload {
+        Value::Array(items) => Box::new(items.iter()),
+        Value::Object(_) => Box::new(std::iter::once(payload)),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn join_description(parts: Vec<String>) -> Option<String> {
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("\n"))
+    }
+}
+
+/// Parse timestamps in the formats used by SpaceX and DONKI
+fn parse_timestamp(s: &str) -> Option<Timestamp> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    // DONKI uses minute precision without seconds, e.g. 2024-05-10T06:27Z
+    if let Ok(ndt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%MZ") {
+        return Some(Utc.from_utc_datetime(&ndt));
+    }
+    None
+}
+
+fn format_utc(ts: Timestamp) -> String {
+    ts.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+/// Escape a TEXT property value (RFC 5545 section 3.3.11)
+fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Fold a content line at 75 octets without splitting UTF-8 sequences
+fn fold_line(line: &str) -> String {
+    if line.len() <= MAX_LINE_OCTETS {
+        return line.to_string();
+    }
+
+    let mut out = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut current = 0;
+    // Continuation lines start with a space, which counts towards the limit
+    let mut limit = MAX_LINE_OCTETS;
+    for c in line.chars() {
+        if current + c.len_utf8() > limit {
+            out.push_str("\r\n ");
+            current = 0;
+            limit = MAX_LINE_OCTETS - 1;
+        }
+        out.push(c);
+        current += c.len_utf8();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source: &str, payload: Value) -> SpaceCache {
+        SpaceCache::new(source.to_string(), payload)
+    }
+
+    #[test]
+    fn test_launch_events_stable_uid() {
+        let cached = entry("spacex", serde_json::json!({
+            "id": "5eb87d42ffd86e000604b384",
+            "name": "Crew-9",
+            "date_utc": "2024-09-28T17:17:00.000Z",
+            "date_precision": "hour",
+            "flight_number": 210
+        }));
+        let first = launch_events(&cached);
+        let second = launch_events(&cached);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].uid, "spacex-launch-5eb87d42ffd86e000604b384@rust-iss");
+        assert_eq!(first[0].uid, second[0].uid);
+        assert!(!first[0].all_day);
+        assert!(first[0].ends_at.is_some());
+    }
+
+    #[test]
+    fn test_flare_and_cme_events() {
+        let flr = entry("flr", serde_json::json!([
+            {"flrID": "2024-05-10T06:27:00-FLR-001", "beginTime": "2024-05-10T06:27Z", "endTime": "2024-05-10T07:00Z", "classType": "X3.9"},
+            {"beginTime": "2024-05-10T06:27Z"}
+        ]));
+        let events = flare_events(&flr);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Solar flare X3.9");
+
+        let cme = entry("cme", serde_json::json!([
+            {"activityID": "2024-05-10T07:00:00-CME-001", "startTime": "2024-05-10T07:00Z"}
+        ]));
+        assert_eq!(cme_events(&cme).len(), 1);
+    }
+
+    #[test]
+    fn test_calendar_filter() {
+        let cached = entry("spacex", serde_json::json!({
+            "id": "abc", "name": "Test", "date_utc": "2030-01-01T00:00:00Z", "date_precision": "month"
+        }));
+        let event = &launch_events(&cached)[0];
+        assert!(event.all_day);
+
+        assert!(CalendarFilter::default().matches(event));
+
+        let filter = CalendarFilter { kinds: vec![CalendarEventKind::Cme], ..Default::default() };
+        assert!(!filter.matches(event));
+
+        let filter = CalendarFilter { to: parse_timestamp("2029-12-31T00:00:00Z"), ..Default::default() };
+        assert!(!filter.matches(event));
+    }
+
+    #[test]
+    fn test_render_ics() {
+        let cached = entry("spacex", serde_json::json!({
+            "id": "abc", "name": "Test, with; specials", "date_utc": "2030-01-01T00:00:00Z"
+        }));
+        let ics = render_ics(&launch_events(&cached), Utc::now());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:spacex-launch-abc@rust-iss\r\n"));
+        assert!(ics.contains("SUMMARY:SpaceX launch: Test\\, with\\; specials\r\n"));
+        assert!(ics.contains("DTSTART:20300101T000000Z\r\n"));
+    }
+
+    fn fix(lat: f64, lon: f64, at: &str) -> IssPoint {
+        IssPoint { lat, lon, at: parse_timestamp(at).unwrap(), velocity: None, altitude: Some(420.0) }
+    }
+
+    fn observer(name: &str, latitude: f64, longitude: f64) -> ObserverLocation {
+        ObserverLocation { name: name.to_string(), latitude, longitude, elevation: 0.0 }
+    }
+
+    #[test]
+    fn test_iss_pass_events() {
+        let track = vec![fix(-0.5, 10.0, "2030-01-01T11:59:00Z"), fix(0.0, 10.3, "2030-01-01T12:00:00Z")];
+        let orbit = IssOrbit::from_fixes(&track[0], &track[1]).unwrap();
+        // Right under the ground track ten minutes after the fix
+        let overhead_at = parse_timestamp("2030-01-01T12:10:00Z").unwrap();
+        let (lat, lon) = orbit.ground_point(overhead_at);
+        let under_track = observer("Under track", lat.to_degrees(), lon.to_degrees());
+
+        assert!(orbit.elevation_from(&under_track, overhead_at) > 89.0);
+
+        let events = iss_pass_events(&track, &[under_track]);
+        assert!(!events.is_empty());
+        let first = &events[0];
+        assert_eq!(first.kind, CalendarEventKind::IssPass);
+        assert!(first.starts_at < overhead_at && first.ends_at.unwrap() > overhead_at);
+        assert!(first.summary.starts_with("ISS pass over Under track, max "));
+        assert!(first.uid.starts_with("iss-pass-under-track-") && first.uid.ends_with("@rust-iss"));
+        // Passes repeat no faster than once an orbit, each on its own revolution
+        for pair in events.windows(2) {
+            assert!(pair[1].starts_at - pair[0].starts_at > Duration::minutes(85));
+            assert_ne!(pair[1].uid, pair[0].uid);
+        }
+
+        // A newer fix along the same orbit keeps the UIDs of the same passes
+        let later = vec![track[1].clone(), {
+            let at = track[1].at + Duration::minutes(1);
+            let (lat, lon) = orbit.ground_point(at);
+            IssPoint { lat: lat.to_degrees(), lon: lon.to_degrees(), at, velocity: None, altitude: Some(420.0) }
+        }];
+        let under_track = observer("Under track", lat.to_degrees(), lon.to_degrees());
+        assert_eq!(iss_pass_events(&later, &[under_track])[0].uid, first.uid);
+
+        // Too far from the orbit's latitude band to ever see it
+        assert!(iss_pass_events(&track, &[observer("Pole", 89.0, 0.0)]).is_empty());
+        assert!(iss_pass_events(&track[..1], &[observer("Anywhere", 0.0, 0.0)]).is_empty());
+
+        assert_eq!(CalendarEventKind::parse("iss"), Some(CalendarEventKind::IssPass));
+        assert!(render_ics(&events, Utc::now()).contains("CATEGORIES:ISS PASS\r\n"));
+    }
+
+    #[test]
+    fn test_iss_pass_uid_survives_prediction_drift() {
+        use chrono::DurationRound;
+
+        let half_hour = Duration::minutes(30);
+        let shifted = |track: &[IssPoint], by: Duration| -> Vec<IssPoint> {
+            track.iter().map(|p| IssPoint { at: p.at + by, ..p.clone() }).collect()
+        };
+        let middle = |e: &CalendarEvent| e.starts_at + (e.ends_at.unwrap() - e.starts_at) / 2;
+        let moscow = observer("Moscow", 55.75, 37.62);
+
+        let track = vec![fix(-0.5, 10.0, "2030-01-01T11:59:00Z"), fix(0.0, 10.3, "2030-01-01T12:00:00Z")];
+        let first = iss_pass_events(&track, std::slice::from_ref(&moscow)).remove(0);
+        let boundary = middle(&first).duration_trunc(half_hour).unwrap() + half_hour;
+
+        // Two refreshes whose predictions of the same passes differ by a few
+        // minutes, with the first pass moving across a half-hour boundary
+        let before = iss_pass_events(&shifted(&track, boundary - Duration::minutes(2) - middle(&first)), std::slice::from_ref(&moscow));
+        let after = iss_pass_events(&shifted(&track, boundary + Duration::minutes(2) - middle(&first)), &[moscow]);
+        assert!(middle(&before[0]) < boundary && middle(&after[0]) > boundary);
+        assert_eq!(before.len(), after.len());
+        for (before, after) in before.iter().zip(&after) {
+            assert_eq!(before.uid, after.uid);
+        }
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = format!("DESCRIPTION:{}", "ж".repeat(100));
+        let folded = fold_line(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= MAX_LINE_OCTETS);
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
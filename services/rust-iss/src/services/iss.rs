@@ -11,11 +11,33 @@ use crate::clients::{IssClient, Result as ClientResult, ClientError};
 pub struct IssServiceImpl<R: IssRepo + Clone, C: IssClient + Clone> {
     repo: R,
     client: C,
+    cache: CacheLayer,
 }
 
 impl<R: IssRepo + Clone, C: IssClient + Clone> IssServiceImpl<R, C> {
     pub fn new(repo: R, client: C) -> Self {
-        Self { repo, client }
+        Self { repo, client, cache: CacheLayer::default() }
+    }
+
+    /// Serve latest position and trend points through the Redis cache layer
+    pub fn with_cache(mut self, cache: CacheLayer) -> Self {
+        self.cache = cache;
+        self
+    }
+}
+
+impl<R: IssRepo + Sync + Clone, C: IssClient + Clone + Sync> IssServiceImpl<R, C> {
+    /// Persist a validated position and write it through to the cache
+    async fn store(&self, mut iss_data: IssData) -> crate::services::Result<IssData> {
+        let id = self.repo
+            .insert_iss_data(&iss_data)
+            .await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+        iss_data.id = Some(id);
+
+        self.cache.put("iss", "iss:last", &iss_data).await;
+        self.cache.bump_generation("iss").await;
+        Ok(iss_data)
     }
 }
 
@@ -35,19 +57,22 @@ impl<R: IssRepo + Sync + Clone, C: IssClient + Clone + Sync> IssService for IssS
             .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
 
         // Store in repository
-        self.repo
-            .insert_iss_data(&iss_data)
-            .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
-
-        Ok(iss_data)
+        self.store(iss_data).await
     }
 
     async fn get_latest_iss_data(&self) -> crate::services::Result<Option<IssData>> {
-        self.repo
+        if let Some(cached) = self.cache.get::<IssData>("iss", "iss:last").await {
+            return Ok(Some(cached));
+        }
+
+        let latest = self.repo
             .get_latest_iss_data()
             .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+        if let Some(ref data) = latest {
+            self.cache.put("iss", "iss:last", data).await;
+        }
+        Ok(latest)
     }
 
     async fn get_iss_trend_analysis(&self) -> crate::services::Result<IssTrend> {
@@ -105,6 +130,11 @@ impl<R: IssRepo + Sync + Clone, C: IssClient + Clone + Sync> IssService for IssS
     }
 
     async fn get_iss_trend_points(&self, limit: usize) -> crate::services::Result<Vec<crate::services::IssPoint>> {
+        let key = format!("iss:trend:{}:{}", self.cache.generation("iss").await, limit);
+        if let Some(cached) = self.cache.get::<Vec<IssPoint>>("iss", &key).await {
+            return Ok(cached);
+        }
+
         let iss_data_list = self.repo
             .get_iss_data_range(limit as i64)
             .await
@@ -125,13 +155,11 @@ impl<R: IssRepo + Sync + Clone, C: IssClient + Clone + Sync> IssService for IssS
                 });
             }
         }
+        self.cache.put("iss", &key, &points).await;
         Ok(points)
     }
 
     async fn trigger_iss_fetch(&self) -> crate::services::Result<IssData> {
-        // Default ISS API URL
-        let default_url = "https://api.wheretheiss.at/v1/satellites/25544";
-
         // Fetch data from ISS API using the client
         let json: Value = self.client
             .fetch_iss_position()
@@ -139,18 +167,13 @@ impl<R: IssRepo + Sync + Clone, C: IssClient + Clone + Sync> IssService for IssS
             .map_err(|e| ServiceError::ExternalApiError(format!("ISS API request failed: {}", e)))?;
 
         // Create domain model and validate
-        let iss_data = IssData::new(default_url.to_string(), json);
+        let iss_data = IssData::new(self.client.position_url().to_string(), json);
         iss_data
             .validate()
             .map_err(|e| ServiceError::ValidationError(e.to_string()))?;
 
         // Store in repository
-        self.repo
-            .insert_iss_data(&iss_data)
-            .await
-            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
-
-        Ok(iss_data)
+        self.store(iss_data).await
     }
 }
 
@@ -220,6 +243,10 @@ mod tests {
 
     #[async_trait]
     impl IssClient for MockIssClient {
+        fn position_url(&self) -> &str {
+            "http://mirror.test/iss"
+        }
+
         async fn fetch_iss_position(&self) -> ClientResult<Value> {
             Ok(serde_json::json!({
                 "latitude": 51.5074,
@@ -276,4 +303,12 @@ mod tests {
         let result = service.get_latest_iss_data().await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_trigger_iss_fetch_records_configured_url() {
+        let service = IssServiceImpl::new(MockIssRepo::new(), MockIssClient);
+        let stored = service.trigger_iss_fetch().await.unwrap();
+        assert_eq!(stored.source_url, "http://mirror.test/iss");
+        assert_eq!(stored.id, Some(1));
+    }
 }
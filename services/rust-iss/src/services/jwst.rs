@@ -0,0 +1,293 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::domain::*;
+use crate::repo::*;
+use crate::services::*;
+use crate::clients::JwstClient;
+
+/// Maximum page size accepted by the JWST API
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Implementation of JWST Service
+#[derive(Clone)]
+pub struct JwstServiceImpl<R: JwstRepo + Clone, C: JwstClient + Clone> {
+    repo: R,
+    client: C,
+    program_ids: Vec<String>,
+    page_size: u32,
+}
+
+impl<R: JwstRepo + Clone, C: JwstClient + Clone> JwstServiceImpl<R, C> {
+    pub fn new(repo: R, client: C) -> Self {
+        Self {
+            repo,
+            client,
+            program_ids: Vec::new(),
+            page_size: 60,
+        }
+    }
+
+    /// Configure the JWST programs to ingest and the page size per request
+    pub fn with_programs(mut self, program_ids: Vec<String>, page_size: u32) -> Self {
+        self.program_ids = program_ids;
+        self.page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        self
+    }
+
+    /// Store every image item of a feed response, returning the number written
+    async fn store_feed(&self, json: Value, program_hint: Option<&str>) -> crate::services::Result<usize> {
+        let mut written = 0usize;
+        for item in parse_jwst_response(json) {
+            let Some(observation) = extract_jwst_observation(&item, program_hint) else {
+                continue;
+            };
+            if let Err(e) = observation.validate() {
+                warn!("Skipping invalid JWST item {}: {}", observation.observation_id, e);
+                continue;
+            }
+
+            self.repo
+                .upsert_jwst_observation(&observation)
+                .await
+                .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+#[async_trait]
+impl<R: JwstRepo + Clone + Sync, C: JwstClient + Clone + Sync> JwstService for JwstServiceImpl<R, C> {
+    async fn sync_jwst_feed(&self) -> crate::services::Result<usize> {
+        // Latest JPEG images across all programs (used for the featured observation)
+        let json = self.client
+            .fetch_images_by_type("jpg", 1, self.page_size)
+            .await
+            .map_err(|e| ServiceError::ExternalApiError(format!("Failed to fetch JWST images: {}", e)))?;
+        let mut written = self.store_feed(json, None).await?;
+
+        for program_id in &self.program_ids {
+            let json = self.client
+                .fetch_program_images(program_id, 1, self.page_size)
+                .await
+                .map_err(|e| ServiceError::ExternalApiError(format!("Failed to fetch JWST program {}: {}", program_id, e)))?;
+            written += self.store_feed(json, Some(program_id)).await?;
+        }
+
+        info!("JWST sync stored {} observations", written);
+        Ok(written)
+    }
+
+    async fn get_jwst_feed(&self, filter: &JwstFilter, page: i64, per_page: i64) -> crate::services::Result<JwstFeedPage> {
+        let page = page.max(1);
+        let per_page = per_page.clamp(1, MAX_PAGE_SIZE as i64);
+
+        let items = self.repo
+            .get_jwst_observations(filter, per_page, (page - 1) * per_page)
+            .await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+        let total = self.repo
+            .count_jwst_observations(filter)
+            .await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+
+        Ok(JwstFeedPage { items, page, per_page, total })
+    }
+}
+
+/// Parse JWST API response into a vector of JSON values
+fn parse_jwst_response(json: Value) -> Vec<Value> {
+    match json {
+        Value::Array(items) => items,
+        Value::Object(mut obj) => {
+            for key in ["body", "data"] {
+                if let Some(Value::Array(items)) = obj.remove(key) {
+                    return items;
+                }
+            }
+            Vec::new()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Build an observation from a raw JWST API item, or `None` if it has no image
+fn extract_jwst_observation(raw: &Value, program_hint: Option<&str>) -> Option<JwstObservation> {
+    let image_url = pick_image_url(raw)?;
+
+    let observation_id = string_field(raw, &["observation_id", "observationId", "id"])?;
+    let program = string_field(raw, &["program"]).or_else(|| program_hint.map(|p| p.to_string()))?;
+
+    let instruments = raw
+        .pointer("/details/instruments")
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|i| i.get("instrument").and_then(|v| v.as_str()))
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let suffix = raw
+        .pointer("/details/suffix")
+        .or_else(|| raw.get("suffix"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+
+    let thumbnail_url = raw
+        .get("thumbnail")
+        .and_then(|v| v.as_str())
+        .filter(|s| is_image_url(s) && *s != image_url)
+        .map(|s| s.to_string());
+
+    Some(JwstObservation {
+        id: None,
+        observation_id,
+        program,
+        instruments,
+        suffix,
+        image_url,
+        thumbnail_url,
+        fetched_at: Utc::now(),
+        raw: raw.clone(),
+    })
+}
+
+/// Pick the image URL of an item: `location`/`url`/`thumbnail` first, then any nested string
+fn pick_image_url(raw: &Value) -> Option<String> {
+    for key in ["location", "url", "thumbnail"] {
+        if let Some(url) = raw.get(key).and_then(|v| v.as_str()) {
+            if is_http_image(url) {
+                return Some(url.to_string());
+            }
+        }
+    }
+
+    let mut stack = vec![raw];
+    while let Some(current) = stack.pop() {
+        match current {
+            Value::String(s) if is_http_image(s) => return Some(s.clone()),
+            Value::Array(items) => stack.extend(items.iter()),
+            Value::Object(obj) => stack.extend(obj.values()),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_http_image(url: &str) -> bool {
+    (url.starts_with("http://") || url.starts_with("https://")) && is_image_url(url)
+}
+
+/// Extract a string (or number rendered as string) using multiple possible keys
+fn string_field(value: &Value, keys: &[&str]) -> Option<String> {
+    for key in keys {
+        match value.get(key) {
+            Some(Value::String(s)) if !s.trim().is_empty() => return Some(s.trim().to_string()),
+            Some(Value::Number(n)) => return Some(n.to_string()),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::Result as ClientResult;
+
+    fn sample_item() -> Value {
+        serde_json::json!({
+            "id": "jw02734002001_02103_00001_nrcalong_i2d",
+            "observation_id": "jw02734002001_02103_00001_nrcalong",
+            "program": 2734,
+            "details": {
+                "mission": "JWST",
+                "instruments": [{"instrument": "NIRCam"}],
+                "suffix": "_i2d"
+            },
+            "file_type": "jpg",
+            "location": "https://stsci-opo.org/jw02734_i2d.jpg"
+        })
+    }
+
+    #[derive(Clone)]
+    struct MockJwstRepo;
+
+    #[async_trait]
+    impl JwstRepo for MockJwstRepo {
+        async fn upsert_jwst_observation(&self, _observation: &JwstObservation) -> crate::repo::Result<i64> {
+            Ok(1)
+        }
+
+        async fn get_jwst_observations(&self, _filter: &JwstFilter, _limit: i64, _offset: i64) -> crate::repo::Result<Vec<JwstObservation>> {
+            Ok(vec![])
+        }
+
+        async fn count_jwst_observations(&self, _filter: &JwstFilter) -> crate::repo::Result<i64> {
+            Ok(0)
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockJwstClient;
+
+    #[async_trait]
+    impl JwstClient for MockJwstClient {
+        async fn fetch_images_by_type(&self, _file_type: &str, _page: u32, _per_page: u32) -> ClientResult<Value> {
+            Ok(serde_json::json!({"statusCode": 200, "body": [sample_item()]}))
+        }
+
+        async fn fetch_program_images(&self, _program_id: &str, _page: u32, _per_page: u32) -> ClientResult<Value> {
+            Ok(serde_json::json!({"statusCode": 200, "body": [
+                sample_item(),
+                {"observation_id": "jw02734", "program": 2734, "location": "https://mast.stsci.edu/file.fits"}
+            ]}))
+        }
+    }
+
+    #[test]
+    fn test_extract_jwst_observation() {
+        let observation = extract_jwst_observation(&sample_item(), None).unwrap();
+        assert_eq!(observation.program, "2734");
+        assert_eq!(observation.instruments, vec!["NIRCAM".to_string()]);
+        assert_eq!(observation.suffix.as_deref(), Some("_i2d"));
+        assert!(observation.validate().is_ok());
+    }
+
+    #[test]
+    fn test_extract_jwst_observation_without_image() {
+        let raw = serde_json::json!({"observation_id": "jw1", "program": 1, "location": "https://x/file.fits"});
+        assert!(extract_jwst_observation(&raw, None).is_none());
+    }
+
+    #[test]
+    fn test_pick_image_url_nested() {
+        let raw = serde_json::json!({"details": {"files": ["https://x/a.fits", "https://x/b.png"]}});
+        assert_eq!(pick_image_url(&raw), Some("https://x/b.png".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_sync_jwst_feed() {
+        let service = JwstServiceImpl::new(MockJwstRepo, MockJwstClient)
+            .with_programs(vec!["2734".to_string()], 10);
+        let result = service.sync_jwst_feed().await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_jwst_feed_clamps_paging() {
+        let service = JwstServiceImpl::new(MockJwstRepo, MockJwstClient);
+        let page = service.get_jwst_feed(&JwstFilter::default(), 0, 1000).await.unwrap();
+        assert_eq!(page.page, 1);
+        assert_eq!(page.per_page, 100);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::cache_layer::glob_match;
+
+/// How a [`LocalCache::get_or_load`] call was answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    /// Served from memory
+    Hit,
+    /// Waited for a concurrent load of the same key and reused its result
+    Coalesced,
+    /// Ran the loader
+    Loaded,
+}
+
+struct Entry<V> {
+    value: V,
+    expires_at: Instant,
+    tick: u64,
+}
+
+struct State<V> {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<String, Entry<V>>,
+    /// Access order, oldest tick first
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    /// Bumped on invalidation so loads started before it are not stored
+    epoch: u64,
+}
+
+impl<V> State<V> {
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.tick);
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+/// Size-bounded in-process LRU with a TTL, sitting in front of Redis.
+///
+/// Concurrent misses for the same key are coalesced: one caller runs the
+/// loader while the others wait and reuse what it stored.
+#[derive(Clone)]
+pub struct LocalCache<V: Clone> {
+    state: Arc<Mutex<State<V>>>,
+    inflight: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
+}
+
+impl<V: Clone> LocalCache<V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                capacity,
+                ttl,
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                tick: 0,
+                epoch: 0,
+            })),
+            inflight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<V> {
+        let mut state = self.state.lock().ok()?;
+        let tick = state.next_tick();
+        let entry = state.entries.get_mut(key)?;
+        if entry.expires_at <= Instant::now() {
+            state.remove(key);
+            return None;
+        }
+        let previous = std::mem::replace(&mut entry.tick, tick);
+        let value = entry.value.clone();
+        state.order.remove(&previous);
+        state.order.insert(tick, key.to_string());
+        Some(value)
+    }
+
+    pub fn insert(&self, key: &str, value: V) {
+        if let Ok(mut state) = self.state.lock() {
+            store(&mut state, key, value);
+        }
+    }
+
+    /// Drop every entry whose key matches the Redis-style glob `pattern`
+    pub fn invalidate_matching(&self, pattern: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.epoch += 1;
+            let keys: Vec<String> = state.entries.keys().filter(|k| glob_match(pattern, k)).cloned().collect();
+            for key in keys {
+                state.remove(&key);
+            }
+        }
+    }
+
+    /// Return the cached value for `key`, or run `load` once for all
+    /// concurrent callers and cache a `Some` result.
+    ///
+    /// Failed or empty loads are not cached; callers that were waiting on
+    /// them run the loader themselves.
+    pub async fn get_or_load<E, F, Fut>(&self, key: &str, load: F) -> (Result<Option<V>, E>, Lookup)
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<V>, E>>,
+    {
+        if let Some(value) = self.get(key) {
+            return (Ok(Some(value)), Lookup::Hit);
+        }
+
+        let gate = self.inflight.lock().ok().map(|mut inflight| inflight.entry(key.to_string()).or_default().clone());
+        let Some(gate) = gate else {
+            return (load().await, Lookup::Loaded);
+        };
+        let guard = gate.lock().await;
+
+        let result = match self.get(key) {
+            Some(value) => (Ok(Some(value)), Lookup::Coalesced),
+            None => {
+                let epoch = self.state.lock().map(|s| s.epoch).unwrap_or(0);
+                let loaded = load().await;
+                if let Ok(Some(ref value)) = loaded {
+                    if let Ok(mut state) = self.state.lock() {
+                        // Skip storing if the key was invalidated while loading
+                        if state.epoch == epoch {
+                            store(&mut state, key, value.clone());
+                        }
+                    }
+                }
+                (loaded, Lookup::Loaded)
+            }
+        };
+
+        drop(guard);
+        if let Ok(mut inflight) = self.inflight.lock() {
+            // Only the map and this call hold the gate: nobody else is waiting
+            if Arc::strong_count(&gate) <= 2 {
+                inflight.remove(key);
+            }
+        }
+        result
+    }
+}
+
+fn store<V>(state: &mut State<V>, key: &str, value: V) {
+    if state.capacity == 0 {
+        return;
+    }
+    state.remove(key);
+    let tick = state.next_tick();
+    let expires_at = Instant::now() + state.ttl;
+    state.entries.insert(key.to_string(), Entry { value, expires_at, tick });
+    state.order.insert(tick, key.to_string());
+    // Evict least recently used entries beyond capacity
+    while state.entries.len() > state.capacity {
+        let Some((_, oldest)) = state.order.pop_first() else {
+            break;
+        };
+        state.entries.remove(&oldest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn len<V: Clone>(cache: &LocalCache<V>) -> usize {
+        cache.state.lock().unwrap().entries.len()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = LocalCache::new(2, Duration::from_secs(60));
+        cache.insert("space:apod", 1);
+        cache.insert("space:neo", 2);
+        assert_eq!(cache.get("space:apod"), Some(1));
+
+        cache.insert("space:flr", 3);
+        assert_eq!(len(&cache), 2);
+        assert_eq!(cache.get("space:neo"), None);
+        assert_eq!(cache.get("space:apod"), Some(1));
+        assert_eq!(cache.get("space:flr"), Some(3));
+    }
+
+    #[test]
+    fn test_entries_expire_and_invalidate_by_pattern() {
+        let cache = LocalCache::new(8, Duration::from_millis(0));
+        cache.insert("space:apod", 1);
+        assert_eq!(cache.get("space:apod"), None);
+
+        let cache = LocalCache::new(8, Duration::from_secs(60));
+        cache.insert("space:apod", 1);
+        cache.insert("space:neo", 2);
+        cache.insert("iss:last", 3);
+        cache.invalidate_matching("space:*");
+        assert_eq!(cache.get("space:apod"), None);
+        assert_eq!(cache.get("space:neo"), None);
+        assert_eq!(cache.get("iss:last"), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_are_coalesced() {
+        let cache = LocalCache::new(8, Duration::from_secs(60));
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let calls = (0..10).map(|_| {
+            let cache = cache.clone();
+            let loads = loads.clone();
+            tokio::spawn(async move {
+                cache.get_or_load("space:apod", || async move {
+                    loads.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    Ok::<_, ()>(Some(42))
+                }).await
+            })
+        });
+        let results = futures_util::future::join_all(calls).await;
+
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        let lookups: Vec<Lookup> = results.into_iter().map(|r| {
+            let (value, lookup) = r.unwrap();
+            assert_eq!(value, Ok(Some(42)));
+            lookup
+        }).collect();
+        assert_eq!(lookups.iter().filter(|l| **l == Lookup::Loaded).count(), 1);
+        assert!(cache.inflight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_and_empty_loads_are_not_cached() {
+        let cache: LocalCache<i32> = LocalCache::new(8, Duration::from_secs(60));
+        let (result, _) = cache.get_or_load("space:apod", || async { Err::<Option<i32>, _>("down") }).await;
+        assert_eq!(result, Err("down"));
+        let (result, _) = cache.get_or_load("space:apod", || async { Ok::<_, ()>(None) }).await;
+        assert_eq!(result, Ok(None));
+        assert_eq!(len(&cache), 0);
+    }
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use crate::domain::MigrationStatus;
+use crate::repo::MigrationRepo;
+use crate::services::{MigrationService, Result, ServiceError};
+
+/// Implementation of the schema migration Service
+#[derive(Clone)]
+pub struct MigrationServiceImpl<R: MigrationRepo + Clone> {
+    repo: R,
+}
+
+impl<R: MigrationRepo + Clone> MigrationServiceImpl<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl<R: MigrationRepo + Clone + Send + Sync> MigrationService for MigrationServiceImpl<R> {
+    async fn apply_migrations(&self) -> Result<Vec<i64>> {
+        self.repo.apply_migrations().await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+    }
+
+    async fn revert_migrations(&self, target: i64) -> Result<Vec<i64>> {
+        self.repo.revert_migrations(target).await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+    }
+
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        self.repo.migration_status().await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+    }
+}
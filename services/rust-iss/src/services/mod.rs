@@ -1,10 +1,20 @@
 mod iss;
 mod osdr;
 mod cache;
+mod cache_layer;
+mod local_cache;
+mod sources;
+mod calendar;
+mod jwst;
+mod astro;
+mod telemetry;
+mod retention;
+mod migrations;
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::result;
 
 use crate::domain::*;
@@ -45,7 +55,7 @@ pub trait IssService {
 }
 
 /// ISS Point for trend visualization
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct IssPoint {
     pub lat: f64,
     pub lon: f64,
@@ -57,22 +67,125 @@ pub struct IssPoint {
 /// OSDR Service trait
 #[async_trait]
 pub trait OsdrService {
-    async fn sync_osdr_data(&self, api_url: &str) -> Result<usize>;
+    async fn sync_osdr_data(&self) -> Result<usize>;
     async fn get_osdr_items(&self, limit: i64) -> Result<Vec<OsdrItem>>;
     async fn get_osdr_item_count(&self) -> Result<i64>;
+    async fn get_osdr_page(&self, page: i64, per_page: i64) -> Result<OsdrPage>;
+}
+
+/// Page of OSDR items, newest first
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OsdrPage {
+    pub items: Vec<OsdrItem>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
 }
 
 /// Cache Service trait
 #[async_trait]
 pub trait CacheService {
-    async fn fetch_and_cache_apod(&self, api_key: Option<&str>) -> Result<SpaceCache>;
-    async fn fetch_and_cache_neo_feed(&self, api_key: Option<&str>) -> Result<SpaceCache>;
-    async fn fetch_and_cache_donki_data(&self, api_key: Option<&str>) -> Result<Vec<SpaceCache>>;
-    async fn fetch_and_cache_spacex_next(&self) -> Result<SpaceCache>;
+    fn sources(&self) -> &SourceRegistry;
+    async fn fetch_and_cache_source(&self, source: &str) -> Result<SpaceCache>;
     async fn get_latest_cache_entry(&self, source: &str) -> Result<Option<SpaceCache>>;
-    async fn refresh_multiple_sources(&self, sources: Vec<String>, api_key: Option<&str>) -> Result<Vec<String>>;
+    async fn refresh_multiple_sources(&self, sources: Vec<String>) -> Result<Vec<String>>;
     async fn get_space_summary(&self) -> Result<SpaceSummary>;
+    async fn get_cached_source(&self, source: &str) -> Result<Option<CachedSource>>;
+    /// Payloads of a source in the order they changed, newest first
+    async fn get_source_history(&self, source: &str, limit: i64) -> Result<Vec<SpaceCache>>;
     async fn store_space_cache(&self, source: String, payload: serde_json::Value) -> Result<()>;
+    fn get_cache_stats(&self) -> BTreeMap<String, CacheCounters>;
+    async fn list_cache_keys(&self, pattern: &str, limit: usize) -> Result<Vec<CacheKeyInfo>>;
+    async fn purge_cache(&self, pattern: &str) -> Result<Vec<String>>;
+}
+
+/// JWST Service trait
+#[async_trait]
+pub trait JwstService {
+    async fn sync_jwst_feed(&self) -> Result<usize>;
+    async fn get_jwst_feed(&self, filter: &JwstFilter, page: i64, per_page: i64) -> Result<JwstFeedPage>;
+}
+
+/// Page of JWST observations served from the database
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct JwstFeedPage {
+    pub items: Vec<JwstObservation>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
+
+/// Astronomical events Service trait
+#[async_trait]
+pub trait AstroService {
+    async fn sync_astro_events(&self) -> Result<usize>;
+    async fn get_astro_events(&self, filter: &AstroEventFilter, limit: i64) -> Result<Vec<AstroEvent>>;
+}
+
+/// Legacy telemetry ingestion Service trait
+#[async_trait]
+pub trait TelemetryService {
+    async fn ingest_drop_directory(&self) -> Result<TelemetryIngestReport>;
+    async fn get_telemetry_summary(&self, from: DateTime<Utc>, to: DateTime<Utc>, bucket_seconds: i64) -> Result<TelemetrySummary>;
+}
+
+/// Retention and compaction Service trait
+#[async_trait]
+pub trait RetentionService {
+    /// Downsample, deduplicate and expire old rows according to the retention policy
+    async fn run_compaction(&self) -> Result<CompactionReport>;
+    /// Create upcoming iss_fetch_log partitions, returning the new ones
+    async fn ensure_partitions(&self) -> Result<Vec<String>>;
+    async fn get_compaction_runs(&self, limit: i64) -> Result<Vec<CompactionReport>>;
+}
+
+/// Schema migration Service trait
+#[async_trait]
+pub trait MigrationService {
+    /// Apply all pending migrations, returning the versions applied
+    async fn apply_migrations(&self) -> Result<Vec<i64>>;
+    /// Revert applied migrations newer than `target`, returning the versions reverted
+    async fn revert_migrations(&self, target: i64) -> Result<Vec<i64>>;
+    async fn migration_status(&self) -> Result<Vec<MigrationStatus>>;
+}
+
+/// Bucketed telemetry statistics with readings outside the configured envelope
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TelemetrySummary {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub bucket_seconds: i64,
+    pub envelope: TelemetryEnvelope,
+    pub readings: i64,
+    pub validity_rate: Option<f64>,
+    pub buckets: Vec<TelemetryBucketStats>,
+    pub out_of_range: Vec<FlaggedReading>,
+}
+
+/// One aggregation bucket as served by the API
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TelemetryBucketStats {
+    #[serde(flatten)]
+    pub bucket: TelemetryBucket,
+    pub validity_rate: f64,
+}
+
+/// Telemetry reading outside the envelope, with the violated limits
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FlaggedReading {
+    #[serde(flatten)]
+    pub reading: TelemetryReading,
+    pub flags: Vec<&'static str>,
+}
+
+/// Outcome of a single scan of the telemetry drop directory
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct TelemetryIngestReport {
+    pub files_archived: usize,
+    pub files_quarantined: usize,
+    pub files_duplicate: usize,
+    pub files_failed: usize,
+    pub rows_inserted: usize,
 }
 
 /// ISS Trend analysis result
@@ -90,14 +203,20 @@ pub struct IssTrend {
     pub to_lon: Option<f64>,
 }
 
+/// Latest cached entry of a source with its freshness, measured from when
+/// the source was last fetched
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CachedSource {
+    pub entry: SpaceCache,
+    pub age_seconds: i64,
+    pub stale: bool,
+}
+
 /// Space data summary
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct SpaceSummary {
-    pub apod: Option<SpaceCache>,
-    pub neo: Option<SpaceCache>,
-    pub flr: Option<SpaceCache>,
-    pub cme: Option<SpaceCache>,
-    pub spacex: Option<SpaceCache>,
+    /// Latest entry of every registered source, in registry order
+    pub sources: Vec<(&'static str, Option<CachedSource>)>,
     pub iss: Option<IssData>,
     pub osdr_count: i64,
 }
@@ -106,3 +225,17 @@ pub struct SpaceSummary {
 pub use crate::services::iss::IssServiceImpl;
 pub use crate::services::osdr::OsdrServiceImpl;
 pub use crate::services::cache::CacheServiceImpl;
+pub use crate::services::cache_layer::{source_pattern, CacheCounters, CacheLayer, DERIVED_SOURCES};
+pub use crate::services::local_cache::LocalCache;
+pub use crate::services::sources::{
+    ApodSource, DonkiCmeSource, DonkiFlrSource, NeoFeedSource, SourceRegistry, SpaceSource, SpaceXNextSource,
+};
+pub use crate::services::jwst::JwstServiceImpl;
+pub use crate::services::astro::AstroServiceImpl;
+pub use crate::services::telemetry::TelemetryServiceImpl;
+pub use crate::services::retention::RetentionServiceImpl;
+pub use crate::services::migrations::MigrationServiceImpl;
+pub use crate::services::calendar::{
+    CalendarEvent, CalendarEventKind, CalendarFilter, cme_events, flare_events, iss_pass_events, launch_events,
+    render_ics,
+};
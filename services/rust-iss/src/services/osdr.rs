@@ -2,39 +2,39 @@ use async_trait::async_trait;
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use serde_json::Value;
 use std::time::Duration;
+use tracing::debug;
 
 use crate::domain::*;
 use crate::repo::*;
 use crate::services::*;
-use crate::clients::{NasaClient, Result as ClientResult};
+use crate::clients::{ItemStream, NasaClient, Result as ClientResult};
 
 /// Implementation of OSDR Service
 #[derive(Clone)]
 pub struct OsdrServiceImpl<R: OsdrRepo + Clone, C: NasaClient + Clone> {
     repo: R,
     client: C,
+    cache: CacheLayer,
 }
 
 impl<R: OsdrRepo + Clone, C: NasaClient + Clone> OsdrServiceImpl<R, C> {
     pub fn new(repo: R, client: C) -> Self {
-        Self { repo, client }
+        Self { repo, client, cache: CacheLayer::default() }
     }
-}
 
-#[async_trait]
-impl<R: OsdrRepo + Clone + Sync, C: NasaClient + Clone + Sync> OsdrService for OsdrServiceImpl<R, C> {
-    async fn sync_osdr_data(&self, _api_url: &str) -> crate::services::Result<usize> {
-        // Fetch data from OSDR API using NasaClient
-        let json: Value = self.client
-            .fetch_osdr_datasets()
-            .await
-            .map_err(|e| ServiceError::ExternalApiError(format!("Failed to fetch OSDR data: {}", e)))?;
+    /// Serve item pages through the Redis cache layer
+    pub fn with_cache(mut self, cache: CacheLayer) -> Self {
+        self.cache = cache;
+        self
+    }
+}
 
-        // Parse the response - handle different possible formats
-        let items = parse_osdr_response(json)?;
+impl<R: OsdrRepo + Clone + Sync, C: NasaClient + Clone + Sync> OsdrServiceImpl<R, C> {
+    /// Validate and upsert every item of the list, counting the ones written
+    async fn store_osdr_items(&self, items: &mut ItemStream, written: &mut usize) -> crate::services::Result<()> {
+        while let Some(item) = items.next().await {
+            let item = item.map_err(|e| ServiceError::ExternalApiError(format!("Failed to read OSDR data: {}", e)))?;
 
-        let mut written = 0usize;
-        for item in items {
             // Create domain model and validate
             let osdr_item = extract_osdr_item_fields(&item);
             osdr_item
@@ -47,10 +47,38 @@ impl<R: OsdrRepo + Clone + Sync, C: NasaClient + Clone + Sync> OsdrService for O
                 .await
                 .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
 
-            written += 1;
+            *written += 1;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<R: OsdrRepo + Clone + Sync, C: NasaClient + Clone + Sync> OsdrService for OsdrServiceImpl<R, C> {
+    async fn sync_osdr_data(&self) -> crate::services::Result<usize> {
+        // Items are deserialized as they are written rather than all up front
+        let mut fetched = self.client
+            .stream_osdr_datasets()
+            .await
+            .map_err(|e| ServiceError::ExternalApiError(format!("Failed to fetch OSDR data: {}", e)))?;
+
+        // Validators are only kept once a whole list was read, so a 304 means
+        // the stored datasets are current
+        if !fetched.changed {
+            debug!("OSDR dataset list not modified, nothing to write");
+            return Ok(0);
         }
 
-        Ok(written)
+        let mut written = 0usize;
+        let result = self.store_osdr_items(&mut fetched.items, &mut written).await;
+
+        // Cached pages are keyed by generation, so this invalidates all of them,
+        // including after a list that failed halfway
+        if written > 0 {
+            self.cache.bump_generation("osdr").await;
+        }
+
+        result.map(|_| written)
     }
 
     async fn get_osdr_items(&self, limit: i64) -> crate::services::Result<Vec<OsdrItem>> {
@@ -66,19 +94,26 @@ impl<R: OsdrRepo + Clone + Sync, C: NasaClient + Clone + Sync> OsdrService for O
             .await
             .map_err(|e| ServiceError::RepositoryError(e.to_string()))
     }
-}
 
-/// Parse OSDR API response into a vector of JSON values
-fn parse_osdr_response(json: Value) -> crate::services::Result<Vec<Value>> {
-    if let Some(array) = json.as_array() {
-        Ok(array.clone())
-    } else if let Some(items) = json.get("items").and_then(|v| v.as_array()) {
-        Ok(items.clone())
-    } else if let Some(results) = json.get("results").and_then(|v| v.as_array()) {
-        Ok(results.clone())
-    } else {
-        // Single object response
-        Ok(vec![json])
+    async fn get_osdr_page(&self, page: i64, per_page: i64) -> crate::services::Result<OsdrPage> {
+        if page < 1 || per_page < 1 {
+            return Err(ServiceError::ValidationError("page and per_page must be positive".to_string()));
+        }
+
+        let key = format!("osdr:list:{}:{}:{}", self.cache.generation("osdr").await, page, per_page);
+        if let Some(cached) = self.cache.get::<OsdrPage>("osdr", &key).await {
+            return Ok(cached);
+        }
+
+        let items = self.repo
+            .get_osdr_items_page(per_page, (page - 1) * per_page)
+            .await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+        let total = self.get_osdr_item_count().await?;
+
+        let result = OsdrPage { items, page, per_page, total };
+        self.cache.put("osdr", &key, &result).await;
+        Ok(result)
     }
 }
 
@@ -133,11 +168,14 @@ fn extract_timestamp_field(value: &Value, keys: &[&str]) -> Option<DateTime<Utc>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clients::{Fetched, FetchedItems};
     use crate::repo::RepoError;
 
     // Mock repository for testing
-    #[derive(Clone)]
-    struct MockOsdrRepo;
+    #[derive(Clone, Default)]
+    struct MockOsdrRepo {
+        stored: i64,
+    }
 
     #[async_trait]
     impl OsdrRepo for MockOsdrRepo {
@@ -149,32 +187,48 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_osdr_items_page(&self, _limit: i64, _offset: i64) -> crate::repo::Result<Vec<OsdrItem>> {
+            Ok(vec![])
+        }
+
         async fn get_osdr_item_by_id(&self, _dataset_id: &str) -> crate::repo::Result<Option<OsdrItem>> {
             Ok(None)
         }
 
         async fn count_osdr_items(&self) -> crate::repo::Result<i64> {
-            Ok(0)
+            Ok(self.stored)
         }
     }
 
-    #[test]
-    fn test_parse_osdr_response_array() {
+    async fn parse_osdr_response(json: Value) -> Vec<Value> {
+        let mut items = ItemStream::from_value(json);
+        let mut all = Vec::new();
+        while let Some(item) = items.next().await {
+            all.push(item.unwrap());
+        }
+        all
+    }
+
+    #[tokio::test]
+    async fn test_parse_osdr_response_array() {
         let json = serde_json::json!([
             {"dataset_id": "1", "title": "Test 1"},
             {"dataset_id": "2", "title": "Test 2"}
         ]);
-        let result = parse_osdr_response(json);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), 2);
+        assert_eq!(parse_osdr_response(json).await.len(), 2);
     }
 
-    #[test]
-    fn test_parse_osdr_response_single() {
+    #[tokio::test]
+    async fn test_parse_osdr_response_single() {
         let json = serde_json::json!({"dataset_id": "1", "title": "Test"});
-        let result = parse_osdr_response(json);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), 1);
+        assert_eq!(parse_osdr_response(json).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parse_osdr_response_keyed_by_accession() {
+        let json = serde_json::json!({"OSD-1": {"REST_URL": "a"}, "OSD-2": {"REST_URL": "b"}});
+        let items = parse_osdr_response(json).await;
+        assert_eq!(extract_osdr_item_fields(&items[1]).dataset_id, Some("OSD-2".to_string()));
     }
 
     #[test]
@@ -193,44 +247,103 @@ mod tests {
     }
 
     // Mock client for testing
-    #[derive(Clone)]
-    struct MockNasaClient;
+    #[derive(Clone, Default)]
+    struct MockNasaClient {
+        not_modified: bool,
+    }
 
     #[async_trait]
     impl NasaClient for MockNasaClient {
-        async fn fetch_osdr_datasets(&self) -> ClientResult<Value> {
-            Ok(serde_json::json!([{"dataset_id": "1", "title": "Test"}]))
+        async fn fetch_osdr_datasets(&self) -> ClientResult<Fetched> {
+            let value = serde_json::json!([{"dataset_id": "1", "title": "Test"}]);
+            Ok(if self.not_modified { Fetched::unchanged(value) } else { Fetched::fresh(value) })
         }
 
-        async fn fetch_apod(&self, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!({"title": "Test APOD"}))
+        async fn fetch_apod(&self, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({"title": "Test APOD"})))
         }
 
-        async fn fetch_neo_feed(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!({"near_earth_objects": {}}))
+        async fn fetch_neo_feed(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!({"near_earth_objects": {}})))
         }
 
-        async fn fetch_donki_flr(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!([]))
+        async fn fetch_donki_flr(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!([])))
         }
 
-        async fn fetch_donki_cme(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Value> {
-            Ok(serde_json::json!([]))
+        async fn fetch_donki_cme(&self, _start_date: &str, _end_date: &str, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!([])))
         }
     }
 
     #[tokio::test]
     async fn test_get_osdr_items() {
-        let service = OsdrServiceImpl::new(MockOsdrRepo, MockNasaClient);
+        let service = OsdrServiceImpl::new(MockOsdrRepo::default(), MockNasaClient::default());
         let result = service.get_osdr_items(10).await;
         assert!(result.is_ok());
     }
 
     #[tokio::test]
     async fn test_sync_osdr_data() {
-        let service = OsdrServiceImpl::new(MockOsdrRepo, MockNasaClient);
-        let result = service.sync_osdr_data("dummy_url").await;
+        let service = OsdrServiceImpl::new(MockOsdrRepo::default(), MockNasaClient::default());
+        let result = service.sync_osdr_data().await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 1);
     }
+
+    #[tokio::test]
+    async fn test_not_modified_dataset_list_is_not_rewritten() {
+        let client = MockNasaClient { not_modified: true };
+        let service = OsdrServiceImpl::new(MockOsdrRepo { stored: 3 }, client);
+        assert_eq!(service.sync_osdr_data().await.unwrap(), 0);
+    }
+
+    /// Streams a list that breaks off after its first item
+    #[derive(Clone, Default)]
+    struct TruncatedNasaClient(MockNasaClient);
+
+    #[async_trait]
+    impl NasaClient for TruncatedNasaClient {
+        async fn fetch_osdr_datasets(&self) -> ClientResult<Fetched> {
+            self.0.fetch_osdr_datasets().await
+        }
+
+        async fn stream_osdr_datasets(&self) -> ClientResult<FetchedItems> {
+            let body = bytes::Bytes::from_static(br#"[{"dataset_id": "1"}, {"dataset_id": "#);
+            Ok(FetchedItems { items: ItemStream::from_bytes(body), changed: true })
+        }
+
+        async fn fetch_apod(&self, api_key: Option<&str>) -> ClientResult<Fetched> {
+            self.0.fetch_apod(api_key).await
+        }
+
+        async fn fetch_neo_feed(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Fetched> {
+            self.0.fetch_neo_feed(start_date, end_date, api_key).await
+        }
+
+        async fn fetch_donki_flr(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Fetched> {
+            self.0.fetch_donki_flr(start_date, end_date, api_key).await
+        }
+
+        async fn fetch_donki_cme(&self, start_date: &str, end_date: &str, api_key: Option<&str>) -> ClientResult<Fetched> {
+            self.0.fetch_donki_cme(start_date, end_date, api_key).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_stops_at_malformed_item() {
+        let service = OsdrServiceImpl::new(MockOsdrRepo::default(), TruncatedNasaClient::default());
+        let err = service.sync_osdr_data().await.unwrap_err();
+        assert!(matches!(err, ServiceError::ExternalApiError(_)));
+        assert!(err.to_string().contains("Failed to read OSDR data"));
+    }
+
+    #[tokio::test]
+    async fn test_get_osdr_page() {
+        let service = OsdrServiceImpl::new(MockOsdrRepo::default(), MockNasaClient::default());
+        let page = service.get_osdr_page(2, 20).await.unwrap();
+        assert_eq!(page.page, 2);
+        assert_eq!(page.total, 0);
+        assert!(service.get_osdr_page(0, 20).await.is_err());
+    }
 }
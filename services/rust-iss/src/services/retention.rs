@@ -0,0 +1,296 @@
+use async_trait::async_trait;
+use chrono::{DateTime, DurationRound, Utc};
+use tracing::{error, info, warn};
+
+use crate::config::RetentionConfig;
+use crate::domain::*;
+use crate::repo::*;
+use crate::services::*;
+
+/// Months of iss_fetch_log partitions kept ready beyond the current one
+const ISS_PARTITIONS_AHEAD: u32 = 2;
+
+/// Implementation of the retention and compaction Service
+#[derive(Clone)]
+pub struct RetentionServiceImpl<R: RetentionRepo + Clone> {
+    repo: R,
+    policy: RetentionConfig,
+}
+
+impl<R: RetentionRepo + Clone> RetentionServiceImpl<R> {
+    pub fn new(repo: R, policy: RetentionConfig) -> Self {
+        Self { repo, policy }
+    }
+
+    /// Add a finished step to the report, or its error to `errors`
+    fn record(report: &mut CompactionReport, what: &str, result: crate::repo::Result<CompactionStep>) {
+        match result {
+            Ok(step) => report.steps.push(step),
+            Err(e) => {
+                error!("Compaction step '{}' failed: {}", what, e);
+                report.errors.push(format!("{}: {}", what, e));
+            }
+        }
+    }
+
+    async fn compact_iss(&self, report: &mut CompactionReport, now: DateTime<Utc>) {
+        // Only whole hours are downsampled so an hour is aggregated in one go
+        if let Some(before) = cutoff(now, self.policy.iss_raw_days).and_then(|t| t.duration_trunc(chrono::Duration::hours(1)).ok()) {
+            let result = self.repo.downsample_iss_positions(before).await;
+            Self::record(report, "downsample iss_fetch_log", result);
+        }
+        if let Some(before) = cutoff(now, self.policy.iss_hourly_days) {
+            let result = self.repo.expire_iss_hourly(before).await;
+            Self::record(report, "expire iss_position_hourly", result);
+        }
+    }
+
+    async fn compact_space_cache(&self, report: &mut CompactionReport, now: DateTime<Utc>) {
+        if self.policy.dedupe_space_cache {
+            match self.repo.dedupe_space_cache().await {
+                Ok(steps) => report.steps.extend(steps),
+                Err(e) => {
+                    error!("Compaction step 'dedupe space_cache' failed: {}", e);
+                    report.errors.push(format!("dedupe space_cache: {}", e));
+                }
+            }
+        }
+
+        let sources = match self.repo.get_space_cache_sources().await {
+            Ok(sources) => sources,
+            Err(e) => {
+                error!("Failed to list space_cache sources: {}", e);
+                report.errors.push(format!("list space_cache sources: {}", e));
+                return;
+            }
+        };
+        for source in sources {
+            let Some(before) = cutoff(now, self.policy.space_cache_days_for(&source)) else {
+                continue;
+            };
+            let result = self.repo.expire_space_cache(&source, before).await;
+            Self::record(report, &format!("expire space_cache/{}", source), result);
+        }
+    }
+
+    async fn table_sizes(&self) -> Vec<(String, i64)> {
+        let mut sizes = Vec::new();
+        for table in COMPACTED_TABLES {
+            match self.repo.table_size(table).await {
+                Ok(bytes) => sizes.push((table.to_string(), bytes)),
+                Err(e) => warn!("Failed to read size of {}: {}", table, e),
+            }
+        }
+        sizes
+    }
+}
+
+/// Start of the retention window, or `None` when rows are kept forever
+fn cutoff(now: DateTime<Utc>, days: u64) -> Option<DateTime<Utc>> {
+    if days == 0 {
+        return None;
+    }
+    now.checked_sub_days(chrono::Days::new(days))
+}
+
+#[async_trait]
+impl<R: RetentionRepo + Clone + Send + Sync> RetentionService for RetentionServiceImpl<R> {
+    async fn run_compaction(&self) -> crate::services::Result<CompactionReport> {
+        let started_at = Utc::now();
+        let before = self.table_sizes().await;
+        let mut report = CompactionReport {
+            id: None,
+            started_at,
+            finished_at: started_at,
+            rows_removed: 0,
+            bytes_reclaimed: 0,
+            steps: Vec::new(),
+            tables: Vec::new(),
+            partitions_created: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        match self.ensure_partitions().await {
+            Ok(created) => report.partitions_created = created,
+            Err(e) => report.errors.push(format!("create iss_fetch_log partitions: {}", e)),
+        }
+        self.compact_iss(&mut report, started_at).await;
+        self.compact_space_cache(&mut report, started_at).await;
+
+        // Make the freed space reusable and refresh planner statistics
+        if self.policy.vacuum {
+            for table in COMPACTED_TABLES {
+                let touched = report.steps.iter().any(|s| s.table == table && (s.rows_removed > 0 || s.rows_written > 0));
+                if touched {
+                    if let Err(e) = self.repo.vacuum_table(table).await {
+                        warn!("VACUUM {} failed: {}", table, e);
+                        report.errors.push(format!("vacuum {}: {}", table, e));
+                    }
+                }
+            }
+        }
+
+        let after = self.table_sizes().await;
+        report.tables = before.into_iter().map(|(table, bytes_before)| {
+            let bytes_after = after.iter().find(|(t, _)| *t == table).map(|(_, b)| *b).unwrap_or(bytes_before);
+            TableSize { table, bytes_before, bytes_after }
+        }).collect();
+        report.rows_removed = report.steps.iter().map(|s| s.rows_removed).sum();
+        report.bytes_reclaimed = report.steps.iter().map(|s| s.bytes_freed).sum();
+        report.finished_at = Utc::now();
+
+        match self.repo.insert_compaction_run(&report).await {
+            Ok(id) => report.id = Some(id),
+            Err(e) => warn!("Failed to record compaction run: {}", e),
+        }
+        info!(
+            "Compaction removed {} rows ({} bytes) in {} steps, {} errors",
+            report.rows_removed, report.bytes_reclaimed, report.steps.len(), report.errors.len()
+        );
+        Ok(report)
+    }
+
+    async fn ensure_partitions(&self) -> crate::services::Result<Vec<String>> {
+        let created = self.repo.ensure_iss_partitions(Utc::now(), ISS_PARTITIONS_AHEAD).await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+        if !created.is_empty() {
+            info!("Created iss_fetch_log partitions: {:?}", created);
+        }
+        Ok(created)
+    }
+
+    async fn get_compaction_runs(&self, limit: i64) -> crate::services::Result<Vec<CompactionReport>> {
+        self.repo.get_compaction_runs(limit).await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct MockRetentionRepo {
+        calls: Arc<Mutex<Vec<String>>>,
+        runs: Arc<Mutex<Vec<CompactionReport>>>,
+    }
+
+    impl MockRetentionRepo {
+        fn call(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
+        }
+    }
+
+    #[async_trait]
+    impl RetentionRepo for MockRetentionRepo {
+        async fn ensure_iss_partitions(&self, _from: Timestamp, months_ahead: u32) -> crate::repo::Result<Vec<String>> {
+            self.call(format!("ensure partitions +{}", months_ahead));
+            Ok(vec!["iss_fetch_log_p2026_05".to_string()])
+        }
+
+        async fn downsample_iss_positions(&self, before: Timestamp) -> crate::repo::Result<CompactionStep> {
+            self.call(format!("downsample {}", before.to_rfc3339()));
+            let mut step = CompactionStep::new("iss_fetch_log", None, CompactionAction::Downsample);
+            step.rows_removed = 30;
+            step.rows_written = 1;
+            step.bytes_freed = 3000;
+            step.partitions_dropped = vec!["iss_fetch_log_p2026_01".to_string()];
+            Ok(step)
+        }
+
+        async fn expire_iss_hourly(&self, _before: Timestamp) -> crate::repo::Result<CompactionStep> {
+            self.call("expire hourly".to_string());
+            Ok(CompactionStep::new("iss_position_hourly", None, CompactionAction::Expire))
+        }
+
+        async fn dedupe_space_cache(&self) -> crate::repo::Result<Vec<CompactionStep>> {
+            self.call("dedupe".to_string());
+            Err(RepoError::DatabaseError("deadlock detected".to_string()))
+        }
+
+        async fn get_space_cache_sources(&self) -> crate::repo::Result<Vec<String>> {
+            Ok(vec!["apod".to_string(), "flr".to_string()])
+        }
+
+        async fn expire_space_cache(&self, source: &str, _before: Timestamp) -> crate::repo::Result<CompactionStep> {
+            self.call(format!("expire {}", source));
+            let mut step = CompactionStep::new("space_cache", Some(source), CompactionAction::Expire);
+            step.rows_removed = 2;
+            step.bytes_freed = 200;
+            Ok(step)
+        }
+
+        async fn table_size(&self, _table: &str) -> crate::repo::Result<i64> {
+            Ok(8192)
+        }
+
+        async fn vacuum_table(&self, table: &str) -> crate::repo::Result<()> {
+            self.call(format!("vacuum {}", table));
+            Ok(())
+        }
+
+        async fn insert_compaction_run(&self, report: &CompactionReport) -> crate::repo::Result<i64> {
+            self.runs.lock().unwrap().push(report.clone());
+            Ok(7)
+        }
+
+        async fn get_compaction_runs(&self, _limit: i64) -> crate::repo::Result<Vec<CompactionReport>> {
+            Ok(self.runs.lock().unwrap().clone())
+        }
+    }
+
+    fn policy() -> RetentionConfig {
+        RetentionConfig {
+            compaction_interval: 86400,
+            iss_raw_days: 7,
+            iss_hourly_days: 0,
+            space_cache_days: 30,
+            space_cache_source_days: HashMap::from([("flr".to_string(), 0)]),
+            dedupe_space_cache: true,
+            vacuum: true,
+        }
+    }
+
+    #[test]
+    fn test_cutoff() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T12:34:56Z").unwrap().with_timezone(&Utc);
+        assert_eq!(cutoff(now, 0), None);
+        assert_eq!(cutoff(now, 7).unwrap().to_rfc3339(), "2026-03-03T12:34:56+00:00");
+    }
+
+    #[tokio::test]
+    async fn test_run_compaction_applies_policy_and_reports() {
+        let repo = MockRetentionRepo::default();
+        let service = RetentionServiceImpl::new(repo.clone(), policy());
+
+        let report = service.run_compaction().await.unwrap();
+        let calls = repo.calls.lock().unwrap().clone();
+
+        // Partitions are prepared before anything is removed
+        assert_eq!(calls[0], format!("ensure partitions +{}", ISS_PARTITIONS_AHEAD));
+        // Downsampling cutoff is truncated to a whole hour
+        assert!(calls[1].starts_with("downsample ") && calls[1].ends_with(":00:00+00:00"));
+        // Hourly aggregates and flr are kept forever
+        assert!(!calls.contains(&"expire hourly".to_string()));
+        assert!(calls.contains(&"expire apod".to_string()));
+        assert!(!calls.contains(&"expire flr".to_string()));
+        // Only touched tables are vacuumed
+        assert!(calls.contains(&"vacuum iss_fetch_log".to_string()));
+        assert!(calls.contains(&"vacuum space_cache".to_string()));
+        assert!(!calls.contains(&"vacuum iss_position_hourly".to_string()));
+
+        assert_eq!(report.id, Some(7));
+        assert_eq!(report.rows_removed, 32);
+        assert_eq!(report.bytes_reclaimed, 3200);
+        assert_eq!(report.steps.len(), 2);
+        assert_eq!(report.partitions_created, vec!["iss_fetch_log_p2026_05"]);
+        assert_eq!(report.steps[0].partitions_dropped, vec!["iss_fetch_log_p2026_01"]);
+        assert_eq!(report.tables.len(), COMPACTED_TABLES.len());
+        // A failing step is reported without aborting the run
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].contains("deadlock"));
+        assert_eq!(service.get_compaction_runs(10).await.unwrap().len(), 1);
+    }
+}
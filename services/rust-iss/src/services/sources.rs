@@ -0,0 +1,405 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::clients::{Fetched, NasaClient, QuotaPriority, SpaceXClient, Result as ClientResult};
+use crate::domain::{DomainError, SpaceCache};
+
+/// An upstream feed stored in `space_cache`.
+///
+/// Implementing this and registering it in the [`SourceRegistry`] is all it
+/// takes for a feed to be scheduled, refreshable, summarised and validated.
+#[async_trait]
+pub trait SpaceSource: Send + Sync {
+    /// Key used in `space_cache.source`, URLs and cache keys
+    fn name(&self) -> &'static str;
+    /// Human readable upstream name used in errors
+    fn title(&self) -> &'static str;
+    /// Conditional fetch; an unchanged payload only refreshes the stored entry
+    async fn fetch(&self, api_key: Option<&str>) -> ClientResult<Fetched>;
+    /// Check that a payload has the shape this source produces
+    fn validate(&self, payload: &Value) -> Result<(), DomainError>;
+    /// How often the background task fetches this source
+    fn schedule(&self) -> Duration;
+    /// Default hard cache TTL, overridable with `CACHE_TTL_<NAME>`; entries go
+    /// stale at its soft share (`CACHE_SOFT_TTL_RATIO`, `CACHE_SOFT_TTL_<NAME>`)
+    fn ttl(&self) -> Duration;
+    /// Share of the NASA API quota this source may draw on; `None` when it needs no NASA key
+    fn quota_priority(&self) -> Option<QuotaPriority> {
+        None
+    }
+}
+
+/// Ordered set of registered space sources
+#[derive(Clone, Default)]
+pub struct SourceRegistry {
+    sources: Vec<Arc<dyn SpaceSource>>,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a source, replacing any earlier one with the same name
+    pub fn register(mut self, source: impl SpaceSource + 'static) -> Self {
+        self.sources.retain(|s| s.name() != source.name());
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn SpaceSource>> {
+        self.sources.iter().find(|s| s.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn SpaceSource>> {
+        self.sources.iter()
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.sources.iter().map(|s| s.name()).collect()
+    }
+
+    /// Validate an entry against the source it claims to come from
+    pub fn validate(&self, entry: &SpaceCache) -> Result<(), DomainError> {
+        entry.validate()?;
+        let source = self.get(&entry.source).ok_or_else(|| {
+            DomainError::ValidationError(format!(
+                "source '{}' is not a valid source. Valid sources: {}",
+                entry.source,
+                self.names().join(", ")
+            ))
+        })?;
+        source.validate(&entry.payload)
+    }
+}
+
+fn expect_object(source: &str, payload: &Value) -> Result<(), DomainError> {
+    if !payload.is_object() {
+        return Err(DomainError::ValidationError(format!("{} payload must be a JSON object", source)));
+    }
+    Ok(())
+}
+
+fn expect_array(source: &str, payload: &Value) -> Result<(), DomainError> {
+    if !payload.is_array() {
+        return Err(DomainError::ValidationError(format!("{} payload must be a JSON array", source)));
+    }
+    Ok(())
+}
+
+/// `(start, end)` dates covering the last `days` days
+fn last_days(days: u64) -> (String, String) {
+    let to = Utc::now().date_naive();
+    let from = to - chrono::Days::new(days);
+    (from.to_string(), to.to_string())
+}
+
+/// NASA Astronomy Picture of the Day
+#[derive(Clone)]
+pub struct ApodSource<N: NasaClient> {
+    client: N,
+    schedule: Duration,
+}
+
+impl<N: NasaClient> ApodSource<N> {
+    pub fn new(client: N) -> Self {
+        Self { client, schedule: Duration::from_secs(43200) }
+    }
+
+    pub fn with_schedule(mut self, schedule: Duration) -> Self {
+        self.schedule = schedule;
+        self
+    }
+}
+
+#[async_trait]
+impl<N: NasaClient + Send + Sync> SpaceSource for ApodSource<N> {
+    fn name(&self) -> &'static str {
+        "apod"
+    }
+
+    fn title(&self) -> &'static str {
+        "APOD API"
+    }
+
+    async fn fetch(&self, api_key: Option<&str>) -> ClientResult<Fetched> {
+        self.client.fetch_apod(api_key).await
+    }
+
+    fn validate(&self, payload: &Value) -> Result<(), DomainError> {
+        expect_object(self.name(), payload)
+    }
+
+    fn quota_priority(&self) -> Option<QuotaPriority> {
+        Some(QuotaPriority::High)
+    }
+
+    fn schedule(&self) -> Duration {
+        self.schedule
+    }
+
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(3600)
+    }
+}
+
+/// NASA NeoWs feed for the last two days
+#[derive(Clone)]
+pub struct NeoFeedSource<N: NasaClient> {
+    client: N,
+    schedule: Duration,
+}
+
+impl<N: NasaClient> NeoFeedSource<N> {
+    pub fn new(client: N) -> Self {
+        Self { client, schedule: Duration::from_secs(7200) }
+    }
+
+    pub fn with_schedule(mut self, schedule: Duration) -> Self {
+        self.schedule = schedule;
+        self
+    }
+}
+
+#[async_trait]
+impl<N: NasaClient + Send + Sync> SpaceSource for NeoFeedSource<N> {
+    fn name(&self) -> &'static str {
+        "neo"
+    }
+
+    fn title(&self) -> &'static str {
+        "NEO API"
+    }
+
+    async fn fetch(&self, api_key: Option<&str>) -> ClientResult<Fetched> {
+        let (start, end) = last_days(2);
+        self.client.fetch_neo_feed(&start, &end, api_key).await
+    }
+
+    fn validate(&self, payload: &Value) -> Result<(), DomainError> {
+        expect_object(self.name(), payload)
+    }
+
+    fn quota_priority(&self) -> Option<QuotaPriority> {
+        Some(QuotaPriority::Normal)
+    }
+
+    fn schedule(&self) -> Duration {
+        self.schedule
+    }
+
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(3600)
+    }
+}
+
+/// NASA DONKI solar flares for the last five days
+#[derive(Clone)]
+pub struct DonkiFlrSource<N: NasaClient> {
+    client: N,
+    schedule: Duration,
+}
+
+impl<N: NasaClient> DonkiFlrSource<N> {
+    pub fn new(client: N) -> Self {
+        Self { client, schedule: Duration::from_secs(3600) }
+    }
+
+    pub fn with_schedule(mut self, schedule: Duration) -> Self {
+        self.schedule = schedule;
+        self
+    }
+}
+
+#[async_trait]
+impl<N: NasaClient + Send + Sync> SpaceSource for DonkiFlrSource<N> {
+    fn name(&self) -> &'static str {
+        "flr"
+    }
+
+    fn title(&self) -> &'static str {
+        "DONKI FLR"
+    }
+
+    async fn fetch(&self, api_key: Option<&str>) -> ClientResult<Fetched> {
+        let (start, end) = last_days(5);
+        self.client.fetch_donki_flr(&start, &end, api_key).await
+    }
+
+    fn validate(&self, payload: &Value) -> Result<(), DomainError> {
+        expect_array(self.name(), payload)
+    }
+
+    fn quota_priority(&self) -> Option<QuotaPriority> {
+        Some(QuotaPriority::Normal)
+    }
+
+    fn schedule(&self) -> Duration {
+        self.schedule
+    }
+
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(1800)
+    }
+}
+
+/// NASA DONKI coronal mass ejections for the last five days
+#[derive(Clone)]
+pub struct DonkiCmeSource<N: NasaClient> {
+    client: N,
+    schedule: Duration,
+}
+
+impl<N: NasaClient> DonkiCmeSource<N> {
+    pub fn new(client: N) -> Self {
+        Self { client, schedule: Duration::from_secs(3600) }
+    }
+
+    pub fn with_schedule(mut self, schedule: Duration) -> Self {
+        self.schedule = schedule;
+        self
+    }
+}
+
+#[async_trait]
+impl<N: NasaClient + Send + Sync> SpaceSource for DonkiCmeSource<N> {
+    fn name(&self) -> &'static str {
+        "cme"
+    }
+
+    fn title(&self) -> &'static str {
+        "DONKI CME"
+    }
+
+    async fn fetch(&self, api_key: Option<&str>) -> ClientResult<Fetched> {
+        let (start, end) = last_days(5);
+        self.client.fetch_donki_cme(&start, &end, api_key).await
+    }
+
+    fn validate(&self, payload: &Value) -> Result<(), DomainError> {
+        expect_array(self.name(), payload)
+    }
+
+    fn quota_priority(&self) -> Option<QuotaPriority> {
+        Some(QuotaPriority::Normal)
+    }
+
+    fn schedule(&self) -> Duration {
+        self.schedule
+    }
+
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(1800)
+    }
+}
+
+/// Next SpaceX launch
+#[derive(Clone)]
+pub struct SpaceXNextSource<S: SpaceXClient> {
+    client: S,
+    schedule: Duration,
+}
+
+impl<S: SpaceXClient> SpaceXNextSource<S> {
+    pub fn new(client: S) -> Self {
+        Self { client, schedule: Duration::from_secs(3600) }
+    }
+
+    pub fn with_schedule(mut self, schedule: Duration) -> Self {
+        self.schedule = schedule;
+        self
+    }
+}
+
+#[async_trait]
+impl<S: SpaceXClient + Send + Sync> SpaceSource for SpaceXNextSource<S> {
+    fn name(&self) -> &'static str {
+        "spacex"
+    }
+
+    fn title(&self) -> &'static str {
+        "SpaceX API"
+    }
+
+    async fn fetch(&self, _api_key: Option<&str>) -> ClientResult<Fetched> {
+        self.client.fetch_next_launch().await
+    }
+
+    fn validate(&self, payload: &Value) -> Result<(), DomainError> {
+        expect_object(self.name(), payload)
+    }
+
+    fn schedule(&self) -> Duration {
+        self.schedule
+    }
+
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(3600)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticSource(&'static str);
+
+    #[async_trait]
+    impl SpaceSource for StaticSource {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn title(&self) -> &'static str {
+            "Static"
+        }
+
+        async fn fetch(&self, _api_key: Option<&str>) -> ClientResult<Fetched> {
+            Ok(Fetched::fresh(serde_json::json!([])))
+        }
+
+        fn validate(&self, payload: &Value) -> Result<(), DomainError> {
+            expect_array(self.name(), payload)
+        }
+
+        fn schedule(&self) -> Duration {
+            Duration::from_secs(60)
+        }
+
+        fn ttl(&self) -> Duration {
+            Duration::from_secs(60)
+        }
+    }
+
+    #[test]
+    fn test_last_days() {
+        let (from, to) = last_days(5);
+        assert!(!from.is_empty());
+        assert!(!to.is_empty());
+        assert!(from < to);
+    }
+
+    #[test]
+    fn test_registry_keeps_order_and_replaces_duplicates() {
+        let registry = SourceRegistry::new()
+            .register(StaticSource("flr"))
+            .register(StaticSource("cme"))
+            .register(StaticSource("flr"));
+        assert_eq!(registry.names(), vec!["cme", "flr"]);
+        assert!(registry.get("cme").is_some());
+        assert!(registry.get("apod").is_none());
+    }
+
+    #[test]
+    fn test_registry_validates_known_sources_and_payloads() {
+        let registry = SourceRegistry::new().register(StaticSource("flr"));
+        assert!(registry.validate(&SpaceCache::new("flr".to_string(), serde_json::json!([]))).is_ok());
+        assert!(registry.validate(&SpaceCache::new("flr".to_string(), serde_json::json!({}))).is_err());
+
+        let unknown = registry.validate(&SpaceCache::new("invalid_source".to_string(), serde_json::json!([])));
+        assert!(unknown.unwrap_err().to_string().contains("Valid sources: flr"));
+    }
+}
@@ -0,0 +1,566 @@
+use async_trait::async_trait;
+use calamine::{open_workbook_from_rs, Data, Reader, Xlsx};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{error, info, warn};
+
+use crate::domain::*;
+use crate::repo::*;
+use crate::services::*;
+
+/// Columns every legacy telemetry file must provide
+const REQUIRED_COLUMNS: [&str; 4] = ["recorded_at", "voltage", "temp", "is_valid"];
+
+/// Columns that are accepted but ignored; the on-disk file name is the ingestion key
+const OPTIONAL_COLUMNS: [&str; 1] = ["source_file"];
+
+/// Maximum number of out-of-range readings returned with a summary
+const MAX_FLAGGED_READINGS: i64 = 500;
+
+/// Supported telemetry file formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TelemetryFormat {
+    Csv,
+    Xlsx,
+}
+
+impl TelemetryFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+/// Implementation of legacy telemetry ingestion Service
+#[derive(Clone)]
+pub struct TelemetryServiceImpl<R: TelemetryRepo + Clone> {
+    repo: R,
+    drop_dir: PathBuf,
+    archive_dir: PathBuf,
+    quarantine_dir: PathBuf,
+    settle_time: Duration,
+    envelope: TelemetryEnvelope,
+}
+
+impl<R: TelemetryRepo + Clone> TelemetryServiceImpl<R> {
+    pub fn new(repo: R) -> Self {
+        Self {
+            repo,
+            drop_dir: PathBuf::from("/data/csv"),
+            archive_dir: PathBuf::from("/data/csv/archive"),
+            quarantine_dir: PathBuf::from("/data/csv/quarantine"),
+            settle_time: Duration::from_secs(5),
+            envelope: TelemetryEnvelope::default(),
+        }
+    }
+
+    /// Configure the drop directory and where processed and rejected files are moved
+    pub fn with_directories(mut self, drop_dir: PathBuf, archive_dir: PathBuf, quarantine_dir: PathBuf) -> Self {
+        self.drop_dir = drop_dir;
+        self.archive_dir = archive_dir;
+        self.quarantine_dir = quarantine_dir;
+        self
+    }
+
+    /// Skip files modified more recently than this (the writer may not be done yet)
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// Set the voltage and temperature range outside of which readings are flagged
+    pub fn with_envelope(mut self, envelope: TelemetryEnvelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// List telemetry files in the drop directory that are ready for ingestion
+    async fn pending_files(&self) -> std::io::Result<Vec<(PathBuf, TelemetryFormat)>> {
+        let mut files = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.drop_dir).await?;
+        let now = SystemTime::now();
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            // Dot-files are temporary files still being written
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Some(format) = TelemetryFormat::from_path(&path) else {
+                continue;
+            };
+            let age = metadata.modified().ok().and_then(|m| now.duration_since(m).ok()).unwrap_or_default();
+            if age < self.settle_time {
+                continue;
+            }
+            files.push((path, format));
+        }
+
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(files)
+    }
+
+    /// Process a single file, moving it to the archive or quarantine directory
+    async fn ingest_file(&self, path: &Path, format: TelemetryFormat, report: &mut TelemetryIngestReport) {
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+        let parsed = match tokio::fs::read(path).await {
+            Ok(bytes) => match format {
+                TelemetryFormat::Csv => parse_csv(&bytes, &file_name),
+                TelemetryFormat::Xlsx => parse_xlsx(&bytes, &file_name),
+            },
+            Err(e) => {
+                error!("Failed to read telemetry file {}: {}", file_name, e);
+                report.files_failed += 1;
+                return;
+            }
+        };
+
+        let readings = match parsed {
+            Ok(readings) => readings,
+            Err(e) => {
+                warn!("Quarantining telemetry file {}: {}", file_name, e);
+                match move_file(path, &self.quarantine_dir).await {
+                    Ok(target) => {
+                        let note = target.with_file_name(format!("{}.error.txt", target.file_name().unwrap_or_default().to_string_lossy()));
+                        if let Err(e) = tokio::fs::write(&note, format!("{}\n", e)).await {
+                            warn!("Failed to write quarantine note for {}: {}", file_name, e);
+                        }
+                        report.files_quarantined += 1;
+                    }
+                    Err(e) => {
+                        error!("Failed to quarantine telemetry file {}: {}", file_name, e);
+                        report.files_failed += 1;
+                    }
+                }
+                return;
+            }
+        };
+
+        // Database errors leave the file in place so the next scan retries it
+        let inserted = match self.repo.insert_telemetry_file(&file_name, &readings).await {
+            Ok(inserted) => inserted,
+            Err(e) => {
+                error!("Failed to store telemetry file {}: {}", file_name, e);
+                report.files_failed += 1;
+                return;
+            }
+        };
+
+        match inserted {
+            Some(rows) => {
+                info!("Ingested {} telemetry readings from {}", rows, file_name);
+                report.rows_inserted += rows;
+            }
+            None => {
+                info!("Telemetry file {} was already ingested, archiving", file_name);
+                report.files_duplicate += 1;
+            }
+        }
+
+        match move_file(path, &self.archive_dir).await {
+            Ok(_) => report.files_archived += 1,
+            Err(e) => {
+                error!("Failed to archive telemetry file {}: {}", file_name, e);
+                report.files_failed += 1;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<R: TelemetryRepo + Clone + Sync + Send> TelemetryService for TelemetryServiceImpl<R> {
+    async fn ingest_drop_directory(&self) -> crate::services::Result<TelemetryIngestReport> {
+        for dir in [&self.archive_dir, &self.quarantine_dir] {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| ServiceError::BusinessLogicError(format!("Cannot create {}: {}", dir.display(), e)))?;
+        }
+
+        let files = self.pending_files()
+            .await
+            .map_err(|e| ServiceError::BusinessLogicError(format!("Cannot scan {}: {}", self.drop_dir.display(), e)))?;
+
+        let mut report = TelemetryIngestReport::default();
+        for (path, format) in files {
+            self.ingest_file(&path, format, &mut report).await;
+        }
+        Ok(report)
+    }
+
+    async fn get_telemetry_summary(&self, from: DateTime<Utc>, to: DateTime<Utc>, bucket_seconds: i64) -> crate::services::Result<TelemetrySummary> {
+        if from >= to {
+            return Err(ServiceError::ValidationError("from must be before to".to_string()));
+        }
+        if bucket_seconds <= 0 {
+            return Err(ServiceError::ValidationError("bucket must be positive".to_string()));
+        }
+
+        let buckets = self.repo
+            .get_telemetry_buckets(from, to, bucket_seconds, &self.envelope)
+            .await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+        let flagged = self.repo
+            .get_out_of_range_readings(from, to, &self.envelope, MAX_FLAGGED_READINGS)
+            .await
+            .map_err(|e| ServiceError::RepositoryError(e.to_string()))?;
+
+        let readings: i64 = buckets.iter().map(|b| b.readings).sum();
+        let valid: i64 = buckets.iter().map(|b| b.valid_readings).sum();
+
+        Ok(TelemetrySummary {
+            from,
+            to,
+            bucket_seconds,
+            envelope: self.envelope,
+            readings,
+            validity_rate: (readings > 0).then(|| valid as f64 / readings as f64),
+            buckets: buckets.into_iter()
+                .map(|bucket| TelemetryBucketStats { validity_rate: bucket.validity_rate(), bucket })
+                .collect(),
+            out_of_range: flagged.into_iter()
+                .map(|reading| FlaggedReading { flags: self.envelope.violations(&reading), reading })
+                .collect(),
+        })
+    }
+}
+
+/// Move a file into `dir`, keeping its name unless a file with that name already exists there
+async fn move_file(path: &Path, dir: &Path) -> std::io::Result<PathBuf> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let mut target = dir.join(&name);
+    if tokio::fs::try_exists(&target).await.unwrap_or(false) {
+        target = dir.join(format!("{}.{}", name, Utc::now().format("%Y%m%d%H%M%S%3f")));
+    }
+    tokio::fs::rename(path, &target).await?;
+    Ok(target)
+}
+
+/// A raw cell value read from CSV or XLSX
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    DateTime(NaiveDateTime),
+    Empty,
+}
+
+/// Map header names to column indexes, rejecting missing, duplicate and unknown columns
+fn column_indexes(header: &[String]) -> crate::services::Result<[usize; 4]> {
+    let header: Vec<String> = header.iter().map(|h| h.trim().trim_start_matches('\u{feff}').to_ascii_lowercase()).collect();
+
+    for (i, name) in header.iter().enumerate() {
+        if !REQUIRED_COLUMNS.contains(&name.as_str()) && !OPTIONAL_COLUMNS.contains(&name.as_str()) {
+            return Err(ServiceError::ValidationError(format!("unknown column '{}'", name)));
+        }
+        if header[..i].contains(name) {
+            return Err(ServiceError::ValidationError(format!("duplicate column '{}'", name)));
+        }
+    }
+
+    let mut indexes = [0usize; 4];
+    for (slot, column) in indexes.iter_mut().zip(REQUIRED_COLUMNS) {
+        *slot = header.iter().position(|h| h == column)
+            .ok_or_else(|| ServiceError::ValidationError(format!("missing column '{}'", column)))?;
+    }
+    Ok(indexes)
+}
+
+/// Convert a data row into a validated reading
+fn parse_row(cells: &[Cell], indexes: &[usize; 4], line: usize, source_file: &str) -> crate::services::Result<TelemetryReading> {
+    let cell = |i: usize| cells.get(indexes[i]).unwrap_or(&Cell::Empty);
+    let invalid = |column: &str, e: String| ServiceError::ValidationError(format!("row {}: {}: {}", line, column, e));
+
+    let reading = TelemetryReading {
+        id: None,
+        recorded_at: parse_recorded_at(cell(0)).map_err(|e| invalid("recorded_at", e))?,
+        voltage: parse_number(cell(1)).map_err(|e| invalid("voltage", e))?,
+        temp: parse_number(cell(2)).map_err(|e| invalid("temp", e))?,
+        source_file: source_file.to_string(),
+        is_valid: parse_bool(cell(3)).map_err(|e| invalid("is_valid", e))?,
+    };
+
+    if reading.recorded_at > Utc::now() + chrono::Duration::days(1) {
+        return Err(invalid("recorded_at", format!("{} is in the future", reading.recorded_at)));
+    }
+    reading.validate().map_err(|e| ServiceError::ValidationError(format!("row {}: {}", line, e)))?;
+    Ok(reading)
+}
+
+/// Parse a legacy telemetry CSV file
+fn parse_csv(bytes: &[u8], source_file: &str) -> crate::services::Result<Vec<TelemetryReading>> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(bytes);
+
+    let header: Vec<String> = reader.headers()
+        .map_err(|e| ServiceError::ValidationError(format!("invalid header: {}", e)))?
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    let indexes = column_indexes(&header)?;
+
+    let mut readings = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let line = i + 2;
+        let record = record.map_err(|e| ServiceError::ValidationError(format!("row {}: {}", line, e)))?;
+        if record.iter().all(|v| v.is_empty()) {
+            continue;
+        }
+        let cells: Vec<Cell> = record.iter()
+            .map(|v| if v.is_empty() { Cell::Empty } else { Cell::Text(v.to_string()) })
+            .collect();
+        readings.push(parse_row(&cells, &indexes, line, source_file)?);
+    }
+
+    if readings.is_empty() {
+        return Err(ServiceError::ValidationError("file contains no readings".to_string()));
+    }
+    Ok(readings)
+}
+
+/// Parse the first worksheet of a legacy telemetry XLSX file
+fn parse_xlsx(bytes: &[u8], source_file: &str) -> crate::services::Result<Vec<TelemetryReading>> {
+    let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes))
+        .map_err(|e| ServiceError::ValidationError(format!("invalid workbook: {}", e)))?;
+    let range = workbook.worksheet_range_at(0)
+        .ok_or_else(|| ServiceError::ValidationError("workbook has no worksheets".to_string()))?
+        .map_err(|e| ServiceError::ValidationError(format!("invalid worksheet: {}", e)))?;
+
+    let mut rows = range.rows();
+    let header: Vec<String> = rows.next()
+        .ok_or_else(|| ServiceError::ValidationError("worksheet is empty".to_string()))?
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    let indexes = column_indexes(&header)?;
+
+    let mut readings = Vec::new();
+    for (i, row) in rows.enumerate() {
+        let line = i + 2;
+        let cells: Vec<Cell> = row.iter().map(cell_from_xlsx).collect();
+        if cells.iter().all(|c| *c == Cell::Empty) {
+            continue;
+        }
+        readings.push(parse_row(&cells, &indexes, line, source_file)?);
+    }
+
+    if readings.is_empty() {
+        return Err(ServiceError::ValidationError("worksheet contains no readings".to_string()));
+    }
+    Ok(readings)
+}
+
+fn cell_from_xlsx(data: &Data) -> Cell {
+    match data {
+        Data::Int(v) => Cell::Number(*v as f64),
+        Data::Float(v) => Cell::Number(*v),
+        Data::Bool(v) => Cell::Bool(*v),
+        Data::String(s) | Data::DateTimeIso(s) if s.trim().is_empty() => Cell::Empty,
+        Data::String(s) | Data::DateTimeIso(s) => Cell::Text(s.trim().to_string()),
+        Data::DateTime(dt) => dt.as_datetime().map(Cell::DateTime).unwrap_or(Cell::Empty),
+        Data::DurationIso(s) => Cell::Text(s.clone()),
+        Data::Error(e) => Cell::Text(format!("#{:?}", e)),
+        Data::Empty => Cell::Empty,
+    }
+}
+
+/// Accepts unix seconds, RFC 3339 and `YYYY-MM-DD HH:MM:SS` (UTC)
+fn parse_recorded_at(cell: &Cell) -> std::result::Result<DateTime<Utc>, String> {
+    match cell {
+        Cell::DateTime(dt) => Ok(Utc.from_utc_datetime(dt)),
+        Cell::Number(v) if v.fract() == 0.0 => Utc.timestamp_opt(*v as i64, 0).single()
+            .ok_or_else(|| format!("{} is not a valid unix timestamp", v)),
+        Cell::Text(s) => {
+            if let Ok(secs) = s.parse::<i64>() {
+                return Utc.timestamp_opt(secs, 0).single()
+                    .ok_or_else(|| format!("{} is not a valid unix timestamp", s));
+            }
+            if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+                return Ok(dt.with_timezone(&Utc));
+            }
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+                .map(|dt| Utc.from_utc_datetime(&dt))
+                .map_err(|_| format!("'{}' is not a unix timestamp or date-time", s))
+        }
+        Cell::Empty => Err("value is missing".to_string()),
+        other => Err(format!("unexpected value {:?}", other)),
+    }
+}
+
+fn parse_number(cell: &Cell) -> std::result::Result<f64, String> {
+    let value = match cell {
+        Cell::Number(v) => *v,
+        Cell::Text(s) => s.parse::<f64>().map_err(|_| format!("'{}' is not a number", s))?,
+        Cell::Empty => return Err("value is missing".to_string()),
+        other => return Err(format!("unexpected value {:?}", other)),
+    };
+    if !value.is_finite() {
+        return Err(format!("{} is not a finite number", value));
+    }
+    Ok(value)
+}
+
+fn parse_bool(cell: &Cell) -> std::result::Result<bool, String> {
+    match cell {
+        Cell::Bool(v) => Ok(*v),
+        Cell::Number(v) if *v == 0.0 || *v == 1.0 => Ok(*v == 1.0),
+        Cell::Text(s) => match s.to_ascii_lowercase().as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(format!("'{}' is not TRUE or FALSE", s)),
+        },
+        Cell::Empty => Err("value is missing".to_string()),
+        other => Err(format!("unexpected value {:?}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct MockTelemetryRepo {
+        files: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl TelemetryRepo for MockTelemetryRepo {
+        async fn insert_telemetry_file(&self, source_file: &str, readings: &[TelemetryReading]) -> crate::repo::Result<Option<usize>> {
+            let mut files = self.files.lock().unwrap();
+            if files.iter().any(|f| f == source_file) {
+                return Ok(None);
+            }
+            files.push(source_file.to_string());
+            Ok(Some(readings.len()))
+        }
+
+        async fn get_telemetry_buckets(&self, from: Timestamp, _to: Timestamp, _bucket_seconds: i64, _envelope: &TelemetryEnvelope) -> crate::repo::Result<Vec<TelemetryBucket>> {
+            Ok(vec![TelemetryBucket {
+                bucket_start: from,
+                readings: 4,
+                voltage_min: 2.0,
+                voltage_max: 12.0,
+                voltage_avg: 7.0,
+                temp_min: -10.0,
+                temp_max: 30.0,
+                temp_avg: 10.0,
+                valid_readings: 3,
+                out_of_range: 1,
+            }])
+        }
+
+        async fn get_out_of_range_readings(&self, from: Timestamp, _to: Timestamp, _envelope: &TelemetryEnvelope, _limit: i64) -> crate::repo::Result<Vec<TelemetryReading>> {
+            Ok(vec![TelemetryReading {
+                id: Some(1),
+                recorded_at: from,
+                voltage: 2.0,
+                temp: 20.0,
+                source_file: "telemetry.csv".to_string(),
+                is_valid: true,
+            }])
+        }
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = b"recorded_at,voltage,temp,is_valid,source_file\n1767225600,12.10,-20.5,TRUE,x.csv\n2026-01-01 00:05:00,3.3,80,false,x.csv\n";
+        let readings = parse_csv(csv, "telemetry_1.csv").unwrap();
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].recorded_at.timestamp(), 1767225600);
+        assert_eq!(readings[0].source_file, "telemetry_1.csv");
+        assert!(readings[0].is_valid);
+        assert_eq!(readings[1].recorded_at.timestamp(), 1767225900);
+        assert!(!readings[1].is_valid);
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_invalid_files() {
+        let missing = b"recorded_at,voltage,is_valid\n1767225600,12.1,TRUE\n";
+        assert!(parse_csv(missing, "f.csv").is_err());
+
+        let unknown = b"recorded_at,voltage,temp,is_valid,extra\n1767225600,12.1,1,TRUE,x\n";
+        assert!(parse_csv(unknown, "f.csv").is_err());
+
+        let bad_bool = b"recorded_at,voltage,temp,is_valid\n1767225600,12.1,1,maybe\n";
+        let err = parse_csv(bad_bool, "f.csv").unwrap_err().to_string();
+        assert!(err.contains("row 2") && err.contains("is_valid"));
+
+        let bad_number = b"recorded_at,voltage,temp,is_valid\n1767225600,NaN,1,TRUE\n";
+        assert!(parse_csv(bad_number, "f.csv").is_err());
+
+        let empty = b"recorded_at,voltage,temp,is_valid\n";
+        assert!(parse_csv(empty, "f.csv").is_err());
+    }
+
+    #[test]
+    fn test_xlsx_cell_conversion() {
+        assert_eq!(cell_from_xlsx(&Data::Float(12.5)), Cell::Number(12.5));
+        assert_eq!(cell_from_xlsx(&Data::Bool(true)), Cell::Bool(true));
+        assert_eq!(cell_from_xlsx(&Data::String(" ".to_string())), Cell::Empty);
+        assert_eq!(parse_bool(&cell_from_xlsx(&Data::Int(1))), Ok(true));
+        assert_eq!(
+            parse_recorded_at(&Cell::Text("2026-01-01T00:00:00Z".to_string())).unwrap().timestamp(),
+            1767225600
+        );
+        // Formula cells without cached values come back empty and must be rejected
+        assert!(parse_number(&cell_from_xlsx(&Data::Empty)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_telemetry_summary() {
+        let service = TelemetryServiceImpl::new(MockTelemetryRepo::default());
+        let to = Utc::now();
+        let from = to - chrono::Duration::hours(1);
+
+        let summary = service.get_telemetry_summary(from, to, 300).await.unwrap();
+        assert_eq!(summary.readings, 4);
+        assert_eq!(summary.validity_rate, Some(0.75));
+        assert_eq!(summary.buckets[0].validity_rate, 0.75);
+        assert_eq!(summary.out_of_range[0].flags, vec!["voltage_low"]);
+
+        assert!(service.get_telemetry_summary(to, from, 300).await.is_err());
+        assert!(service.get_telemetry_summary(from, to, 0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ingest_drop_directory() {
+        let root = std::env::temp_dir().join(format!("telemetry-test-{}", std::process::id()));
+        let drop_dir = root.join("drop");
+        tokio::fs::create_dir_all(&drop_dir).await.unwrap();
+        tokio::fs::write(drop_dir.join("good.csv"), "recorded_at,voltage,temp,is_valid\n1767225600,12.1,20,TRUE\n").await.unwrap();
+        tokio::fs::write(drop_dir.join("bad.csv"), "recorded_at,voltage\n1767225600,12.1\n").await.unwrap();
+        tokio::fs::write(drop_dir.join("notes.txt"), "ignored").await.unwrap();
+
+        let service = TelemetryServiceImpl::new(MockTelemetryRepo::default())
+            .with_directories(drop_dir.clone(), root.join("archive"), root.join("quarantine"))
+            .with_settle_time(Duration::ZERO);
+        let report = service.ingest_drop_directory().await.unwrap();
+
+        assert_eq!(report.files_archived, 1);
+        assert_eq!(report.files_quarantined, 1);
+        assert_eq!(report.rows_inserted, 1);
+        assert!(root.join("archive/good.csv").exists());
+        assert!(root.join("quarantine/bad.csv").exists());
+        assert!(root.join("quarantine/bad.csv.error.txt").exists());
+        assert!(drop_dir.join("notes.txt").exists());
+
+        // Re-dropping an already ingested file is archived without inserting again
+        tokio::fs::write(drop_dir.join("good.csv"), "recorded_at,voltage,temp,is_valid\n1767225600,12.1,20,TRUE\n").await.unwrap();
+        let report = service.ingest_drop_directory().await.unwrap();
+        assert_eq!(report.files_duplicate, 1);
+        assert_eq!(report.rows_inserted, 0);
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+}